@@ -20,6 +20,16 @@
  * ENVIRONMENT VARIABLES:
  * - PORT: Server port (default: 3007)
  * - N8N_WEBHOOK_URL: AI validation webhook endpoint
+ * - PRIORITY_FEE_PERCENTILE: Percentile of recent fees to pay (default: 75)
+ * - PRIORITY_FEE_CAP_MICROLAMPORTS: Max price per compute unit (default: 100000)
+ * - COMPUTE_UNIT_LIMIT: Compute units requested per transaction (default: 200000)
+ * - LOOKUP_CONCURRENCY: Max domain lookups in flight against RPC (default: 16)
+ * - LOOKUP_QUEUE_LIMIT: Lookups allowed to wait for a slot before 503 (default: 256)
+ * - CACHE_MAX_BYTES: Approximate memory budget for the resolve cache (default: 64 MiB)
+ * - CACHE_TTL_MS: Fresh lifetime of a found domain (default: 30000)
+ * - CACHE_NEGATIVE_TTL_MS: Fresh lifetime of a not-found answer (default: 5000)
+ * - CACHE_STALE_MS: How long past its TTL an entry may be served while
+ *   revalidating or during RPC outages (default: 300000)
  * 
  * @author NeuraDNS Team
  * @version 1.0.0
@@ -29,9 +39,10 @@
 
 import express from "express";
 import cors from "cors";
-import { Connection, Keypair, PublicKey, SystemProgram, TransactionInstruction, Transaction, sendAndConfirmTransaction } from "@solana/web3.js";
+import { ComputeBudgetProgram, Connection, Keypair, PublicKey, SystemProgram, TransactionExpiredBlockheightExceededError, TransactionInstruction, Transaction, sendAndConfirmTransaction } from "@solana/web3.js";
 import { createHash } from "crypto";
 import fs from "fs";
+import https from "https";
 import axios from "axios";
 
 // ============================================================================
@@ -55,6 +66,67 @@ const PORT = Number(process.env.PORT) || 3007;
  */
 const N8N_WEBHOOK_URL = process.env.N8N_WEBHOOK_URL || "https://n8n.backend.lehana.in/webhook/validate_domain";
 
+/**
+ * Percentile of recent prioritization fees to pay (0-100)
+ * @default 75
+ */
+const PRIORITY_FEE_PERCENTILE = Number(process.env.PRIORITY_FEE_PERCENTILE) || 75;
+
+/**
+ * Upper bound on the compute unit price, in micro-lamports
+ * Keeps a fee spike from draining the wallet
+ * @default 100000
+ */
+const PRIORITY_FEE_CAP_MICROLAMPORTS = Number(process.env.PRIORITY_FEE_CAP_MICROLAMPORTS) || 100_000;
+
+/**
+ * Compute units requested per transaction
+ * Priority fees are charged on the requested limit, so keep it tight
+ * @default 200000
+ */
+const COMPUTE_UNIT_LIMIT = Number(process.env.COMPUTE_UNIT_LIMIT) || 200_000;
+
+/**
+ * Send attempts before giving up on an expired transaction
+ */
+const MAX_SEND_ATTEMPTS = 3;
+
+/**
+ * Maximum domain lookups running against the RPC endpoint at once
+ * @default 16
+ */
+const LOOKUP_CONCURRENCY = Number(process.env.LOOKUP_CONCURRENCY) || 16;
+
+/**
+ * Lookups allowed to queue for a slot; beyond this requests get 503
+ * @default 256
+ */
+const LOOKUP_QUEUE_LIMIT = Number(process.env.LOOKUP_QUEUE_LIMIT) || 256;
+
+/**
+ * Approximate memory budget for cached resolve answers, in bytes
+ * @default 64 MiB
+ */
+const CACHE_MAX_BYTES = Number(process.env.CACHE_MAX_BYTES) || 64 * 1024 * 1024;
+
+/**
+ * How long a found domain is served from cache without revalidating
+ * @default 30000
+ */
+const CACHE_TTL_MS = Number(process.env.CACHE_TTL_MS) || 30_000;
+
+/**
+ * How long a not-found answer is cached; short so new registrations show up
+ * @default 5000
+ */
+const CACHE_NEGATIVE_TTL_MS = Number(process.env.CACHE_NEGATIVE_TTL_MS) || 5_000;
+
+/**
+ * Grace period past the TTL during which a stale answer is still served
+ * @default 300000
+ */
+const CACHE_STALE_MS = Number(process.env.CACHE_STALE_MS) || 300_000;
+
 // ============================================================================
 // MIDDLEWARE SETUP
 // ============================================================================
@@ -76,8 +148,12 @@ app.use(express.json());
 /**
  * Solana connection to Devnet cluster
  * Uses 'confirmed' commitment level for balance between speed and reliability
+ * and a keep-alive agent so bursts reuse TLS connections to the RPC node
  */
-const connection = new Connection("https://api.devnet.solana.com", "confirmed");
+const connection = new Connection("https://api.devnet.solana.com", {
+  commitment: "confirmed",
+  httpAgent: new https.Agent({ keepAlive: true }),
+});
 
 /**
  * NeuraDNS Solana Program ID
@@ -147,18 +223,128 @@ function serializeString(str: string): Buffer {
   return Buffer.concat([lenBuf, strBytes]);
 }
 
+/**
+ * Record types in `RecordType` enum order (the serialized variant index)
+ */
+const RECORD_TYPES = ["A", "AAAA", "CNAME", "TXT", "MX", "NS", "SOL", "CONTENTHASH"];
+
+/**
+ * TTL (seconds) the program assigns to accounts from before layout V6
+ */
+const DEFAULT_RECORD_TTL = 3600;
+
+/**
+ * Decode the leading fields of a DomainRecord account
+ * 
+ * Walks the Borsh layout up to `ttl`. Accounts older than layout V4
+ * read the zeroed growth margin at `record_type`, which decodes as "A";
+ * accounts older than V6 get the default TTL, as the program's
+ * layout migration would give them. `expiresAt` (unix seconds) is
+ * null before V7, when domains had no expiry yet.
+ * 
+ * @param data - Raw account data, including the 8-byte discriminator
+ */
+function decodeDomainRecord(data: Buffer) {
+  let offset = 8;
+  const domainLen = data.readUInt32LE(offset);
+  offset += 4;
+  const domain = data.toString('utf-8', offset, offset + domainLen);
+  offset += domainLen;
+
+  const recordLen = data.readUInt32LE(offset);
+  offset += 4;
+  const record = data.toString('utf-8', offset, offset + recordLen);
+  offset += recordLen;
+
+  // Short legacy accounts end early; missing bytes read as zero
+  const byteAt = (at: number) => at < data.length ? data.readUInt8(at) : 0;
+  offset += 32 + 8;                           // authority, created_at
+  offset += byteAt(offset) === 1 ? 33 : 1;    // wrapped_mint
+  offset += 1 + 4;                            // verified_external, index_page
+  const layoutVersion = byteAt(offset);
+  offset += 1 + 8;                            // layout_version, updated_at
+  offset += byteAt(offset) === 1 ? 33 : 1;    // pending_authority
+  const recordType = RECORD_TYPES[byteAt(offset)] || "A";
+  offset += 1 + 1;                            // record_type, record_set_count
+  const ttl = layoutVersion >= 6 && offset + 4 <= data.length
+    ? data.readUInt32LE(offset)
+    : DEFAULT_RECORD_TTL;
+  offset += 4;
+  // i64 as two halves; timestamps fit well within 2^53
+  const expiresAt = layoutVersion >= 7 && offset + 8 <= data.length
+    ? data.readInt32LE(offset + 4) * 0x100000000 + data.readUInt32LE(offset)
+    : null;
+
+  return { domain, record, recordType, ttl, expiresAt };
+}
+
+/**
+ * Derive the PDA holding a domain's extra values of one record type
+ * 
+ * Seeds: ["record", domain_account, record_type as u8]
+ */
+function getRecordSetPDA(domainAccount: PublicKey, recordType: string): PublicKey {
+  const [pda] = PublicKey.findProgramAddressSync(
+    [Buffer.from("record"), domainAccount.toBuffer(), Buffer.from([RECORD_TYPES.indexOf(recordType)])],
+    PROGRAM_ID
+  );
+  return pda;
+}
+
+/**
+ * Fetch the values of a domain's record set
+ * 
+ * ACCOUNT LAYOUT (after the 8-byte discriminator):
+ * - RecordSet: domain (Pubkey), record_type (u8), values (Vec<String>), ...
+ * 
+ * @returns The values, or an empty list when the domain has no such set
+ */
+async function fetchRecordSet(domainAccount: PublicKey, recordType: string): Promise<string[]> {
+  const info = await withLookupSlot(() =>
+    connection.getAccountInfo(getRecordSetPDA(domainAccount, recordType))
+  );
+  if (!info) {
+    return [];
+  }
+  let offset = 8 + 32 + 1;
+  const count = info.data.readUInt32LE(offset);
+  offset += 4;
+  const values: string[] = [];
+  for (let i = 0; i < count; i++) {
+    const len = info.data.readUInt32LE(offset);
+    offset += 4;
+    values.push(info.data.toString('utf-8', offset, offset + len));
+    offset += len;
+  }
+  return values;
+}
+
+/**
+ * Canonical form of a user-supplied domain name
+ * 
+ * The program rejects uppercase names so each name has one PDA; DNS is
+ * case-insensitive, so requests are lowercased instead of refused.
+ * 
+ * @param value - Raw `domain` from the request body or query
+ * @returns The lowercased name, or undefined if it is not a string
+ */
+function normalizeDomain(value: unknown): string | undefined {
+  return typeof value === "string" ? value.toLowerCase() : undefined;
+}
+
 /**
  * Derive Program Derived Address (PDA) for a domain
  * 
  * PDAs are deterministic addresses derived from seeds and the program ID.
  * For NeuraDNS, each domain has a unique PDA derived from:
- * - Seed: ["domain", domain_name]
+ * - Seed: ["domain", sha256(domain_name)]
  * - Program ID: PROGRAM_ID
  * 
  * This ensures:
  * - One domain = one unique account
  * - Collision-free addressing
  * - No private key needed for the account
+ * - Names longer than 32 bytes still fit Solana's seed limit
  * 
  * @param domain - The domain name (e.g., "example.com")
  * @returns Tuple of [PublicKey, bump] for the PDA
@@ -167,10 +353,548 @@ function serializeString(str: string): Buffer {
  * const [pda, bump] = getDomainPDA("example.com");
  */
 function getDomainPDA(domain: string): [PublicKey, number] {
+  const domainHash = createHash('sha256').update(domain, 'utf-8').digest();
   return PublicKey.findProgramAddressSync(
+    [Buffer.from("domain"), domainHash],
+    PROGRAM_ID
+  );
+}
+
+/**
+ * Derive the legacy raw-name PDA for a domain
+ * 
+ * Accounts registered before seeds were hashed live at ["domain", domain_name]
+ * until their owner calls `migrate_domain_account`. Only names of at most
+ * 32 bytes can have a legacy PDA.
+ * 
+ * @param domain - The domain name (e.g., "example.com")
+ * @returns The legacy PDA, or null if the name is too long to have one
+ */
+function getLegacyDomainPDA(domain: string): PublicKey | null {
+  if (Buffer.byteLength(domain, 'utf-8') > 32) {
+    return null;
+  }
+  const [pda] = PublicKey.findProgramAddressSync(
     [Buffer.from("domain"), Buffer.from(domain)],
     PROGRAM_ID
   );
+  return pda;
+}
+
+/**
+ * Fetch a domain account, falling back to its legacy PDA
+ * 
+ * @param domain - The domain name (e.g., "example.com")
+ * @returns The account address and info, or null if not registered
+ */
+async function fetchDomainAccount(domain: string) {
+  const [domainPda] = getDomainPDA(domain);
+  const legacyPda = getLegacyDomainPDA(domain);
+  const addresses = legacyPda ? [domainPda, legacyPda] : [domainPda];
+
+  // One RPC round trip covers both the hashed and the legacy PDA
+  const infos = await connection.getMultipleAccountsInfo(addresses);
+  for (let i = 0; i < addresses.length; i++) {
+    const accountInfo = infos[i];
+    if (accountInfo) {
+      return { address: addresses[i], accountInfo };
+    }
+  }
+  return null;
+}
+
+/**
+ * Result of a domain account lookup
+ */
+type DomainLookup = Awaited<ReturnType<typeof fetchDomainAccount>>;
+
+/**
+ * Thrown when the lookup queue is full; mapped to HTTP 503
+ */
+class GatewayOverloadedError extends Error {
+  constructor() {
+    super("Too many pending lookups, retry shortly");
+  }
+}
+
+let activeLookups = 0;
+const lookupQueue: Array<() => void> = [];
+const inflightLookups = new Map<string, Promise<DomainLookup>>();
+
+/**
+ * Run an RPC task within the LOOKUP_CONCURRENCY limit
+ * 
+ * Waiters queue in FIFO order and a finishing task hands its slot
+ * directly to the next one. Once LOOKUP_QUEUE_LIMIT tasks are waiting,
+ * new ones are rejected instead of piling latency onto the RPC node.
+ * 
+ * @throws GatewayOverloadedError when the queue is full
+ */
+async function withLookupSlot<T>(task: () => Promise<T>): Promise<T> {
+  if (activeLookups < LOOKUP_CONCURRENCY) {
+    activeLookups++;
+  } else if (lookupQueue.length < LOOKUP_QUEUE_LIMIT) {
+    await new Promise<void>(resolve => lookupQueue.push(resolve));
+  } else {
+    throw new GatewayOverloadedError();
+  }
+
+  try {
+    return await task();
+  } finally {
+    const next = lookupQueue.shift();
+    if (next) {
+      next();
+    } else {
+      activeLookups--;
+    }
+  }
+}
+
+/**
+ * Look up a domain account through the bounded, coalescing pipeline
+ * 
+ * Identical names requested while a lookup is in flight share its
+ * result instead of issuing another RPC call.
+ * 
+ * @param domain - The domain name (e.g., "example.com")
+ */
+function lookupDomain(domain: string): Promise<DomainLookup> {
+  const inflight = inflightLookups.get(domain);
+  if (inflight) {
+    return inflight;
+  }
+
+  const lookup = (async () => {
+    try {
+      return await withLookupSlot(() => fetchDomainAccount(domain));
+    } finally {
+      inflightLookups.delete(domain);
+    }
+  })();
+  inflightLookups.set(domain, lookup);
+  return lookup;
+}
+
+/**
+ * Pick a compute unit price from recent prioritization fees
+ * 
+ * Fees are sampled for the accounts the transaction writes, since those
+ * are what it contends on. Each retry moves the percentile halfway to
+ * 100 so a transaction that expired bids more aggressively.
+ * 
+ * @param writable - Accounts the transaction locks for writing
+ * @param attempt - 0-based send attempt
+ * @returns Price in micro-lamports per compute unit, capped
+ */
+async function getPriorityFee(writable: PublicKey[], attempt: number): Promise<number> {
+  const recent = await connection.getRecentPrioritizationFees({ lockedWritableAccounts: writable });
+  if (recent.length === 0) {
+    return 0;
+  }
+
+  let percentile = PRIORITY_FEE_PERCENTILE;
+  for (let i = 0; i < attempt; i++) {
+    percentile += (100 - percentile) / 2;
+  }
+  const fees = recent.map(f => f.prioritizationFee).sort((a, b) => a - b);
+  const idx = Math.min(fees.length - 1, Math.floor((percentile / 100) * fees.length));
+  return Math.min(fees[idx], PRIORITY_FEE_CAP_MICROLAMPORTS);
+}
+
+/**
+ * Send instructions with compute budget and priority fee prepended
+ * 
+ * Retries with a fresh blockhash and a higher fee when the transaction
+ * expires before landing, instead of surfacing a silent timeout.
+ * 
+ * @param instructions - Program instructions to send, in order
+ * @returns Transaction signature
+ */
+async function sendWithPriorityFee(instructions: TransactionInstruction[]): Promise<string> {
+  const writable = Array.from(new Map(
+    instructions
+      .flatMap(ix => ix.keys)
+      .filter(k => k.isWritable && !k.isSigner)
+      .map(k => [k.pubkey.toBase58(), k.pubkey] as [string, PublicKey])
+  ).values());
+
+  for (let attempt = 0; ; attempt++) {
+    const microLamports = await getPriorityFee(writable, attempt);
+    const transaction = new Transaction().add(
+      ComputeBudgetProgram.setComputeUnitLimit({ units: COMPUTE_UNIT_LIMIT }),
+      ComputeBudgetProgram.setComputeUnitPrice({ microLamports }),
+      ...instructions
+    );
+    console.log(`⛽ Priority fee: ${microLamports} micro-lamports/CU (attempt ${attempt + 1})`);
+
+    try {
+      return await sendAndConfirmTransaction(connection, transaction, [wallet], { commitment: 'confirmed' });
+    } catch (error) {
+      if (error instanceof TransactionExpiredBlockheightExceededError && attempt + 1 < MAX_SEND_ATTEMPTS) {
+        console.warn("⌛ Transaction expired before landing - retrying with a higher fee");
+        continue;
+      }
+      throw error;
+    }
+  }
+}
+
+/**
+ * Cached lookup result with its fetch time and approximate footprint
+ */
+interface CacheEntry {
+  lookup: DomainLookup;
+  fetchedAt: number;
+  bytes: number;
+}
+
+/**
+ * Resolve cache in LRU order (Map iteration order = least recent first)
+ */
+const resolveCache = new Map<string, CacheEntry>();
+let resolveCacheBytes = 0;
+
+/**
+ * Fixed per-entry overhead estimate (Map slot, objects, PublicKey)
+ */
+const CACHE_ENTRY_OVERHEAD = 256;
+
+function cacheStore(domain: string, lookup: DomainLookup) {
+  cacheEvict(domain);
+  const bytes = CACHE_ENTRY_OVERHEAD + domain.length * 2 + (lookup ? lookup.accountInfo.data.length : 0);
+  resolveCache.set(domain, { lookup, fetchedAt: Date.now(), bytes });
+  resolveCacheBytes += bytes;
+
+  while (resolveCacheBytes > CACHE_MAX_BYTES && resolveCache.size > 0) {
+    const oldest = resolveCache.keys().next().value as string;
+    cacheEvict(oldest);
+  }
+}
+
+/**
+ * Drop a domain from the resolve cache (e.g. after registering it)
+ */
+function cacheEvict(domain: string) {
+  const entry = resolveCache.get(domain);
+  if (entry) {
+    resolveCache.delete(domain);
+    resolveCacheBytes -= entry.bytes;
+  }
+}
+
+/**
+ * Refresh a stale entry without blocking the request that found it
+ * 
+ * On RPC failure the stale entry stays in place, so it keeps being
+ * served until CACHE_STALE_MS runs out.
+ */
+function revalidateInBackground(domain: string) {
+  if (inflightLookups.has(domain)) {
+    return;
+  }
+  lookupDomain(domain)
+    .then(lookup => cacheStore(domain, lookup))
+    .catch(error => console.warn(`⚠️ Revalidation failed for ${domain}: ${error.message}`));
+}
+
+/**
+ * Look up a domain through the cache
+ * 
+ * - Fresh entry (within CACHE_TTL_MS, or the record's own TTL if that is
+ *   shorter, or CACHE_NEGATIVE_TTL_MS for not-found): served directly
+ * - Stale entry (within a further CACHE_STALE_MS): served immediately
+ *   while a background lookup refreshes it
+ * - Otherwise: fetched through the lookup pipeline; if that fails, an
+ *   expired entry is still better than an error during an RPC outage
+ * 
+ * @param domain - The domain name (e.g., "example.com")
+ * @returns The lookup and how it was served ("hit", "stale" or "miss")
+ */
+async function resolveCached(domain: string): Promise<{ lookup: DomainLookup, cache: string }> {
+  const entry = resolveCache.get(domain);
+  if (entry) {
+    // Re-insert to mark as most recently used
+    resolveCache.delete(domain);
+    resolveCache.set(domain, entry);
+
+    const age = Date.now() - entry.fetchedAt;
+    const ttl = entry.lookup
+      ? Math.min(CACHE_TTL_MS, decodeDomainRecord(entry.lookup.accountInfo.data).ttl * 1000)
+      : CACHE_NEGATIVE_TTL_MS;
+    if (age < ttl) {
+      return { lookup: entry.lookup, cache: "hit" };
+    }
+    if (age < ttl + CACHE_STALE_MS) {
+      revalidateInBackground(domain);
+      return { lookup: entry.lookup, cache: "stale" };
+    }
+  }
+
+  try {
+    const lookup = await lookupDomain(domain);
+    cacheStore(domain, lookup);
+    return { lookup, cache: "miss" };
+  } catch (error) {
+    if (entry && entry.lookup && !(error instanceof GatewayOverloadedError)) {
+      console.warn(`⚠️ RPC lookup failed for ${domain}, serving expired entry`);
+      return { lookup: entry.lookup, cache: "stale" };
+    }
+    throw error;
+  }
+}
+
+/**
+ * Program error decoded from transaction logs
+ * 
+ * `account` is set for constraint failures on a named account, and
+ * `left`/`right` carry the compared values (actual vs expected) when
+ * Anchor logs them, e.g. for has_one or seeds mismatches.
+ */
+interface ProgramError {
+  code: string;
+  number: number;
+  message: string;
+  account: string | null;
+  left: string | null;
+  right: string | null;
+}
+
+/**
+ * First custom error number; DnsError variants count up from here
+ */
+const ANCHOR_CUSTOM_ERROR_OFFSET = 6000;
+
+/**
+ * Decode an Anchor/NeuraDNS error from a failed transaction
+ * 
+ * Parses the `AnchorError ...` log line (and the Left/Right lines that
+ * follow comparison failures). When logs are missing, falls back to the
+ * raw `custom program error: 0x...` code in the error message.
+ * 
+ * @param error - Error thrown by sendAndConfirmTransaction / simulation
+ * @returns Decoded error, or null if it did not come from a program
+ */
+function decodeProgramError(error: any): ProgramError | null {
+  const logs: string[] = Array.isArray(error?.logs) ? error.logs : [];
+  const anchorLine = /AnchorError (?:caused by account: (\w+)|thrown in \S+:\d+|occurred)\. Error Code: (\w+)\. Error Number: (\d+)\. Error Message: (.*?)\.?$/;
+
+  for (let i = 0; i < logs.length; i++) {
+    const match = logs[i].match(anchorLine);
+    if (!match) {
+      continue;
+    }
+    const valueAfter = (label: string) => {
+      const at = logs.indexOf(`Program log: ${label}:`, i);
+      return at >= 0 && at + 1 < logs.length ? logs[at + 1].replace("Program log: ", "") : null;
+    };
+    return {
+      code: match[2],
+      number: Number(match[3]),
+      message: match[4],
+      account: match[1] || null,
+      left: valueAfter("Left"),
+      right: valueAfter("Right"),
+    };
+  }
+
+  const raw = String(error?.message || "").match(/custom program error: (0x[0-9a-f]+)/i);
+  if (raw) {
+    const number = parseInt(raw[1], 16);
+    return {
+      code: number >= ANCHOR_CUSTOM_ERROR_OFFSET ? `DnsError#${number - ANCHOR_CUSTOM_ERROR_OFFSET}` : `Anchor#${number}`,
+      number,
+      message: `Program error ${number}`,
+      account: null,
+      left: null,
+      right: null,
+    };
+  }
+  return null;
+}
+
+/**
+ * Number of domains listed per namespace index page (INDEX_PAGE_CAPACITY)
+ */
+const INDEX_PAGE_CAPACITY = 64;
+
+/**
+ * Derive the namespace index head PDA
+ * 
+ * Seeds: ["index"]. Holds `page_count`; the tail page is `page_count - 1`.
+ */
+function getIndexPDA(): PublicKey {
+  const [pda] = PublicKey.findProgramAddressSync([Buffer.from("index")], PROGRAM_ID);
+  return pda;
+}
+
+/**
+ * Derive the availability Bloom filter PDA
+ * 
+ * Seeds: ["bloom"]. Updated on every registration.
+ */
+function getAvailabilityFilterPDA(): PublicKey {
+  const [pda] = PublicKey.findProgramAddressSync([Buffer.from("bloom")], PROGRAM_ID);
+  return pda;
+}
+
+/**
+ * Derive the protocol stats PDA
+ * 
+ * Seeds: ["stats"]. Counts registrations per epoch.
+ */
+function getStatsPDA(): PublicKey {
+  const [pda] = PublicKey.findProgramAddressSync([Buffer.from("stats")], PROGRAM_ID);
+  return pda;
+}
+
+/**
+ * Derive the reserved-name chunk PDA a domain is checked against
+ * 
+ * Seeds: ["reserved", sha256(domain_name)[0] % 16]
+ */
+function getReservedNamesPDA(domain: string): PublicKey {
+  const chunk = createHash('sha256').update(domain, 'utf-8').digest()[0] % 16;
+  const [pda] = PublicKey.findProgramAddressSync(
+    [Buffer.from("reserved"), Buffer.from([chunk])],
+    PROGRAM_ID
+  );
+  return pda;
+}
+
+/**
+ * Derive the release PDA recording when a name was last reclaimed
+ * 
+ * Seeds: ["release", sha256(domain_name)]. Empty unless the name expired and
+ * was reclaimed; re-registering it soon after costs a decaying premium.
+ */
+function getReleasePDA(domain: string): PublicKey {
+  const [pda] = PublicKey.findProgramAddressSync(
+    [Buffer.from("release"), createHash('sha256').update(domain, 'utf-8').digest()],
+    PROGRAM_ID
+  );
+  return pda;
+}
+
+/**
+ * Derive the TLD PDA for a name's last label
+ * 
+ * Seeds: ["tld", sha256(tld)]. Empty unless the TLD has an owner; a
+ * restricted TLD also needs its owner or registrar to co-sign.
+ */
+function getTldPDA(domain: string): PublicKey {
+  const tld = domain.split('.').pop() || domain;
+  const [pda] = PublicKey.findProgramAddressSync(
+    [Buffer.from("tld"), createHash('sha256').update(tld, 'utf-8').digest()],
+    PROGRAM_ID
+  );
+  return pda;
+}
+
+/**
+ * Derive a namespace index page PDA
+ * 
+ * Seeds: ["index_page", page_number as u32 little-endian]
+ * 
+ * @param page - Page number (0-based)
+ */
+function getIndexPagePDA(page: number): PublicKey {
+  const pageBuf = Buffer.alloc(4);
+  pageBuf.writeUInt32LE(page, 0);
+  const [pda] = PublicKey.findProgramAddressSync(
+    [Buffer.from("index_page"), pageBuf],
+    PROGRAM_ID
+  );
+  return pda;
+}
+
+/**
+ * Read the fee treasury from the program config
+ * 
+ * ACCOUNT LAYOUT (after the 8-byte discriminator):
+ * - ProgramConfig: admin (Pubkey), verifier (Pubkey), bump (u8),
+ *   treasury (Pubkey), registration_fee (u64), renewal_fee (u64),
+ *   fee_mint (Option<Pubkey>), token_registration_fee (u64), token_renewal_fee (u64)
+ * 
+ * @returns The config PDA and the treasury registration fees go to
+ */
+async function prepareFeeAccounts(): Promise<{ config: PublicKey, treasury: PublicKey }> {
+  const [config] = PublicKey.findProgramAddressSync([Buffer.from("config")], PROGRAM_ID);
+  const configInfo = await connection.getAccountInfo(config);
+  const treasuryOffset = 8 + 32 + 32 + 1;
+  if (!configInfo || configInfo.data.length < treasuryOffset + 32) {
+    throw new Error("Program config missing fee settings (run initialize_config or resize_config)");
+  }
+  const treasury = new PublicKey(configInfo.data.subarray(treasuryOffset, treasuryOffset + 32));
+  return { config, treasury };
+}
+
+/**
+ * Read the treasury an owned TLD's fee share is paid to
+ * 
+ * ACCOUNT LAYOUT (after the 8-byte discriminator):
+ * - TldRecord: tld (String: u32 length + bytes), owner (Pubkey),
+ *   treasury (Pubkey), revenue_share_bps (u16), ...
+ * 
+ * @returns The TLD treasury, or null when the TLD has no owner
+ */
+async function getTldTreasury(domain: string): Promise<PublicKey | null> {
+  const tldInfo = await connection.getAccountInfo(getTldPDA(domain));
+  if (!tldInfo) {
+    return null;
+  }
+  const treasuryOffset = 8 + 4 + tldInfo.data.readUInt32LE(8) + 32;
+  return new PublicKey(tldInfo.data.subarray(treasuryOffset, treasuryOffset + 32));
+}
+
+/**
+ * Resolve the index accounts a registration must write to
+ * 
+ * Every registration appends the new domain to the tail index page. When the
+ * tail is full, an `append_index_page` instruction is returned so it can be
+ * sent in the same transaction ahead of `register_request`.
+ * 
+ * ACCOUNT LAYOUTS (after the 8-byte discriminator):
+ * - NamespaceIndex: page_count (u32), total_domains (u64), bump (u8)
+ * - IndexPage: page (u32), bump (u8), next (Option<Pubkey>), domains (Vec<Pubkey>)
+ * 
+ * @returns The index PDA, the tail page to register into, and any setup instructions
+ */
+async function prepareIndexAccounts(): Promise<{ index: PublicKey, tailPage: PublicKey, setup: TransactionInstruction[] }> {
+  const index = getIndexPDA();
+  const indexInfo = await connection.getAccountInfo(index);
+  if (!indexInfo) {
+    throw new Error("Namespace index not initialized (run initialize_index)");
+  }
+  const pageCount = indexInfo.data.readUInt32LE(8);
+  const tailPage = getIndexPagePDA(pageCount - 1);
+
+  const tailInfo = await connection.getAccountInfo(tailPage);
+  if (!tailInfo) {
+    throw new Error("Namespace index tail page missing");
+  }
+  let offset = 8 + 4 + 1;
+  const hasNext = tailInfo.data.readUInt8(offset) === 1;
+  offset += 1 + (hasNext ? 32 : 0);
+  const listed = tailInfo.data.readUInt32LE(offset);
+
+  if (listed < INDEX_PAGE_CAPACITY) {
+    return { index, tailPage, setup: [] };
+  }
+
+  const newPage = getIndexPagePDA(pageCount);
+  const appendIx = new TransactionInstruction({
+    keys: [
+      { pubkey: index, isSigner: false, isWritable: true },
+      { pubkey: tailPage, isSigner: false, isWritable: true },
+      { pubkey: newPage, isSigner: false, isWritable: true },
+      { pubkey: wallet.publicKey, isSigner: true, isWritable: true },
+      { pubkey: SystemProgram.programId, isSigner: false, isWritable: false }
+    ],
+    programId: PROGRAM_ID,
+    data: getDiscriminator("append_index_page")
+  });
+  return { index, tailPage: newPage, setup: [appendIx] };
 }
 
 // ============================================================================
@@ -275,7 +999,8 @@ async function validateWithAI(domain: string, ip: string): Promise<{valid: boole
  */
 app.post("/validate", async (req, res) => {
   try {
-    const { domain, ip } = req.body;
+    const domain = normalizeDomain(req.body.domain);
+    const { ip } = req.body;
 
     if (!domain || !ip) {
       return res.status(400).json({
@@ -287,23 +1012,12 @@ app.post("/validate", async (req, res) => {
     console.log(`🔍 Validating: ${domain} -> ${ip}`);
 
     // Step 1: Check if domain already exists on blockchain
-    const [domainPda] = getDomainPDA(domain);
-    
     try {
-      const accountInfo = await connection.getAccountInfo(domainPda);
+      const existing = await lookupDomain(domain);
       
-      if (accountInfo && accountInfo.data.length > 0) {
+      if (existing && existing.accountInfo.data.length > 0) {
         // Domain already registered
-        const data = accountInfo.data;
-        let offset = 8;
-        const domainLen = data.readUInt32LE(offset);
-        offset += 4;
-        const domainName = data.slice(offset, offset + domainLen).toString('utf-8');
-        offset += domainLen;
-        
-        const recordLen = data.readUInt32LE(offset);
-        offset += 4;
-        const ipAddress = data.slice(offset, offset + recordLen).toString('utf-8');
+        const { domain: domainName, record: ipAddress, recordType, ttl } = decodeDomainRecord(existing.accountInfo.data);
         
         return res.json({
           success: false,
@@ -313,11 +1027,16 @@ app.post("/validate", async (req, res) => {
           data: {
             domain: domainName,
             ip: ipAddress,
-            accountAddress: domainPda.toBase58()
+            type: recordType,
+            ttl,
+            accountAddress: existing.address.toBase58()
           }
         });
       }
     } catch (error) {
+      if (error instanceof GatewayOverloadedError) {
+        throw error;
+      }
       // Account doesn't exist - continue with validation
       console.log(`✅ Domain "${domain}" is available`);
     }
@@ -335,6 +1054,13 @@ app.post("/validate", async (req, res) => {
       aiValidation: aiValidation
     });
   } catch (error: any) {
+    if (error instanceof GatewayOverloadedError) {
+      res.set("Retry-After", "1");
+      return res.status(503).json({
+        success: false,
+        error: error.message,
+      });
+    }
     console.error("❌ Validation error:", error);
     res.status(500).json({
       success: false,
@@ -356,6 +1082,7 @@ app.post("/validate", async (req, res) => {
  * 
  * STEP 2: Blockchain Transaction
  * - Derives PDA for the domain
+ * - Looks up the namespace index tail page (appending a page if full)
  * - Creates instruction with discriminator + serialized data
  * - Signs transaction with wallet
  * - Sends to Solana Devnet
@@ -364,7 +1091,9 @@ app.post("/validate", async (req, res) => {
  * REQUEST BODY:
  * {
  *   "domain": "example.com",
- *   "ip": "8.8.8.8"
+ *   "ip": "8.8.8.8",        // Record value; an address for A/AAAA
+ *   "type": "A",            // Optional: A (default), AAAA, CNAME, TXT, MX, NS, SOL, CONTENTHASH
+ *   "ttl": 3600             // Optional: seconds resolvers may cache it (30-86400, default 3600)
  * }
  * 
  * SUCCESS RESPONSE:
@@ -388,10 +1117,22 @@ app.post("/validate", async (req, res) => {
  *   "reason": "Homograph attack detected",
  *   "aiChecked": true
  * }
+ * 
+ * ERROR RESPONSE (400, program rejected the transaction):
+ * {
+ *   "success": false,
+ *   "error": "Invalid domain format",
+ *   "programError": {
+ *     "code": "InvalidDomain", "number": 6000,
+ *     "account": null, "left": null, "right": null
+ *   },
+ *   "details": [...transaction logs]
+ * }
  */
 app.post("/register", async (req, res) => {
   try {
-    const { domain, ip } = req.body;
+    const domain = normalizeDomain(req.body.domain);
+    const { ip, type = "A", ttl = DEFAULT_RECORD_TTL } = req.body;
 
     if (!domain || !ip) {
       return res.status(400).json({
@@ -400,7 +1141,22 @@ app.post("/register", async (req, res) => {
       });
     }
 
-    console.log(`📝 Registering: ${domain} -> ${ip}`);
+    const recordTypeIndex = RECORD_TYPES.indexOf(String(type).toUpperCase());
+    if (recordTypeIndex < 0) {
+      return res.status(400).json({
+        success: false,
+        error: `Unsupported record type "${type}" (expected one of ${RECORD_TYPES.join(", ")})`,
+      });
+    }
+
+    if (!Number.isInteger(ttl) || ttl < 30 || ttl > 86400) {
+      return res.status(400).json({
+        success: false,
+        error: "ttl must be an integer between 30 and 86400 seconds",
+      });
+    }
+
+    console.log(`📝 Registering: ${domain} -> ${RECORD_TYPES[recordTypeIndex]} ${ip}`);
 
     // Step 1: AI-powered validation via n8n
     const aiValidation = await validateWithAI(domain, ip);
@@ -419,16 +1175,23 @@ app.post("/register", async (req, res) => {
     // Continue with blockchain registration...
 
     const [domainPda] = getDomainPDA(domain);
+    const { index, tailPage, setup } = await prepareIndexAccounts();
+    const { config, treasury } = await prepareFeeAccounts();
+    const tldTreasury = await getTldTreasury(domain);
 
     // Build instruction data: discriminator + serialized arguments
     const discriminator = getDiscriminator("register_request");
     const domainBytes = serializeString(domain);
     const ipBytes = serializeString(ip);
+    const ttlBytes = Buffer.alloc(4);
+    ttlBytes.writeUInt32LE(ttl);
     
     const instructionData = Buffer.concat([
       discriminator,
       domainBytes,
-      ipBytes
+      ipBytes,
+      Buffer.from([recordTypeIndex]),
+      ttlBytes
     ]);
 
     console.log(`📦 Instruction data length: ${instructionData.length} bytes`);
@@ -438,22 +1201,36 @@ app.post("/register", async (req, res) => {
       keys: [
         { pubkey: domainPda, isSigner: false, isWritable: true },
         { pubkey: wallet.publicKey, isSigner: true, isWritable: true },
-        { pubkey: SystemProgram.programId, isSigner: false, isWritable: false }
+        { pubkey: SystemProgram.programId, isSigner: false, isWritable: false },
+        { pubkey: index, isSigner: false, isWritable: true },
+        { pubkey: tailPage, isSigner: false, isWritable: true },
+        { pubkey: getAvailabilityFilterPDA(), isSigner: false, isWritable: true },
+        { pubkey: getStatsPDA(), isSigner: false, isWritable: true },
+        { pubkey: getReservedNamesPDA(domain), isSigner: false, isWritable: false },
+        { pubkey: config, isSigner: false, isWritable: false },
+        { pubkey: treasury, isSigner: false, isWritable: true },
+        { pubkey: getReleasePDA(domain), isSigner: false, isWritable: false },
+        { pubkey: getTldPDA(domain), isSigner: false, isWritable: false },
+        // Optional SPL fee accounts (token program, mint, payer and treasury
+        // token accounts), the commit-reveal commitment, the auction and the
+        // TLD approver; the program ID stands in for "not passed", so the fee
+        // is paid in SOL and only open or ownerless TLDs can be registered
+        ...[0, 1, 2, 3, 4, 5, 6].map(() => ({ pubkey: PROGRAM_ID, isSigner: false, isWritable: false })),
+        // The TLD's share of the fee goes to its treasury
+        tldTreasury
+          ? { pubkey: tldTreasury, isSigner: false, isWritable: true }
+          : { pubkey: PROGRAM_ID, isSigner: false, isWritable: false },
+        { pubkey: PROGRAM_ID, isSigner: false, isWritable: false }
       ],
       programId: PROGRAM_ID,
       data: instructionData
     });
 
-    // Create and send transaction
-    const transaction = new Transaction().add(instruction);
-    const signature = await sendAndConfirmTransaction(
-      connection,
-      transaction,
-      [wallet],
-      { commitment: 'confirmed' }
-    );
+    // Send transaction (appending an index page first if needed)
+    const signature = await sendWithPriorityFee([...setup, instruction]);
 
     console.log(`✅ Registered! Tx: ${signature}`);
+    cacheEvict(domain);
 
     res.json({
       success: true,
@@ -474,9 +1251,11 @@ app.post("/register", async (req, res) => {
     });
   } catch (error: any) {
     console.error("❌ Error:", error);
-    res.status(500).json({
+    const programError = decodeProgramError(error);
+    res.status(programError ? 400 : 500).json({
       success: false,
-      error: error.message || "Failed to register domain",
+      error: programError?.message || error.message || "Failed to register domain",
+      programError,
       details: error.logs || error.toString()
     });
   }
@@ -489,7 +1268,8 @@ app.post("/register", async (req, res) => {
  * Queries the PDA account for a registered domain and returns the IP address.
  * 
  * PROCESS:
- * 1. Derive PDA from domain name using seeds ["domain", domain_name]
+ * 1. Derive PDA from domain name using seeds ["domain", sha256(domain_name)]
+ *    (falling back to the legacy ["domain", domain_name] PDA)
  * 2. Fetch account data from Solana
  * 3. Parse serialized data (discriminator + strings with length prefixes)
  * 4. Return domain record with IP address
@@ -503,6 +1283,9 @@ app.post("/register", async (req, res) => {
  *   "data": {
  *     "domain": "example.com",
  *     "ip": "8.8.8.8",
+ *     "type": "A",
+ *     "ttl": 3600,
+ *     "expiresAt": 1767225600,
  *     "accountAddress": "PDA address..."
  *   }
  * }
@@ -512,12 +1295,44 @@ app.post("/register", async (req, res) => {
  *   "success": false,
  *   "error": "Domain not found on blockchain"
  * }
+ * 
+ * ERROR RESPONSE (410, registration lapsed; renewable by its owner
+ * during the grace and redemption periods):
+ * {
+ *   "success": false,
+ *   "error": "Domain registration has expired",
+ *   "data": { "domain": "example.com", "expiresAt": 1767225600 }
+ * }
+ * 
+ * REFERRAL RESPONSE (200, the domain's primary record is NS). The
+ * gateway is not authoritative for delegated domains, so it points
+ * the client at their nameservers instead of answering:
+ * {
+ *   "success": true,
+ *   "referral": true,
+ *   "data": {
+ *     "domain": "example.com",
+ *     "nameservers": ["ns1.example.com", "ns2.example.com"],
+ *     "ttl": 86400,
+ *     "expiresAt": 1767225600,
+ *     "accountAddress": "PDA address..."
+ *   }
+ * }
+ * 
+ * HEADERS:
+ * - X-Cache: HIT (fresh), STALE (served while revalidating) or MISS
+ * 
+ * OVERLOADED RESPONSE (503, Retry-After: 1):
+ * {
+ *   "success": false,
+ *   "error": "Too many pending lookups, retry shortly"
+ * }
  */
 app.get("/resolve", async (req, res) => {
   try {
-    const { domain } = req.query;
+    const domain = normalizeDomain(req.query.domain);
 
-    if (!domain || typeof domain !== "string") {
+    if (!domain) {
       return res.status(400).json({
         success: false,
         error: "Missing required query parameter: domain",
@@ -526,12 +1341,11 @@ app.get("/resolve", async (req, res) => {
 
     console.log(`🔍 Resolving: ${domain}`);
 
-    const [domainPda] = getDomainPDA(domain);
+    // Fetch the account (cached)
+    const { lookup: existing, cache } = await resolveCached(domain);
+    res.set("X-Cache", cache.toUpperCase());
 
-    // Fetch the account
-    const accountInfo = await connection.getAccountInfo(domainPda);
-
-    if (!accountInfo) {
+    if (!existing) {
       return res.status(404).json({
         success: false,
         error: "Domain not found on blockchain",
@@ -539,29 +1353,53 @@ app.get("/resolve", async (req, res) => {
     }
 
     // Parse account data
-    const data = accountInfo.data;
-    
-    // Skip 8-byte discriminator
-    let offset = 8;
-    const domainNameLen = data.readUInt32LE(offset);
-    offset += 4;
-    const domainName = data.toString('utf-8', offset, offset + domainNameLen);
-    offset += domainNameLen;
-    
-    // Read IP
-    const ipLen = data.readUInt32LE(offset);
-    offset += 4;
-    const ipAddress = data.toString('utf-8', offset, offset + ipLen);
+    const { domain: domainName, record: ipAddress, recordType, ttl, expiresAt } = decodeDomainRecord(existing.accountInfo.data);
+
+    // Lapsed domains stop resolving, as on-chain `resolve_domain` does
+    if (expiresAt !== null && Date.now() / 1000 >= expiresAt) {
+      return res.status(410).json({
+        success: false,
+        error: "Domain registration has expired",
+        data: { domain: domainName, expiresAt },
+      });
+    }
+
+    // Delegated domains get a referral, as on-chain `resolve_delegation`
+    if (recordType === "NS") {
+      const extra = await fetchRecordSet(existing.address, "NS");
+      const nameservers = Array.from(new Set([ipAddress, ...extra]));
+      return res.json({
+        success: true,
+        referral: true,
+        data: {
+          domain: domainName,
+          nameservers,
+          ttl,
+          expiresAt,
+          accountAddress: existing.address.toBase58(),
+        },
+      });
+    }
 
     res.json({
       success: true,
       data: {
         domain: domainName,
         ip: ipAddress,
-        accountAddress: domainPda.toBase58(),
+        type: recordType,
+        ttl,
+        expiresAt,
+        accountAddress: existing.address.toBase58(),
       },
     });
   } catch (error: any) {
+    if (error instanceof GatewayOverloadedError) {
+      res.set("Retry-After", "1");
+      return res.status(503).json({
+        success: false,
+        error: error.message,
+      });
+    }
     console.error("❌ Error:", error);
     res.status(500).json({
       success: false,
@@ -4,13 +4,43 @@
 // Deployed Program ID: H7azh1pVd3uySy7z4JRmQL2HpF2D9673Y9RP4yXZWfFM
 // Network: Solana Devnet
 // Framework: Anchor 0.32.1
+// Dependencies: anchor-lang (init-if-needed),
+//               anchor-spl (token_2022, token_2022_extensions, associated_token)
+// Features: verbose-logs (devnet only; keeps msg! output on hot paths)
 // ============================================================================
 
 use anchor_lang::prelude::*;
+use anchor_lang::solana_program::hash::{hash, hashv};
+use anchor_lang::solana_program::keccak;
+use anchor_lang::solana_program::program::MAX_RETURN_DATA;
+use anchor_lang::system_program;
+use anchor_spl::associated_token::AssociatedToken;
+use anchor_spl::token_2022::{spl_token_2022::instruction::AuthorityType, Token2022};
+use anchor_spl::token_2022_extensions::spl_token_metadata_interface::state::Field;
+use anchor_spl::token_interface::{
+    self, token_metadata_initialize, token_metadata_update_field, Burn, Mint, MintTo,
+    SetAuthority, TokenAccount, TokenInterface, TokenMetadataInitialize,
+    TokenMetadataUpdateField, TransferChecked,
+};
+use std::str::FromStr;
 
 // This will be auto-generated when you deploy
 declare_id!("H7azh1pVd3uySy7z4JRmQL2HpF2D9673Y9RP4yXZWfFM");
 
+/// `msg!` that is only compiled in with the `verbose-logs` feature
+/// 
+/// Hot paths (register, resolve) log through this so mainnet builds skip
+/// the formatting and log syscalls; they report via events and return
+/// data instead.
+macro_rules! verbose_msg {
+    ($($arg:tt)*) => {{
+        #[cfg(feature = "verbose-logs")]
+        {
+            msg!($($arg)*);
+        }
+    }};
+}
+
 #[program]
 pub mod neura_dns {
     use super::*;
@@ -21,41 +51,359 @@ pub mod neura_dns {
     /// # Arguments
     /// * `domain_name` - The domain name to register (e.g., "example.com")
     /// * `record` - The IP address or record value (e.g., "8.8.8.8")
+    /// * `record_type` - How `record` is interpreted (A, AAAA, CNAME, ...)
+    /// * `ttl` - Seconds resolvers may cache the answer (30-86400)
     /// 
     /// # Process
     /// 1. Logs registration request details
-    /// 2. Validates domain and IP format
-    /// 3. Creates PDA (Program Derived Address) account
-    /// 4. Stores domain record on-chain with metadata
+    /// 2. Validates the domain, the record for its type and the TTL, and
+    ///    checks the name is not on the reserved list
+    /// 3. Transfers the registration fee to the treasury, in lamports or,
+    ///    when the token accounts are passed, in the configured fee mint;
+    ///    names of 1-4 characters pay the premium multiple, and recently
+    ///    reclaimed names a decaying release premium in lamports; an owned
+    ///    TLD's revenue share goes to its treasury instead
+    /// 4. Creates PDA (Program Derived Address) account
+    /// 5. Stores domain record on-chain with metadata, expiring in a year
+    /// 6. Appends the domain to the tail page of the namespace index
+    /// 7. Adds the name hash to the availability Bloom filter
+    /// 8. Emits `DomainRegistered`
+    /// 9. Counts the registration and fee in the protocol stats
     /// 
     /// # Storage
-    /// - Uses PDA with seeds: ["domain", domain_name.as_bytes()]
+    /// - Uses PDA with seeds: ["domain", sha256(domain_name)]
     /// - Stores: domain_name, record (IP), authority (wallet), timestamp
     /// 
     /// # Errors
-    /// - InvalidDomain: Domain format validation failed
-    /// - InvalidIp: IP address format validation failed
+    /// - InvalidDomain, DomainTooLong, EmptyLabel, LabelTooLong,
+    ///   InvalidLabelCharacter, LabelHyphen: Domain format validation failed
+    /// - UppercaseDomain: Domain is not in lowercase canonical form
+    /// - InvalidIp, InvalidIpv6, InvalidHostname, InvalidMxRecord,
+    ///   InvalidTxtRecord: Record does not match its type
+    /// - InvalidTtl: TTL outside 30-86400 seconds
+    /// - NameReserved: Name is reserved or blocked by the admin
+    /// - InsufficientFee: Authority can't cover the registration fee
+    /// - WrongFeeMint: Token accounts passed for a mint that isn't configured,
+    ///   or the treasury token account isn't the treasury's
+    /// - IndexPageFull: Tail index page is full; call `append_index_page` first
+    /// - CommitmentRequired: Admin turned on commit-reveal; use
+    ///   `reveal_and_register`
+    /// - NameRequiresAuction: Name is short enough that it must be auctioned
+    /// - TldApprovalRequired: The TLD is restricted and its owner did not sign
+    /// - WrongTldTreasury: The TLD takes a share and its treasury (or
+    ///   treasury token account) was not passed
+    /// - ProgramPaused: The admin has paused the program
     pub fn register_request(
         ctx: Context<RegisterDomain>,
         domain_name: String,
         record: String,
+        record_type: RecordType,
+        ttl: u32,
     ) -> Result<()> {
-        msg!("🌐 NEURA DNS - Domain Registration Request");
-        msg!("Domain: {}", domain_name);
-        msg!("Record: {}", record);
-        msg!("Authority: {}", ctx.accounts.authority.key());
+        require!(
+            !ctx.accounts.config.require_commitment,
+            DnsError::CommitmentRequired
+        );
+        register_domain(ctx, domain_name, record, record_type, ttl, None)
+    }
+
+    /// Commit to registering a name without revealing it
+    /// 
+    /// # Arguments
+    /// * `commitment` - sha256(domain_name ‖ salt ‖ payer), with a random
+    ///   32-byte salt kept secret until the reveal
+    /// 
+    /// # Notes
+    /// - Reveal with `reveal_and_register` after at least 10 slots and
+    ///   within ~1 day (216,000 slots)
+    /// - Unused commitments can be closed with `close_commitment`
+    pub fn commit_registration(
+        ctx: Context<CommitRegistration>,
+        commitment: [u8; 32],
+    ) -> Result<()> {
+        let pending = &mut ctx.accounts.commitment;
+        pending.committer = ctx.accounts.committer.key();
+        pending.commitment = commitment;
+        pending.slot = Clock::get()?.slot;
+        pending.bump = ctx.bumps.commitment;
 
-        // Validate domain and IP format
-        validate_domain_and_ip(&domain_name, &record)?;
+        verbose_msg!("🤐 NEURA DNS - Registration Committed");
+        verbose_msg!("Slot: {}", pending.slot);
+        Ok(())
+    }
 
-        // Store domain record on-chain
-        let domain_account = &mut ctx.accounts.domain_account;
-        domain_account.domain_name = domain_name;
-        domain_account.record = record;
-        domain_account.authority = ctx.accounts.authority.key();
-        domain_account.created_at = Clock::get()?.unix_timestamp;
+    /// Register a name committed to earlier with `commit_registration`
+    /// 
+    /// # Arguments
+    /// * `domain_name`, `record`, `record_type`, `ttl` - As for
+    ///   `register_request`
+    /// * `salt` - Salt used in the commitment
+    /// 
+    /// # Process
+    /// 1. Checks the commitment matches the name, salt and signer and is
+    ///    between 10 and 216,000 slots old
+    /// 2. Closes the commitment, refunding its rent to the signer
+    /// 3. Registers the domain exactly like `register_request`
+    /// 
+    /// # Notes
+    /// - Pass the commitment PDA as the optional `commitment` account
+    /// - The name only appears on chain once the commitment has aged, so
+    ///   watchers can't snipe the pending transaction with their own
+    /// 
+    /// # Errors
+    /// - CommitmentMismatch: No commitment passed, or it doesn't match
+    /// - CommitmentTooNew: Fewer than 10 slots since the commit
+    /// - CommitmentExpired: More than 216,000 slots since the commit
+    /// - Any `register_request` error
+    pub fn reveal_and_register(
+        ctx: Context<RegisterDomain>,
+        domain_name: String,
+        record: String,
+        record_type: RecordType,
+        ttl: u32,
+        salt: [u8; 32],
+    ) -> Result<()> {
+        let authority = ctx.accounts.authority.key();
+        let commitment = ctx
+            .accounts
+            .commitment
+            .as_ref()
+            .ok_or(DnsError::CommitmentMismatch)?;
+        require!(
+            commitment.committer == authority
+                && commitment.commitment == registration_commitment(&domain_name, &salt, &authority),
+            DnsError::CommitmentMismatch
+        );
+        let age = Clock::get()?.slot.saturating_sub(commitment.slot);
+        require!(age >= COMMITMENT_MIN_AGE_SLOTS, DnsError::CommitmentTooNew);
+        require!(age <= COMMITMENT_MAX_AGE_SLOTS, DnsError::CommitmentExpired);
+        commitment.close(ctx.accounts.authority.to_account_info())?;
+
+        register_domain(ctx, domain_name, record, record_type, ttl, None)
+    }
+
+    /// Close an unused registration commitment, refunding its rent
+    /// 
+    /// # Errors
+    /// - Unauthorized: Signer did not make the commitment
+    pub fn close_commitment(_ctx: Context<CloseCommitment>) -> Result<()> {
+        verbose_msg!("🤐 NEURA DNS - Commitment Closed");
+        Ok(())
+    }
+
+    /// Open an English auction for an unregistered short name
+    /// 
+    /// # Arguments
+    /// * `domain_name` - Name to auction; its leftmost label must be no
+    ///   longer than `config.auction_max_len`
+    /// * `bid` - Opening bid in lamports, at least `config.auction_min_bid`
+    /// 
+    /// # Process
+    /// 1. Checks the name is valid, unregistered and auction-only
+    /// 2. Escrows the opening bid in the auction PDA
+    /// 3. Sets the end to `config.auction_duration` from now
+    /// 
+    /// # Notes
+    /// - The starter pays the auction PDA's rent, which goes to the
+    ///   winner when the auction is settled
+    /// 
+    /// # Errors
+    /// - InvalidDomain, UppercaseDomain and other format errors
+    /// - TldApprovalRequired: The TLD is restricted and its owner did not sign
+    /// - NotAuctionable: Auctions are off or the name is too long for one
+    /// - DomainAlreadyRegistered: The name is already taken
+    /// - BidTooLow: Bid below the minimum
+    pub fn start_auction(ctx: Context<StartAuction>, domain_name: String, bid: u64) -> Result<()> {
+        validate_domain(&domain_name)?;
+        require!(
+            !domain_name.bytes().any(|b| b.is_ascii_uppercase()),
+            DnsError::UppercaseDomain
+        );
+        require_tld_approval(&ctx.accounts.tld, ctx.accounts.tld_approver.as_ref())?;
+        let config = &ctx.accounts.config;
+        require!(config.requires_auction(&domain_name), DnsError::NotAuctionable);
+        require!(
+            ctx.accounts.domain_account.data_is_empty(),
+            DnsError::DomainAlreadyRegistered
+        );
+        require!(bid >= config.auction_min_bid, DnsError::BidTooLow);
+
+        system_program::transfer(
+            CpiContext::new(
+                ctx.accounts.system_program.to_account_info(),
+                system_program::Transfer {
+                    from: ctx.accounts.bidder.to_account_info(),
+                    to: ctx.accounts.auction.to_account_info(),
+                },
+            ),
+            bid,
+        )?;
+
+        let auction = &mut ctx.accounts.auction;
+        auction.domain_name = domain_name;
+        auction.started_by = ctx.accounts.bidder.key();
+        auction.highest_bidder = ctx.accounts.bidder.key();
+        auction.highest_bid = bid;
+        auction.ends_at = Clock::get()?.unix_timestamp + config.auction_duration;
+        auction.bump = ctx.bumps.auction;
+
+        emit!(AuctionBid {
+            auction: auction.key(),
+            domain_name: auction.domain_name.clone(),
+            bidder: auction.highest_bidder,
+            bid,
+            ends_at: auction.ends_at,
+            slot: Clock::get()?.slot,
+        });
+
+        msg!("🔨 NEURA DNS - Auction Started");
+        msg!("Domain: {}", auction.domain_name);
+        msg!("Opening bid: {} lamports, ends at {}", bid, auction.ends_at);
+        Ok(())
+    }
+
+    /// Outbid the current leader of an auction
+    /// 
+    /// # Arguments
+    /// * `bid` - Lamports, at least 5% above the current highest bid
+    /// 
+    /// # Process
+    /// 1. Escrows the new bid in the auction PDA
+    /// 2. Refunds the previous leader's bid in full
+    /// 3. Extends the end to 10 minutes from now if it was closer
+    /// 
+    /// # Errors
+    /// - AuctionEnded: Bidding has closed
+    /// - BidTooLow: Bid is less than 5% above the highest bid
+    pub fn place_bid(ctx: Context<PlaceBid>, bid: u64) -> Result<()> {
+        let now = Clock::get()?.unix_timestamp;
+        let auction = &ctx.accounts.auction;
+        require!(now < auction.ends_at, DnsError::AuctionEnded);
+        let min_bid = auction
+            .highest_bid
+            .saturating_add((auction.highest_bid / 20).max(1));
+        require!(bid >= min_bid, DnsError::BidTooLow);
+
+        system_program::transfer(
+            CpiContext::new(
+                ctx.accounts.system_program.to_account_info(),
+                system_program::Transfer {
+                    from: ctx.accounts.bidder.to_account_info(),
+                    to: ctx.accounts.auction.to_account_info(),
+                },
+            ),
+            bid,
+        )?;
+        let refund = ctx.accounts.auction.highest_bid;
+        **ctx.accounts.auction.to_account_info().try_borrow_mut_lamports()? -= refund;
+        **ctx.accounts.previous_bidder.try_borrow_mut_lamports()? += refund;
+
+        let auction = &mut ctx.accounts.auction;
+        auction.highest_bidder = ctx.accounts.bidder.key();
+        auction.highest_bid = bid;
+        auction.ends_at = auction.ends_at.max(now + AUCTION_EXTENSION);
+
+        emit!(AuctionBid {
+            auction: auction.key(),
+            domain_name: auction.domain_name.clone(),
+            bidder: auction.highest_bidder,
+            bid,
+            ends_at: auction.ends_at,
+            slot: Clock::get()?.slot,
+        });
+
+        msg!("🔨 NEURA DNS - Bid Placed");
+        msg!("Domain: {}", auction.domain_name);
+        msg!("Bid: {} lamports, ends at {}", bid, auction.ends_at);
+        Ok(())
+    }
+
+    /// Register an auctioned name to the winning bidder
+    /// 
+    /// # Arguments
+    /// * `domain_name`, `record`, `record_type`, `ttl` - As for
+    ///   `register_request`
+    /// 
+    /// # Process
+    /// 1. Checks the auction for this name has ended and the signer won
+    /// 2. Pays the winning bid from escrow to the treasury
+    /// 3. Closes the auction PDA, refunding its rent to the winner
+    /// 4. Registers the domain like `register_request`, with the winning
+    ///   bid in place of the registration fee
+    /// 
+    /// # Notes
+    /// - Pass the auction PDA as the optional `auction` account
+    /// - Losing bids were refunded when they were outbid
+    /// - A winner who doesn't settle within 30 days, or can no longer
+    ///   settle, gets the bid back through `refund_bid` instead
+    /// 
+    /// # Errors
+    /// - AuctionMismatch: No auction passed, or it is for another name
+    /// - AuctionNotEnded: Bidding is still open
+    /// - Unauthorized: Signer is not the winning bidder
+    /// - Any `register_request` validation error
+    pub fn settle_auction(
+        ctx: Context<RegisterDomain>,
+        domain_name: String,
+        record: String,
+        record_type: RecordType,
+        ttl: u32,
+    ) -> Result<()> {
+        let auction = ctx
+            .accounts
+            .auction
+            .as_ref()
+            .ok_or(DnsError::AuctionMismatch)?;
+        require!(auction.domain_name == domain_name, DnsError::AuctionMismatch);
+        require!(
+            Clock::get()?.unix_timestamp >= auction.ends_at,
+            DnsError::AuctionNotEnded
+        );
+        require_keys_eq!(
+            auction.highest_bidder,
+            ctx.accounts.authority.key(),
+            DnsError::Unauthorized
+        );
+
+        let winning_bid = auction.highest_bid;
+        **auction.to_account_info().try_borrow_mut_lamports()? -= winning_bid;
+        **ctx.accounts.treasury.try_borrow_mut_lamports()? += winning_bid;
+        auction.close(ctx.accounts.authority.to_account_info())?;
+
+        register_domain(ctx, domain_name, record, record_type, ttl, Some(winning_bid))
+    }
+
+    /// Refund the winning bid of an auction that will not be settled
+    /// 
+    /// # Process
+    /// 1. Checks bidding has ended and `settle_auction` can no longer
+    ///    succeed, or the winner let the settlement window pass
+    /// 2. Closes the auction PDA, returning the bid and rent to the
+    ///    highest bidder
+    /// 
+    /// # Notes
+    /// - Permissionless, so a stuck bid can be freed by anyone
+    /// - Settlement is treated as impossible once the name is registered
+    ///   or the auction policy no longer reserves it for auction
+    /// - The settlement window is 30 days after `ends_at`
+    /// 
+    /// # Errors
+    /// - AuctionNotEnded: Bidding is still open
+    /// - AuctionSettleable: The winner can still settle the auction
+    pub fn refund_bid(ctx: Context<RefundBid>) -> Result<()> {
+        let now = Clock::get()?.unix_timestamp;
+        let auction = &ctx.accounts.auction;
+        require!(now >= auction.ends_at, DnsError::AuctionNotEnded);
+        let unsettleable = !ctx.accounts.domain_account.data_is_empty()
+            || !ctx.accounts.config.requires_auction(&auction.domain_name);
+        require!(
+            unsettleable || now >= auction.ends_at.saturating_add(AUCTION_SETTLE_WINDOW),
+            DnsError::AuctionSettleable
+        );
 
-        msg!("✅ Registration successful - stored on-chain");
+        msg!("🔨 NEURA DNS - Auction Bid Refunded");
+        msg!("Domain: {}", auction.domain_name);
+        msg!("Refunded: {} lamports to {}", auction.highest_bid, auction.highest_bidder);
         Ok(())
     }
 
@@ -70,166 +418,12058 @@ pub mod neura_dns {
     /// 3. Returns domain record data
     /// 
     /// # Returns
-    /// - Domain name
-    /// - IP address/record
-    /// - Registrar's wallet address
-    /// - Creation timestamp
+    /// - The resolved record type, value and TTL, as instruction return data
     /// 
     /// # Notes
     /// - Requires valid PDA account to exist
     /// - Read-only operation, no state modification
+    /// - Simulate the transaction and read `returnData` for the answer;
+    ///   the detailed logs only exist in `verbose-logs` builds
+    /// - If a fresh dynamic record is passed, its value wins over the
+    ///   static record; a stale one falls back to the static record
+    /// - Dynamic records carry IPv4 values, so they only apply to A records
+    /// 
+    /// # Errors
+    /// - DomainExpired: Registration lapsed (grace period or later)
     pub fn resolve_domain(
         ctx: Context<ResolveDomain>,
         _domain_name: String,
-    ) -> Result<()> {
+    ) -> Result<ResolvedRecord> {
         let domain_account = &ctx.accounts.domain_account;
+        let now = Clock::get()?.unix_timestamp;
+        require!(
+            domain_account.phase(now) == DomainPhase::Active,
+            DnsError::DomainExpired
+        );
+        let dynamic_record = ctx
+            .accounts
+            .dynamic_record
+            .as_ref()
+            .filter(|_| domain_account.record_type == RecordType::A);
+        let record = match dynamic_record {
+            Some(dynamic) if dynamic.is_fresh(now) => {
+                verbose_msg!("⚡ Dynamic record from updater: {}", dynamic.updater);
+                &dynamic.value
+            }
+            Some(_) => {
+                verbose_msg!("⚠️ Dynamic record stale - using static record");
+                &domain_account.record
+            }
+            None => &domain_account.record,
+        };
         
-        msg!("🔍 NEURA DNS - Domain Resolution Request");
-        msg!("Looking up: {}", domain_account.domain_name);
-        msg!("✅ Resolved to: {}", domain_account.record);
-        msg!("Registered by: {}", domain_account.authority);
-        msg!("Created at: {}", domain_account.created_at);
+        verbose_msg!("🔍 NEURA DNS - Domain Resolution Request");
+        verbose_msg!("Looking up: {}", domain_account.domain_name);
+        verbose_msg!(
+            "✅ Resolved to: {} {} (TTL {})",
+            domain_account.record_type.mnemonic(),
+            record,
+            domain_account.ttl
+        );
+        verbose_msg!("Registered by: {}", domain_account.authority);
+        verbose_msg!("Created at: {}", domain_account.created_at);
+        if domain_account.verified_external {
+            verbose_msg!("🏅 Verified: controls this name in traditional DNS");
+        }
         
+        Ok(ResolvedRecord {
+            record_type: domain_account.record_type,
+            value: record.clone(),
+            ttl: domain_account.ttl,
+        })
+    }
+
+    /// Wrap a domain as a transferable NFT
+    /// Mints a Token-2022 NFT and moves authority into escrow
+    /// 
+    /// # Arguments
+    /// * `uri` - URI of a JSON descriptor of the record set (max 200 chars)
+    /// 
+    /// # Process
+    /// 1. Creates a Token-2022 mint whose metadata pointer targets itself
+    /// 2. Writes name (domain), symbol, URI and a "domain" field holding
+    ///    the domain PDA address into the mint's metadata
+    /// 3. Mints exactly one token to the authority's ATA and revokes
+    ///    the mint authority so supply is fixed at one
+    /// 4. Sets the domain authority to the wrap escrow PDA
+    /// 5. Records the mint on the domain account
+    /// 
+    /// # Notes
+    /// - Metadata lives in the mint itself; no metadata program is needed
+    /// - The escrow stays metadata update authority for descriptor changes
+    /// - Whoever holds the token can call `unwrap_domain` to take authority,
+    ///   and can update the record while wrapped (see `update_record`)
+    /// - Each wrap uses a new mint; old mints are left with zero supply
+    /// 
+    /// # Errors
+    /// - Unauthorized: Signer is not the domain authority
+    /// - GovernanceThreshold: Domain has governance and too few members signed
+    /// - AlreadyWrapped: Domain is already wrapped
+    /// - DomainLocked: Domain is locked (see `lock_domain`)
+    /// - InvalidMetadataUri: URI is empty or too long
+    /// - FeatureDisabled: The admin has turned off wrapping
+    /// - ProgramPaused: The admin has paused the program
+    pub fn wrap_domain(ctx: Context<WrapDomain>, uri: String) -> Result<()> {
+        ctx.accounts.config.require_not_paused()?;
+        ctx.accounts.config.require_feature(Feature::Wrapping)?;
+        require_governance(
+            &ctx.accounts.domain_account,
+            &ctx.accounts.governance,
+            ctx.remaining_accounts,
+        )?;
+        require_unlocked(&ctx.accounts.domain_account, &ctx.accounts.lock)?;
+        require!(
+            ctx.accounts.domain_account.wrapped_mint.is_none(),
+            DnsError::AlreadyWrapped
+        );
+        require!(
+            !uri.is_empty() && uri.len() <= MAX_METADATA_URI_LEN,
+            DnsError::InvalidMetadataUri
+        );
+
+        msg!("🎁 NEURA DNS - Wrapping Domain");
+        msg!("Domain: {}", ctx.accounts.domain_account.domain_name);
+        msg!("Mint: {}", ctx.accounts.mint.key());
+
+        let domain_key = ctx.accounts.domain_account.key();
+        let escrow_seeds: &[&[u8]] = &[
+            b"wrap_escrow",
+            domain_key.as_ref(),
+            &[ctx.bumps.wrap_escrow],
+        ];
+        let signer_seeds = &[escrow_seeds];
+
+        let name = ctx.accounts.domain_account.domain_name.clone();
+        let symbol = WRAPPED_DOMAIN_SYMBOL.to_string();
+        let domain_address = domain_key.to_string();
+
+        // Fund the mint for the metadata extension before it reallocates
+        let mint_info = ctx.accounts.mint.to_account_info();
+        let new_len = mint_info.data_len()
+            + token_metadata_space(name.len(), symbol.len(), uri.len())
+            + metadata_field_space(WRAPPED_DOMAIN_FIELD, &domain_address);
+        let required = Rent::get()?.minimum_balance(new_len);
+        let shortfall = required.saturating_sub(mint_info.lamports());
+        if shortfall > 0 {
+            system_program::transfer(
+                CpiContext::new(
+                    ctx.accounts.system_program.to_account_info(),
+                    system_program::Transfer {
+                        from: ctx.accounts.authority.to_account_info(),
+                        to: mint_info.clone(),
+                    },
+                ),
+                shortfall,
+            )?;
+        }
+
+        // Store name/symbol/URI in the mint's token metadata extension
+        token_metadata_initialize(
+            CpiContext::new_with_signer(
+                ctx.accounts.token_program.to_account_info(),
+                TokenMetadataInitialize {
+                    program_id: ctx.accounts.token_program.to_account_info(),
+                    metadata: mint_info.clone(),
+                    update_authority: ctx.accounts.wrap_escrow.to_account_info(),
+                    mint_authority: ctx.accounts.wrap_escrow.to_account_info(),
+                    mint: mint_info.clone(),
+                },
+                signer_seeds,
+            ),
+            name,
+            symbol,
+            uri,
+        )?;
+
+        // Point marketplaces at the domain account the token controls
+        token_metadata_update_field(
+            CpiContext::new_with_signer(
+                ctx.accounts.token_program.to_account_info(),
+                TokenMetadataUpdateField {
+                    program_id: ctx.accounts.token_program.to_account_info(),
+                    metadata: mint_info.clone(),
+                    update_authority: ctx.accounts.wrap_escrow.to_account_info(),
+                },
+                signer_seeds,
+            ),
+            Field::Key(WRAPPED_DOMAIN_FIELD.to_string()),
+            domain_address,
+        )?;
+
+        // Mint the single domain token to the current owner
+        token_interface::mint_to(
+            CpiContext::new_with_signer(
+                ctx.accounts.token_program.to_account_info(),
+                MintTo {
+                    mint: mint_info.clone(),
+                    to: ctx.accounts.holder_token_account.to_account_info(),
+                    authority: ctx.accounts.wrap_escrow.to_account_info(),
+                },
+                signer_seeds,
+            ),
+            1,
+        )?;
+
+        // Revoke minting so wallets see a fixed supply of one
+        token_interface::set_authority(
+            CpiContext::new_with_signer(
+                ctx.accounts.token_program.to_account_info(),
+                SetAuthority {
+                    current_authority: ctx.accounts.wrap_escrow.to_account_info(),
+                    account_or_mint: mint_info,
+                },
+                signer_seeds,
+            ),
+            AuthorityType::MintTokens,
+            None,
+        )?;
+
+        let domain_account = &mut ctx.accounts.domain_account;
+        domain_account.authority = ctx.accounts.wrap_escrow.key();
+        domain_account.wrapped_mint = Some(ctx.accounts.mint.key());
+        // Escrow owns the domain now; an old proposal must not bypass it
+        domain_account.pending_authority = None;
+
+        msg!("✅ Domain wrapped - authority held in escrow");
+        Ok(())
+    }
+
+    /// Unwrap a domain by burning its NFT
+    /// Returns direct authority to the current token holder
+    /// 
+    /// # Process
+    /// 1. Verifies the holder owns the single token of the wrapped mint
+    /// 2. Burns the token
+    /// 3. Sets the domain authority to the holder and clears the mint
+    /// 
+    /// # Notes
+    /// - The empty mint and its metadata are left in place
+    /// 
+    /// # Errors
+    /// - NotWrapped: Domain is not wrapped, or the mint does not match
+    /// - NotTokenHolder: Holder does not own the domain token
+    /// - ProgramPaused: The admin has paused the program
+    pub fn unwrap_domain(ctx: Context<UnwrapDomain>) -> Result<()> {
+        ctx.accounts.config.require_not_paused()?;
+        msg!("📦 NEURA DNS - Unwrapping Domain");
+        msg!("Domain: {}", ctx.accounts.domain_account.domain_name);
+        msg!("Holder: {}", ctx.accounts.holder.key());
+
+        token_interface::burn(
+            CpiContext::new(
+                ctx.accounts.token_program.to_account_info(),
+                Burn {
+                    mint: ctx.accounts.mint.to_account_info(),
+                    from: ctx.accounts.holder_token_account.to_account_info(),
+                    authority: ctx.accounts.holder.to_account_info(),
+                },
+            ),
+            1,
+        )?;
+
+        let domain_account = &mut ctx.accounts.domain_account;
+        domain_account.hand_over(ctx.accounts.holder.key(), Clock::get()?.unix_timestamp);
+        domain_account.wrapped_mint = None;
+
+        msg!("✅ Domain unwrapped - authority restored to holder");
+        Ok(())
+    }
+
+    /// Offer a domain for sale at a fixed price
+    /// 
+    /// # Arguments
+    /// * `price` - Asking price in lamports
+    /// 
+    /// # Notes
+    /// - The seller keeps full control until someone buys; a transfer or
+    ///   re-registration in the meantime voids the listing, even if the
+    ///   domain later comes back to the seller
+    /// - One listing per domain; cancel it to change the price
+    /// 
+    /// # Errors
+    /// - Unauthorized: Signer is not the domain authority
+    /// - GovernanceThreshold: Domain has governance and too few members signed
+    /// - DomainLocked: Domain is locked
+    /// - InvalidPrice: Price is zero
+    /// - FeatureDisabled: The admin has turned off the marketplace
+    pub fn list_for_sale(ctx: Context<ListForSale>, price: u64) -> Result<()> {
+        ctx.accounts.config.require_feature(Feature::Marketplace)?;
+        require_governance(
+            &ctx.accounts.domain_account,
+            &ctx.accounts.governance,
+            ctx.remaining_accounts,
+        )?;
+        require_unlocked(&ctx.accounts.domain_account, &ctx.accounts.lock)?;
+        require!(price > 0, DnsError::InvalidPrice);
+
+        let listing = &mut ctx.accounts.listing;
+        listing.domain = ctx.accounts.domain_account.key();
+        listing.seller = ctx.accounts.authority.key();
+        listing.price = price;
+        listing.listed_at = Clock::get()?.unix_timestamp;
+        listing.bump = ctx.bumps.listing;
+
+        emit!(DomainListed {
+            domain: listing.domain,
+            seller: listing.seller,
+            price,
+            slot: Clock::get()?.slot,
+        });
+
+        msg!("🏷️ NEURA DNS - Domain Listed");
+        msg!("Domain: {}", ctx.accounts.domain_account.domain_name);
+        msg!("Price: {} lamports", price);
+        Ok(())
+    }
+
+    /// Buy a listed domain
+    /// 
+    /// # Arguments
+    /// * `max_price` - Most the buyer will pay, guarding against a relist
+    ///   at a higher price landing first
+    /// 
+    /// # Process
+    /// 1. Checks the listing is still valid and within `max_price`
+    /// 2. Transfers the price from the buyer to the seller
+    /// 3. Makes the buyer the authority and closes the listing, refunding
+    ///    its rent to the seller
+    /// 4. Emits `DomainSold` and `DomainTransferred`
+    /// 
+    /// # Errors
+    /// - ListingStale: Seller no longer owns the domain
+    /// - DomainExpired: Registration has lapsed
+    /// - DomainLocked: Seller locked the domain after listing it
+    /// - PriceAboveMax: Listing price exceeds `max_price`
+    /// - FeatureDisabled: The admin has turned off the marketplace
+    /// - ProgramPaused: The admin has paused the program
+    pub fn buy_domain(ctx: Context<BuyDomain>, max_price: u64) -> Result<()> {
+        ctx.accounts.config.require_not_paused()?;
+        ctx.accounts.config.require_feature(Feature::Marketplace)?;
+        let now = Clock::get()?.unix_timestamp;
+        let listing = &ctx.accounts.listing;
+        let domain_account = &ctx.accounts.domain_account;
+        require!(
+            listing.seller == domain_account.authority
+                && listing.listed_at >= domain_account.owned_since(),
+            DnsError::ListingStale
+        );
+        require!(
+            domain_account.phase(now) == DomainPhase::Active,
+            DnsError::DomainExpired
+        );
+        require_unlocked(domain_account, &ctx.accounts.lock)?;
+        require!(listing.price <= max_price, DnsError::PriceAboveMax);
+
+        let price = listing.price;
+        system_program::transfer(
+            CpiContext::new(
+                ctx.accounts.system_program.to_account_info(),
+                system_program::Transfer {
+                    from: ctx.accounts.buyer.to_account_info(),
+                    to: ctx.accounts.seller.to_account_info(),
+                },
+            ),
+            price,
+        )?;
+
+        let seller = ctx.accounts.seller.key();
+        let buyer = ctx.accounts.buyer.key();
+        let domain_account = &mut ctx.accounts.domain_account;
+        domain_account.hand_over(buyer, now);
+
+        let slot = Clock::get()?.slot;
+        emit!(DomainSold {
+            domain: domain_account.key(),
+            seller,
+            buyer,
+            price,
+            slot,
+        });
+        emit!(DomainTransferred {
+            domain: domain_account.key(),
+            from: seller,
+            to: buyer,
+            timestamp: now,
+            slot,
+        });
+
+        msg!("🛒 NEURA DNS - Domain Sold");
+        msg!("Domain: {}", domain_account.domain_name);
+        msg!("Price: {} lamports", price);
+        msg!("From: {} To: {}", seller, buyer);
+        Ok(())
+    }
+
+    /// Withdraw a listing, refunding its rent to the seller
+    /// 
+    /// # Notes
+    /// - Also cleans up listings voided by a transfer
+    /// 
+    /// # Errors
+    /// - Unauthorized: Signer is not the seller
+    pub fn cancel_listing(ctx: Context<CancelListing>) -> Result<()> {
+        emit!(ListingCancelled {
+            domain: ctx.accounts.listing.domain,
+            seller: ctx.accounts.seller.key(),
+            slot: Clock::get()?.slot,
+        });
+
+        msg!("🏷️ NEURA DNS - Listing Cancelled");
+        msg!("Domain: {}", ctx.accounts.listing.domain);
+        Ok(())
+    }
+
+    /// Deposit a domain into escrow for a private sale to a named buyer
+    /// 
+    /// # Arguments
+    /// * `buyer` - Only account that can complete the sale
+    /// * `price` - Price in lamports the buyer must pay
+    /// 
+    /// # Process
+    /// 1. Creates the escrow PDA and makes it the domain authority
+    /// 2. Until completion or cancellation, nobody can change the domain
+    /// 
+    /// # Errors
+    /// - Unauthorized: Signer is not the domain authority
+    /// - GovernanceThreshold: Domain has governance and too few members signed
+    /// - DomainLocked: Domain is locked
+    /// - InvalidPrice: Price is zero
+    /// - FeatureDisabled: The admin has turned off escrowed sales
+    /// - ProgramPaused: The admin has paused the program
+    pub fn open_escrow(ctx: Context<OpenEscrow>, buyer: Pubkey, price: u64) -> Result<()> {
+        ctx.accounts.config.require_not_paused()?;
+        ctx.accounts.config.require_feature(Feature::Escrow)?;
+        require_governance(
+            &ctx.accounts.domain_account,
+            &ctx.accounts.governance,
+            ctx.remaining_accounts,
+        )?;
+        require_unlocked(&ctx.accounts.domain_account, &ctx.accounts.lock)?;
+        require!(price > 0, DnsError::InvalidPrice);
+
+        let escrow = &mut ctx.accounts.escrow;
+        escrow.domain = ctx.accounts.domain_account.key();
+        escrow.seller = ctx.accounts.authority.key();
+        escrow.buyer = buyer;
+        escrow.price = price;
+        escrow.opened_at = Clock::get()?.unix_timestamp;
+        escrow.bump = ctx.bumps.escrow;
+
+        let domain_account = &mut ctx.accounts.domain_account;
+        domain_account.authority = escrow.key();
+        domain_account.pending_authority = None;
+
+        emit!(EscrowChanged {
+            domain: escrow.domain,
+            seller: escrow.seller,
+            buyer,
+            price,
+            status: EscrowStatus::Opened,
+            slot: Clock::get()?.slot,
+        });
+
+        msg!("🤝 NEURA DNS - Escrow Opened");
+        msg!("Domain: {}", domain_account.domain_name);
+        msg!("Buyer: {}", buyer);
+        msg!("Price: {} lamports", price);
+        Ok(())
+    }
+
+    /// Pay for an escrowed domain and take ownership
+    /// 
+    /// # Process
+    /// 1. Transfers the price from the buyer to the seller
+    /// 2. Makes the buyer the authority and closes the escrow, refunding
+    ///    its rent to the seller
+    /// 
+    /// # Errors
+    /// - Unauthorized: Signer is not the named buyer
+    /// - DomainExpired: Registration lapsed while in escrow
+    /// - ProgramPaused: The admin has paused the program
+    pub fn complete_escrow(ctx: Context<CompleteEscrow>) -> Result<()> {
+        ctx.accounts.config.require_not_paused()?;
+        let now = Clock::get()?.unix_timestamp;
+        require!(
+            ctx.accounts.domain_account.phase(now) == DomainPhase::Active,
+            DnsError::DomainExpired
+        );
+
+        let price = ctx.accounts.escrow.price;
+        system_program::transfer(
+            CpiContext::new(
+                ctx.accounts.system_program.to_account_info(),
+                system_program::Transfer {
+                    from: ctx.accounts.buyer.to_account_info(),
+                    to: ctx.accounts.seller.to_account_info(),
+                },
+            ),
+            price,
+        )?;
+
+        let seller = ctx.accounts.seller.key();
+        let buyer = ctx.accounts.buyer.key();
+        let domain_account = &mut ctx.accounts.domain_account;
+        domain_account.hand_over(buyer, now);
+
+        let slot = Clock::get()?.slot;
+        emit!(EscrowChanged {
+            domain: domain_account.key(),
+            seller,
+            buyer,
+            price,
+            status: EscrowStatus::Completed,
+            slot,
+        });
+        emit!(DomainTransferred {
+            domain: domain_account.key(),
+            from: seller,
+            to: buyer,
+            timestamp: now,
+            slot,
+        });
+
+        msg!("🤝 NEURA DNS - Escrow Completed");
+        msg!("Domain: {}", domain_account.domain_name);
+        msg!("From: {} To: {}", seller, buyer);
+        Ok(())
+    }
+
+    /// Cancel an escrowed sale, returning the domain to the seller
+    /// 
+    /// # Notes
+    /// - Either the seller or the named buyer may cancel
+    /// 
+    /// # Errors
+    /// - Unauthorized: Signer is neither seller nor buyer
+    /// - ProgramPaused: The admin has paused the program
+    pub fn cancel_escrow(ctx: Context<CancelEscrow>) -> Result<()> {
+        ctx.accounts.config.require_not_paused()?;
+        let escrow = &ctx.accounts.escrow;
+        let signer = ctx.accounts.signer.key();
+        require!(
+            signer == escrow.seller || signer == escrow.buyer,
+            DnsError::Unauthorized
+        );
+
+        let domain_account = &mut ctx.accounts.domain_account;
+        domain_account.authority = escrow.seller;
+
+        emit!(EscrowChanged {
+            domain: domain_account.key(),
+            seller: escrow.seller,
+            buyer: escrow.buyer,
+            price: escrow.price,
+            status: EscrowStatus::Cancelled,
+            slot: Clock::get()?.slot,
+        });
+
+        msg!("🤝 NEURA DNS - Escrow Cancelled");
+        msg!("Domain: {}", domain_account.domain_name);
+        Ok(())
+    }
+
+    /// Publish DID document data for a domain
+    /// Lets the domain double as the identifier `did:neura:<domain>`
+    /// 
+    /// # Arguments
+    /// * `verification_keys` - Ed25519 keys listed as verification methods (max 4)
+    /// * `document_uri` - Optional URI of a full DID document (empty for none, max 200 chars)
+    /// 
+    /// # Process
+    /// 1. Creates the DID record PDA on first use
+    /// 2. Overwrites keys and document URI
+    /// 
+    /// # Notes
+    /// - With no keys listed, the domain authority is the only verification method
+    /// - Clients assemble the W3C document from this account and the domain record
+    /// 
+    /// # Errors
+    /// - Unauthorized: Signer is not the domain authority
+    /// - TooManyDidKeys: More than 4 verification keys
+    /// - InvalidDidDocument: Document URI too long
+    /// - GovernanceThreshold: Domain has governance and too few members signed
+    /// - DomainLocked: Domain is locked (see `lock_domain`)
+    /// - UpdateTimelocked: Domain has an update timelock in force
+    /// - ProgramPaused: The admin has paused the program
+    pub fn set_did_document(
+        ctx: Context<SetDidDocument>,
+        verification_keys: Vec<Pubkey>,
+        document_uri: String,
+    ) -> Result<()> {
+        ctx.accounts.config.require_not_paused()?;
+        require_record_write(
+            &ctx.accounts.domain_account,
+            &ctx.accounts.governance,
+            &ctx.accounts.lock,
+            &ctx.accounts.timelock,
+            ctx.remaining_accounts,
+        )?;
+        require!(
+            verification_keys.len() <= MAX_DID_KEYS,
+            DnsError::TooManyDidKeys
+        );
+        require!(
+            document_uri.len() <= MAX_DID_URI_LEN,
+            DnsError::InvalidDidDocument
+        );
+
+        msg!("🪪 NEURA DNS - DID Document Update");
+        msg!("DID: {}", did_for_domain(&ctx.accounts.domain_account.domain_name));
+        msg!("Verification keys: {}", verification_keys.len());
+
+        let did_record = &mut ctx.accounts.did_record;
+        did_record.domain = ctx.accounts.domain_account.key();
+        did_record.verification_keys = verification_keys;
+        did_record.document_uri = document_uri;
+        did_record.updated_at = Clock::get()?.unix_timestamp;
+        did_record.bump = ctx.bumps.did_record;
+
+        msg!("✅ DID document stored on-chain");
+        Ok(())
+    }
+
+    /// Resolve the DID document data for a domain
+    /// 
+    /// # Process
+    /// 1. Logs the DID, controller, verification keys and document URI
+    /// 
+    /// # Returns
+    /// - The same data as a `ResolvedDid`, as instruction return data
+    /// 
+    /// # Notes
+    /// - Read-only operation, no state modification
+    pub fn resolve_did(ctx: Context<ResolveDid>) -> Result<ResolvedDid> {
+        let domain_account = &ctx.accounts.domain_account;
+        let did_record = &ctx.accounts.did_record;
+
+        msg!("🔍 NEURA DNS - DID Resolution Request");
+        msg!("DID: {}", did_for_domain(&domain_account.domain_name));
+        msg!("Controller: {}", domain_account.authority);
+        for key in did_record.verification_keys.iter() {
+            msg!("Verification key: {}", key);
+        }
+        if !did_record.document_uri.is_empty() {
+            msg!("Document: {}", did_record.document_uri);
+        }
+
+        Ok(ResolvedDid {
+            did: did_for_domain(&domain_account.domain_name),
+            controller: domain_account.authority,
+            verification_keys: did_record.verification_keys.clone(),
+            document_uri: did_record.document_uri.clone(),
+        })
+    }
+
+    /// Remove a domain's DID document and refund its rent
+    /// 
+    /// # Errors
+    /// - Unauthorized: Signer is not the domain authority
+    /// - GovernanceThreshold: Domain has governance and too few members signed
+    /// - DomainLocked: Domain is locked (see `lock_domain`)
+    /// - UpdateTimelocked: Domain has an update timelock in force
+    /// - ProgramPaused: The admin has paused the program
+    pub fn remove_did_document(ctx: Context<RemoveDidDocument>) -> Result<()> {
+        ctx.accounts.config.require_not_paused()?;
+        require_record_write(
+            &ctx.accounts.domain_account,
+            &ctx.accounts.governance,
+            &ctx.accounts.lock,
+            &ctx.accounts.timelock,
+            ctx.remaining_accounts,
+        )?;
+        msg!("🗑️ NEURA DNS - DID Document Removed");
+        msg!("DID: {}", did_for_domain(&ctx.accounts.domain_account.domain_name));
+        Ok(())
+    }
+
+    /// Create the program config singleton
+    /// Only the program's upgrade authority can bootstrap it
+    /// 
+    /// # Arguments
+    /// * `verifier` - Oracle key allowed to confirm external verification
+    /// 
+    /// # Process
+    /// 1. Checks the signer is the program's upgrade authority
+    /// 2. Creates the config PDA with the signer as admin and treasury,
+    ///    and no fees
+    /// 
+    /// # Errors
+    /// - NotAdmin: Signer is not the upgrade authority
+    pub fn initialize_config(ctx: Context<InitializeConfig>, verifier: Pubkey) -> Result<()> {
+        let config = &mut ctx.accounts.config;
+        config.admin = ctx.accounts.admin.key();
+        config.verifier = verifier;
+        config.bump = ctx.bumps.config;
+        config.treasury = config.admin;
+        config.registration_fee = 0;
+        config.renewal_fee = 0;
+        config.fee_mint = None;
+        config.token_registration_fee = 0;
+        config.token_renewal_fee = 0;
+        config.premium_multipliers = [1; PREMIUM_TIERS];
+        config.require_commitment = false;
+        config.auction_max_len = 0;
+        config.auction_min_bid = 0;
+        config.auction_duration = 0;
+        config.release_start_premium = 0;
+        config.disabled_features = 0;
+        config.paused = false;
+        config.pending_admin = None;
+
+        msg!("⚙️ NEURA DNS - Config Initialized");
+        msg!("Admin: {}", config.admin);
+        msg!("Verifier: {}", config.verifier);
+        Ok(())
+    }
+
+    /// Replace the external verification oracle
+    /// 
+    /// # Errors
+    /// - NotAdmin: Signer is not the config admin
+    pub fn set_verifier(ctx: Context<UpdateConfig>, verifier: Pubkey) -> Result<()> {
+        ctx.accounts.config.verifier = verifier;
+        msg!("⚙️ Verifier set to: {}", verifier);
         Ok(())
     }
+
+    /// Set the fee schedule and the treasury that receives fees
+    /// 
+    /// # Arguments
+    /// * `treasury` - Account credited with registration and renewal fees
+    /// * `registration_fee` - Lamports charged by `register_request`
+    /// * `renewal_fee` - Lamports charged by `renew_domain`
+    /// 
+    /// # Notes
+    /// - A config created before fees existed must go through
+    ///   `resize_config` first
+    /// 
+    /// # Errors
+    /// - NotAdmin: Signer is not the config admin
+    pub fn set_fees(
+        ctx: Context<UpdateConfig>,
+        treasury: Pubkey,
+        registration_fee: u64,
+        renewal_fee: u64,
+    ) -> Result<()> {
+        let config = &mut ctx.accounts.config;
+        config.treasury = treasury;
+        config.registration_fee = registration_fee;
+        config.renewal_fee = renewal_fee;
+
+        msg!("⚙️ NEURA DNS - Fees Updated");
+        msg!("Treasury: {}", treasury);
+        msg!("Registration: {} lamports, renewal: {} lamports", registration_fee, renewal_fee);
+        Ok(())
+    }
+
+    /// Configure SPL token fee payments
+    /// 
+    /// # Arguments
+    /// * `fee_mint` - Mint fees may be paid in, or None to accept only SOL
+    /// * `registration_fee` - Base units charged by `register_request`
+    /// * `renewal_fee` - Base units charged by `renew_domain`
+    /// 
+    /// # Notes
+    /// - Token fees go to the treasury's token account for `fee_mint`
+    /// - Redemption penalties are always charged in lamports
+    /// 
+    /// # Errors
+    /// - NotAdmin: Signer is not the config admin
+    pub fn set_token_fees(
+        ctx: Context<UpdateConfig>,
+        fee_mint: Option<Pubkey>,
+        registration_fee: u64,
+        renewal_fee: u64,
+    ) -> Result<()> {
+        let config = &mut ctx.accounts.config;
+        config.fee_mint = fee_mint;
+        config.token_registration_fee = registration_fee;
+        config.token_renewal_fee = renewal_fee;
+
+        msg!("⚙️ NEURA DNS - Token Fees Updated");
+        match fee_mint {
+            Some(mint) => msg!("Mint: {}", mint),
+            None => msg!("Mint: none (SOL only)"),
+        }
+        msg!("Registration: {}, renewal: {}", registration_fee, renewal_fee);
+        Ok(())
+    }
+
+    /// Turn one of the optional features on or off
+    /// 
+    /// # Arguments
+    /// * `feature` - Marketplace, Escrow, Leasing, Wrapping or Subdomains
+    /// * `enabled` - Whether new listings, escrows, leases, wraps or
+    ///   subdomains may be created
+    /// 
+    /// # Notes
+    /// - Only entry points are gated; cancelling, ending a lease and
+    ///   unwrapping keep working so nobody gets stuck
+    /// - Features start enabled, including on resized older configs
+    /// 
+    /// # Errors
+    /// - NotAdmin: Signer is not the config admin
+    pub fn set_feature(ctx: Context<UpdateConfig>, feature: Feature, enabled: bool) -> Result<()> {
+        let config = &mut ctx.accounts.config;
+        if enabled {
+            config.disabled_features &= !feature.bit();
+        } else {
+            config.disabled_features |= feature.bit();
+        }
+
+        msg!("⚙️ NEURA DNS - Feature Toggled");
+        msg!("{:?}: {}", feature, if enabled { "enabled" } else { "disabled" });
+        Ok(())
+    }
+
+    /// Nominate a new config admin
+    /// 
+    /// # Arguments
+    /// * `new_admin` - Key that must accept to become the admin
+    /// 
+    /// # Notes
+    /// - Nothing changes until `accept_admin` is signed by the nominee,
+    ///   so a mistyped key can't lock everyone out of the config
+    /// - A new nomination replaces any earlier one; nominating the
+    ///   current admin withdraws it
+    /// 
+    /// # Errors
+    /// - NotAdmin: Signer is not the config admin
+    pub fn nominate_admin(ctx: Context<UpdateConfig>, new_admin: Pubkey) -> Result<()> {
+        let config = &mut ctx.accounts.config;
+        config.pending_admin = Some(new_admin).filter(|key| *key != config.admin);
+
+        msg!("⚙️ NEURA DNS - Admin Nominated");
+        msg!("Admin: {}", config.admin);
+        msg!("Nominee: {}", new_admin);
+        Ok(())
+    }
+
+    /// Accept a nomination and become the config admin
+    /// 
+    /// # Notes
+    /// - The treasury is left as it is; the new admin moves it with
+    ///   `set_fees` if needed
+    /// 
+    /// # Errors
+    /// - NotPendingAdmin: Signer is not the nominated admin
+    pub fn accept_admin(ctx: Context<AcceptAdmin>) -> Result<()> {
+        let config = &mut ctx.accounts.config;
+        let previous_admin = config.admin;
+        config.admin = ctx.accounts.new_admin.key();
+        config.pending_admin = None;
+
+        msg!("⚙️ NEURA DNS - Admin Rotated");
+        msg!("From: {}", previous_admin);
+        msg!("To: {}", config.admin);
+        Ok(())
+    }
+
+    /// Pause or unpause the program in an emergency
+    /// 
+    /// # Arguments
+    /// * `paused` - Whether registrations, record updates and transfers
+    ///   are halted
+    /// 
+    /// # Notes
+    /// - Meant for halting writes while a validation bug is fixed,
+    ///   without upgrading the program
+    /// - Resolution and every other read keeps working while paused
+    /// 
+    /// # Errors
+    /// - NotAdmin: Signer is not the config admin
+    pub fn set_paused(ctx: Context<UpdateConfig>, paused: bool) -> Result<()> {
+        let config = &mut ctx.accounts.config;
+        config.paused = paused;
+
+        msg!("🛑 NEURA DNS - Program {}", if paused { "Paused" } else { "Unpaused" });
+        msg!("Admin: {}", config.admin);
+        Ok(())
+    }
+
+    /// Set the premium multiples charged for short names
+    /// 
+    /// # Arguments
+    /// * `multipliers` - Fee multiple for names of 1, 2, 3 and 4
+    ///   characters, e.g. [1000, 200, 40, 8]; longer names pay 1x
+    /// 
+    /// # Notes
+    /// - Length is that of the leftmost label ("ab.com" is 2 characters)
+    /// - Applies to SOL and token fees, for registration and renewal
+    /// - 0 is read as 1x, so a freshly resized config charges base fees
+    /// 
+    /// # Errors
+    /// - NotAdmin: Signer is not the config admin
+    pub fn set_premium_pricing(ctx: Context<UpdateConfig>, multipliers: [u32; PREMIUM_TIERS]) -> Result<()> {
+        ctx.accounts.config.premium_multipliers = multipliers;
+
+        msg!("⚙️ NEURA DNS - Premium Pricing Updated");
+        msg!("1-4 character multiples: {:?}", multipliers);
+        Ok(())
+    }
+
+    /// Make commit-reveal the only way to register names
+    /// 
+    /// # Arguments
+    /// * `required` - When true, `register_request` fails with
+    ///   `CommitmentRequired` and clients must use `reveal_and_register`
+    /// 
+    /// # Errors
+    /// - NotAdmin: Signer is not the config admin
+    pub fn set_commitment_required(ctx: Context<UpdateConfig>, required: bool) -> Result<()> {
+        ctx.accounts.config.require_commitment = required;
+
+        msg!("⚙️ NEURA DNS - Commit-Reveal Policy Updated");
+        msg!("Commitment required: {}", required);
+        Ok(())
+    }
+
+    /// Send short names to auction instead of first-come registration
+    /// 
+    /// # Arguments
+    /// * `max_len` - Names whose leftmost label is this long or shorter
+    ///   must be auctioned (0 turns auctions off, max 63)
+    /// * `min_bid` - Smallest opening bid in lamports
+    /// * `duration` - Seconds bidding stays open (1 hour to 30 days)
+    /// 
+    /// # Errors
+    /// - NotAdmin: Signer is not the config admin
+    /// - InvalidAuctionPolicy: Length or duration out of range
+    pub fn set_auction_policy(
+        ctx: Context<UpdateConfig>,
+        max_len: u8,
+        min_bid: u64,
+        duration: i64,
+    ) -> Result<()> {
+        require!(
+            max_len as usize <= MAX_LABEL_LEN
+                && (MIN_AUCTION_DURATION..=MAX_AUCTION_DURATION).contains(&duration),
+            DnsError::InvalidAuctionPolicy
+        );
+        let config = &mut ctx.accounts.config;
+        config.auction_max_len = max_len;
+        config.auction_min_bid = min_bid;
+        config.auction_duration = duration;
+
+        msg!("⚙️ NEURA DNS - Auction Policy Updated");
+        msg!("Names up to {} characters, min bid {}, {}s", max_len, min_bid, duration);
+        Ok(())
+    }
+
+    /// Set the starting Dutch-auction premium for reclaimed names
+    /// 
+    /// # Arguments
+    /// * `start_premium` - Lamports charged on top of the fee right after
+    ///   `reclaim_expired`; halves daily and is gone after 21 days
+    /// 
+    /// # Notes
+    /// - Applies to names reclaimed after the change
+    /// 
+    /// # Errors
+    /// - NotAdmin: Signer is not the config admin
+    pub fn set_release_premium(ctx: Context<UpdateConfig>, start_premium: u64) -> Result<()> {
+        ctx.accounts.config.release_start_premium = start_premium;
+
+        msg!("⚙️ NEURA DNS - Release Premium Updated");
+        msg!("Start premium: {} lamports", start_premium);
+        Ok(())
+    }
+
+    /// Create a top-level domain and hand it to an owner
+    /// 
+    /// # Arguments
+    /// * `tld` - Single label, e.g. "neura" or "dao"
+    /// * `owner` - Wallet that will control registrations under it
+    /// 
+    /// # Notes
+    /// - The TLD starts restricted: every new name under it needs the
+    ///   owner (or its registrar) to co-sign; see `set_tld_policy`
+    /// - The revenue share starts at zero, paid to the owner once set
+    /// - Names registered under the TLD before it existed are unaffected
+    /// 
+    /// # Errors
+    /// - NotAdmin: Signer is not the config admin
+    /// - EmptyLabel, LabelTooLong, InvalidLabelCharacter, LabelHyphen,
+    ///   UppercaseDomain: `tld` is not a valid lowercase label
+    pub fn register_tld(ctx: Context<RegisterTld>, tld: String, owner: Pubkey) -> Result<()> {
+        check_label(tld.as_bytes())?;
+        require!(
+            !tld.bytes().any(|b| b.is_ascii_uppercase()),
+            DnsError::UppercaseDomain
+        );
+
+        let record = &mut ctx.accounts.tld;
+        record.tld = tld;
+        record.owner = owner;
+        record.treasury = owner;
+        record.revenue_share_bps = 0;
+        record.registrar = None;
+        record.open = false;
+        record.created_at = Clock::get()?.unix_timestamp;
+        record.bump = ctx.bumps.tld;
+        record.emit_changed()?;
+
+        msg!("🏛️ NEURA DNS - TLD Registered");
+        msg!("TLD: .{}", record.tld);
+        msg!("Owner: {}", owner);
+        Ok(())
+    }
+
+    /// Choose who may register names under a TLD
+    /// 
+    /// # Arguments
+    /// * `open` - Let anyone register without an approval signature
+    /// * `registrar` - Extra key whose signature counts as the owner's
+    ///   approval (e.g. a registrar service's hot wallet)
+    /// 
+    /// # Errors
+    /// - Unauthorized: Signer is not the TLD owner
+    pub fn set_tld_policy(
+        ctx: Context<UpdateTld>,
+        open: bool,
+        registrar: Option<Pubkey>,
+    ) -> Result<()> {
+        let tld = &mut ctx.accounts.tld;
+        tld.open = open;
+        tld.registrar = registrar;
+        tld.emit_changed()?;
+
+        msg!("🏛️ NEURA DNS - TLD Policy Updated");
+        msg!("TLD: .{} open: {}", tld.tld, open);
+        Ok(())
+    }
+
+    /// Hand a TLD to a new owner
+    /// 
+    /// # Notes
+    /// - Clears the registrar and points the fee share at the new owner
+    /// 
+    /// # Errors
+    /// - Unauthorized: Signer is not the TLD owner
+    pub fn transfer_tld(ctx: Context<UpdateTld>, new_owner: Pubkey) -> Result<()> {
+        let tld = &mut ctx.accounts.tld;
+        tld.owner = new_owner;
+        tld.treasury = new_owner;
+        tld.registrar = None;
+        tld.emit_changed()?;
+
+        msg!("🏛️ NEURA DNS - TLD Transferred");
+        msg!("TLD: .{} to {}", tld.tld, new_owner);
+        Ok(())
+    }
+
+    /// Set the share of fees under a TLD that goes to its owner
+    /// 
+    /// # Arguments
+    /// * `share_bps` - Basis points (0-10000) of each registration and
+    ///   renewal fee under the TLD
+    /// 
+    /// # Notes
+    /// - Applies to the base fee with its premium multiple, in lamports or
+    ///   the fee mint; release premiums, auction bids and redemption
+    ///   penalties go to the protocol treasury in full
+    /// 
+    /// # Errors
+    /// - NotAdmin: Signer is not the config admin
+    /// - InvalidRevenueShare: Share above 10000 basis points
+    pub fn set_tld_revenue_share(ctx: Context<UpdateTldTerms>, share_bps: u16) -> Result<()> {
+        require!(share_bps as u64 <= BPS_DENOMINATOR, DnsError::InvalidRevenueShare);
+        let tld = &mut ctx.accounts.tld;
+        tld.revenue_share_bps = share_bps;
+        tld.emit_changed()?;
+
+        msg!("🏛️ NEURA DNS - TLD Revenue Share Updated");
+        msg!("TLD: .{} share: {} bps", tld.tld, share_bps);
+        Ok(())
+    }
+
+    /// Choose where a TLD's share of fees is paid
+    /// 
+    /// # Errors
+    /// - Unauthorized: Signer is not the TLD owner
+    pub fn set_tld_treasury(ctx: Context<UpdateTld>, treasury: Pubkey) -> Result<()> {
+        let tld = &mut ctx.accounts.tld;
+        tld.treasury = treasury;
+        tld.emit_changed()?;
+
+        msg!("🏛️ NEURA DNS - TLD Treasury Updated");
+        msg!("TLD: .{} treasury: {}", tld.tld, treasury);
+        Ok(())
+    }
+
+    /// Grow a config created by an older program version to the current size
+    /// 
+    /// # Process
+    /// 1. Checks the raw account is the config and the signer its admin
+    /// 2. Tops up rent and resizes the account (new bytes zeroed)
+    /// 3. Sets the treasury to the admin if none was set; new fees start
+    ///    at zero
+    /// 
+    /// # Notes
+    /// - Done on raw data because a short config can't be deserialized
+    ///   until it has been resized
+    /// 
+    /// # Errors
+    /// - NotAdmin: Signer is not the config admin
+    /// - LayoutUpToDate: Config already has the current size
+    pub fn resize_config(ctx: Context<ResizeConfig>) -> Result<()> {
+        let config = ctx.accounts.config.to_account_info();
+        let new_len = 8 + ProgramConfig::INIT_SPACE;
+        require!(config.data_len() < new_len, DnsError::LayoutUpToDate);
+        {
+            let data = config.try_borrow_data()?;
+            require!(
+                data.len() >= 40 && data[..8] == *ProgramConfig::DISCRIMINATOR,
+                DnsError::NotAdmin
+            );
+            require!(
+                data[8..40] == ctx.accounts.admin.key().to_bytes(),
+                DnsError::NotAdmin
+            );
+        }
+
+        let shortfall = Rent::get()?
+            .minimum_balance(new_len)
+            .saturating_sub(config.lamports());
+        if shortfall > 0 {
+            system_program::transfer(
+                CpiContext::new(
+                    ctx.accounts.system_program.to_account_info(),
+                    system_program::Transfer {
+                        from: ctx.accounts.admin.to_account_info(),
+                        to: config.clone(),
+                    },
+                ),
+                shortfall,
+            )?;
+        }
+        config.resize(new_len)?;
+
+        let mut data = config.try_borrow_mut_data()?;
+        let mut program_config = ProgramConfig::try_deserialize(&mut &data[..])?;
+        if program_config.treasury == Pubkey::default() {
+            program_config.treasury = program_config.admin;
+        }
+        program_config.try_serialize(&mut &mut data[..])?;
+
+        msg!("⚙️ NEURA DNS - Config Resized to {} bytes", new_len);
+        Ok(())
+    }
+
+    /// Start external verification of a domain
+    /// Issues a challenge the owner proves in traditional DNS or email
+    /// 
+    /// # Arguments
+    /// * `method` - How the challenge will be proven (DNS TXT or DKIM email)
+    /// 
+    /// # Process
+    /// 1. Derives a challenge from the domain, authority and current slot
+    /// 2. Stores it in a verification request PDA
+    /// 3. Logs where to publish it
+    /// 
+    /// # Proving the Challenge
+    /// - DnsTxt: publish the hex challenge as TXT at `_neura-challenge.<domain>`
+    /// - DkimEmail: send a DKIM-signed email from the domain with the
+    ///   challenge in the subject to the verifier
+    /// 
+    /// # Errors
+    /// - Unauthorized: Signer is not the domain authority
+    pub fn request_external_verification(
+        ctx: Context<RequestExternalVerification>,
+        method: VerificationMethod,
+    ) -> Result<()> {
+        let domain_key = ctx.accounts.domain_account.key();
+        let authority_key = ctx.accounts.authority.key();
+        let clock = Clock::get()?;
+        let digest = hashv(&[
+            domain_key.as_ref(),
+            authority_key.as_ref(),
+            &clock.slot.to_le_bytes(),
+        ])
+        .to_bytes();
+
+        let request = &mut ctx.accounts.verification_request;
+        request.domain = domain_key;
+        request.authority = authority_key;
+        request.method = method;
+        request.challenge.copy_from_slice(&digest[..CHALLENGE_LEN]);
+        request.requested_at = clock.unix_timestamp;
+        request.bump = ctx.bumps.verification_request;
+
+        msg!("🔐 NEURA DNS - External Verification Requested");
+        msg!("Domain: {}", ctx.accounts.domain_account.domain_name);
+        msg!("Challenge: {}", to_hex(&request.challenge));
+        if method == VerificationMethod::DnsTxt {
+            msg!(
+                "Publish TXT at: {}.{}",
+                CHALLENGE_TXT_LABEL,
+                ctx.accounts.domain_account.domain_name
+            );
+        }
+        Ok(())
+    }
+
+    /// Confirm an external verification challenge
+    /// Called by the verifier oracle after checking DNS or the DKIM signature
+    /// 
+    /// # Process
+    /// 1. Sets `verified_external` on the domain
+    /// 2. Closes the request PDA, refunding the domain owner
+    /// 
+    /// # Errors
+    /// - NotVerifier: Signer is not the configured verifier
+    /// - StaleVerification: Domain authority changed since the request
+    pub fn confirm_external_verification(
+        ctx: Context<ConfirmExternalVerification>,
+    ) -> Result<()> {
+        let domain_account = &mut ctx.accounts.domain_account;
+        domain_account.verified_external = true;
+
+        msg!("🏅 NEURA DNS - External Verification Confirmed");
+        msg!("Domain: {}", domain_account.domain_name);
+        Ok(())
+    }
+
+    /// Revoke a domain's external verification badge
+    /// 
+    /// # Errors
+    /// - NotVerifier: Signer is not the configured verifier
+    pub fn revoke_external_verification(
+        ctx: Context<RevokeExternalVerification>,
+    ) -> Result<()> {
+        let domain_account = &mut ctx.accounts.domain_account;
+        domain_account.verified_external = false;
+
+        msg!("⚠️ NEURA DNS - External Verification Revoked");
+        msg!("Domain: {}", domain_account.domain_name);
+        Ok(())
+    }
+
+    /// Publish an OpenPGP public key for an email address at this domain
+    /// Mirrors DNS OPENPGPKEY records (RFC 7929)
+    /// 
+    /// # Arguments
+    /// * `localpart` - Part of the email address before the `@` (max 64 bytes)
+    /// * `key` - Binary transferable public key, starting with a public-key packet
+    /// 
+    /// # Process
+    /// 1. Validates the local part and the leading OpenPGP packet
+    /// 2. Creates or overwrites the key PDA for the hashed local part
+    /// 
+    /// # Storage
+    /// - Uses PDA with seeds: ["openpgpkey", domain_account, sha256(localpart)[..28]]
+    /// 
+    /// # Errors
+    /// - Unauthorized: Signer is not the domain authority
+    /// - InvalidLocalPart: Local part is empty or longer than 64 bytes
+    /// - InvalidOpenPgpKey: Key is empty, too large or not a public-key packet
+    /// - GovernanceThreshold: Domain has governance and too few members signed
+    /// - DomainLocked: Domain is locked (see `lock_domain`)
+    /// - UpdateTimelocked: Domain has an update timelock in force
+    /// - ProgramPaused: The admin has paused the program
+    pub fn set_openpgp_key(
+        ctx: Context<SetOpenPgpKey>,
+        localpart: String,
+        key: Vec<u8>,
+    ) -> Result<()> {
+        ctx.accounts.config.require_not_paused()?;
+        require_record_write(
+            &ctx.accounts.domain_account,
+            &ctx.accounts.governance,
+            &ctx.accounts.lock,
+            &ctx.accounts.timelock,
+            ctx.remaining_accounts,
+        )?;
+        validate_localpart(&localpart)?;
+        validate_openpgp_key(&key)?;
+
+        msg!("🔑 NEURA DNS - OPENPGPKEY Update");
+        msg!(
+            "Owner name: {}",
+            openpgpkey_owner_name(&localpart, &ctx.accounts.domain_account.domain_name)
+        );
+        msg!("Key size: {} bytes", key.len());
+
+        let key_record = &mut ctx.accounts.key_record;
+        key_record.domain = ctx.accounts.domain_account.key();
+        key_record.localpart_hash = openpgpkey_hash(&localpart);
+        key_record.key = key;
+        key_record.updated_at = Clock::get()?.unix_timestamp;
+        key_record.bump = ctx.bumps.key_record;
+
+        msg!("✅ OpenPGP key stored on-chain");
+        Ok(())
+    }
+
+    /// Look up the OpenPGP key for an email address at this domain
+    /// 
+    /// # Arguments
+    /// * `localpart` - Part of the email address before the `@`
+    /// 
+    /// # Returns
+    /// - The transferable public key bytes, as instruction return data
+    /// 
+    /// # Notes
+    /// - Logs a summary (owner name, size, digest) alongside
+    pub fn resolve_openpgp_key(
+        ctx: Context<ResolveOpenPgpKey>,
+        localpart: String,
+    ) -> Result<Vec<u8>> {
+        let key_record = &ctx.accounts.key_record;
+        require!(
+            key_record.updated_at >= ctx.accounts.domain_account.created_at,
+            DnsError::OrphanedRecord
+        );
+
+        msg!("🔍 NEURA DNS - OPENPGPKEY Lookup");
+        msg!(
+            "Owner name: {}",
+            openpgpkey_owner_name(&localpart, &ctx.accounts.domain_account.domain_name)
+        );
+        msg!("✅ Key found: {} bytes", key_record.key.len());
+        msg!("Key digest: {}", hash(&key_record.key));
+        Ok(key_record.key.clone())
+    }
+
+    /// Remove the OpenPGP key for an email address and refund its rent
+    /// 
+    /// # Errors
+    /// - Unauthorized: Signer is not the domain authority
+    /// - GovernanceThreshold: Domain has governance and too few members signed
+    /// - DomainLocked: Domain is locked (see `lock_domain`)
+    /// - UpdateTimelocked: Domain has an update timelock in force
+    /// - ProgramPaused: The admin has paused the program
+    pub fn remove_openpgp_key(ctx: Context<RemoveOpenPgpKey>, localpart: String) -> Result<()> {
+        ctx.accounts.config.require_not_paused()?;
+        require_record_write(
+            &ctx.accounts.domain_account,
+            &ctx.accounts.governance,
+            &ctx.accounts.lock,
+            &ctx.accounts.timelock,
+            ctx.remaining_accounts,
+        )?;
+        msg!("🗑️ NEURA DNS - OPENPGPKEY Removed");
+        msg!(
+            "Owner name: {}",
+            openpgpkey_owner_name(&localpart, &ctx.accounts.domain_account.domain_name)
+        );
+        Ok(())
+    }
+
+    /// Add or replace an SSHFP fingerprint for a domain's host key
+    /// Mirrors DNS SSHFP records (RFC 4255 / RFC 6594 / RFC 7479)
+    /// 
+    /// # Arguments
+    /// * `algorithm` - Key algorithm: 1 RSA, 2 DSA, 3 ECDSA, 4 Ed25519, 6 Ed448
+    /// * `fingerprint_type` - Digest: 1 SHA-1 (20 bytes), 2 SHA-256 (32 bytes)
+    /// * `fingerprint` - Raw digest bytes of the host key
+    /// 
+    /// # Process
+    /// 1. Validates algorithm, digest type and digest length
+    /// 2. Replaces any entry with the same (algorithm, fingerprint_type)
+    /// 3. Otherwise appends a new entry (max 8 per domain)
+    /// 
+    /// # Errors
+    /// - Unauthorized: Signer is not the domain authority
+    /// - InvalidSshfp: Unknown algorithm/type or wrong digest length
+    /// - TooManySshfpRecords: Domain already has 8 fingerprints
+    /// - GovernanceThreshold: Domain has governance and too few members signed
+    /// - DomainLocked: Domain is locked (see `lock_domain`)
+    /// - UpdateTimelocked: Domain has an update timelock in force
+    /// - ProgramPaused: The admin has paused the program
+    pub fn set_sshfp(
+        ctx: Context<SetSshfp>,
+        algorithm: u8,
+        fingerprint_type: u8,
+        fingerprint: Vec<u8>,
+    ) -> Result<()> {
+        ctx.accounts.config.require_not_paused()?;
+        require_record_write(
+            &ctx.accounts.domain_account,
+            &ctx.accounts.governance,
+            &ctx.accounts.lock,
+            &ctx.accounts.timelock,
+            ctx.remaining_accounts,
+        )?;
+        validate_sshfp(algorithm, fingerprint_type, &fingerprint)?;
+
+        let domain_key = ctx.accounts.domain_account.key();
+        let sshfp_records = &mut ctx.accounts.sshfp_records;
+        sshfp_records.domain = domain_key;
+        sshfp_records.bump = ctx.bumps.sshfp_records;
+
+        let entry = SshfpEntry {
+            algorithm,
+            fingerprint_type,
+            fingerprint,
+        };
+        match sshfp_records
+            .entries
+            .iter_mut()
+            .find(|e| e.algorithm == algorithm && e.fingerprint_type == fingerprint_type)
+        {
+            Some(existing) => *existing = entry,
+            None => {
+                require!(
+                    sshfp_records.entries.len() < MAX_SSHFP_RECORDS,
+                    DnsError::TooManySshfpRecords
+                );
+                sshfp_records.entries.push(entry);
+            }
+        }
+
+        msg!("🖥️ NEURA DNS - SSHFP Update");
+        msg!("Domain: {}", ctx.accounts.domain_account.domain_name);
+        msg!("Algorithm: {} / Type: {}", algorithm, fingerprint_type);
+        Ok(())
+    }
+
+    /// Remove the SSHFP fingerprint for one (algorithm, fingerprint_type)
+    /// 
+    /// # Errors
+    /// - Unauthorized: Signer is not the domain authority
+    /// - SshfpNotFound: No fingerprint with that algorithm and type
+    /// - GovernanceThreshold: Domain has governance and too few members signed
+    /// - DomainLocked: Domain is locked (see `lock_domain`)
+    /// - UpdateTimelocked: Domain has an update timelock in force
+    /// - ProgramPaused: The admin has paused the program
+    pub fn remove_sshfp(ctx: Context<RemoveSshfp>, algorithm: u8, fingerprint_type: u8) -> Result<()> {
+        ctx.accounts.config.require_not_paused()?;
+        require_record_write(
+            &ctx.accounts.domain_account,
+            &ctx.accounts.governance,
+            &ctx.accounts.lock,
+            &ctx.accounts.timelock,
+            ctx.remaining_accounts,
+        )?;
+        let entries = &mut ctx.accounts.sshfp_records.entries;
+        let before = entries.len();
+        entries.retain(|e| !(e.algorithm == algorithm && e.fingerprint_type == fingerprint_type));
+        require!(entries.len() < before, DnsError::SshfpNotFound);
+
+        msg!("🗑️ NEURA DNS - SSHFP Removed");
+        msg!("Algorithm: {} / Type: {}", algorithm, fingerprint_type);
+        Ok(())
+    }
+
+    /// Resolve a domain's SSHFP fingerprints
+    /// 
+    /// # Process
+    /// 1. Logs each fingerprint as a zone-file SSHFP line
+    /// 
+    /// # Returns
+    /// - The fingerprint entries, as instruction return data
+    /// 
+    /// # Notes
+    /// - Output matches what `ssh -o VerifyHostKeyDNS=yes` expects from DNS
+    /// - Read-only operation, no state modification
+    pub fn resolve_sshfp(ctx: Context<ResolveSshfp>) -> Result<Vec<SshfpEntry>> {
+        let domain_name = &ctx.accounts.domain_account.domain_name;
+
+        msg!("🔍 NEURA DNS - SSHFP Lookup");
+        for entry in ctx.accounts.sshfp_records.entries.iter() {
+            msg!(
+                "{}. IN SSHFP {} {} {}",
+                domain_name,
+                entry.algorithm,
+                entry.fingerprint_type,
+                to_hex(&entry.fingerprint)
+            );
+        }
+        Ok(ctx.accounts.sshfp_records.entries.clone())
+    }
+
+    /// Add or replace a DS record for the domain's zone
+    /// Mirrors DNS DS records (RFC 4034), for the parent side of a DNSSEC
+    /// chain of trust
+    /// 
+    /// # Arguments
+    /// * `key_tag` - Key tag of the DNSKEY the digest covers
+    /// * `algorithm` - DNSKEY algorithm: 8, 10, 13, 14, 15 or 16
+    /// * `digest_type` - 2 SHA-256 (32 bytes) or 4 SHA-384 (48 bytes)
+    /// * `digest` - Digest as hex, as shown in a zone file
+    /// 
+    /// # Process
+    /// 1. Validates algorithm, digest type and the hex digest's length
+    /// 2. Replaces any entry with the same (key_tag, algorithm, digest_type)
+    /// 3. Otherwise appends a new entry (max 4 per domain)
+    /// 
+    /// # Errors
+    /// - Unauthorized: Signer is not the domain authority
+    /// - InvalidDnssecRecord: Unsupported algorithm/digest type, or the
+    ///   digest is not hex of the right length
+    /// - TooManyDnssecRecords: Domain already has 4 DS records
+    /// - GovernanceThreshold: Domain has governance and too few members signed
+    /// - DomainLocked: Domain is locked (see `lock_domain`)
+    /// - UpdateTimelocked: Domain has an update timelock in force
+    /// - ProgramPaused: The admin has paused the program
+    pub fn set_ds(
+        ctx: Context<SetDnssec>,
+        key_tag: u16,
+        algorithm: u8,
+        digest_type: u8,
+        digest: String,
+    ) -> Result<()> {
+        ctx.accounts.config.require_not_paused()?;
+        require_record_write(
+            &ctx.accounts.domain_account,
+            &ctx.accounts.governance,
+            &ctx.accounts.lock,
+            &ctx.accounts.timelock,
+            ctx.remaining_accounts,
+        )?;
+        let digest = validate_ds(algorithm, digest_type, &digest)?;
+        let now = Clock::get()?.unix_timestamp;
+        let domain_account = &ctx.accounts.domain_account;
+        let dnssec_records = &mut ctx.accounts.dnssec_records;
+        dnssec_records.claim(domain_account, ctx.bumps.dnssec_records, now);
+
+        let entry = DsEntry {
+            key_tag,
+            algorithm,
+            digest_type,
+            digest,
+        };
+        match dnssec_records.ds.iter_mut().find(|e| {
+            e.key_tag == key_tag && e.algorithm == algorithm && e.digest_type == digest_type
+        }) {
+            Some(existing) => *existing = entry,
+            None => {
+                require!(
+                    dnssec_records.ds.len() < MAX_DNSSEC_RECORDS,
+                    DnsError::TooManyDnssecRecords
+                );
+                dnssec_records.ds.push(entry);
+            }
+        }
+
+        msg!("🔏 NEURA DNS - DS Update");
+        msg!("Domain: {}", domain_account.domain_name);
+        msg!("Key tag: {} / Algorithm: {} / Digest type: {}", key_tag, algorithm, digest_type);
+        Ok(())
+    }
+
+    /// Remove the DS record for one (key_tag, algorithm, digest_type)
+    /// 
+    /// # Errors
+    /// - Unauthorized: Signer is not the domain authority
+    /// - DnssecRecordNotFound: No DS record with that key tag, algorithm
+    ///   and digest type
+    /// - GovernanceThreshold: Domain has governance and too few members signed
+    /// - DomainLocked: Domain is locked (see `lock_domain`)
+    /// - UpdateTimelocked: Domain has an update timelock in force
+    /// - ProgramPaused: The admin has paused the program
+    pub fn remove_ds(
+        ctx: Context<RemoveDnssec>,
+        key_tag: u16,
+        algorithm: u8,
+        digest_type: u8,
+    ) -> Result<()> {
+        ctx.accounts.config.require_not_paused()?;
+        require_record_write(
+            &ctx.accounts.domain_account,
+            &ctx.accounts.governance,
+            &ctx.accounts.lock,
+            &ctx.accounts.timelock,
+            ctx.remaining_accounts,
+        )?;
+        let dnssec_records = &mut ctx.accounts.dnssec_records;
+        let before = dnssec_records.ds.len();
+        dnssec_records.ds.retain(|e| {
+            !(e.key_tag == key_tag && e.algorithm == algorithm && e.digest_type == digest_type)
+        });
+        require!(dnssec_records.ds.len() < before, DnsError::DnssecRecordNotFound);
+        dnssec_records.updated_at = Clock::get()?.unix_timestamp;
+
+        msg!("🗑️ NEURA DNS - DS Removed");
+        msg!("Key tag: {} / Algorithm: {} / Digest type: {}", key_tag, algorithm, digest_type);
+        Ok(())
+    }
+
+    /// Add a DNSKEY record for the domain's zone
+    /// Mirrors DNS DNSKEY records (RFC 4034)
+    /// 
+    /// # Arguments
+    /// * `flags` - 256 (zone signing key) or 257 (key signing key)
+    /// * `protocol` - Must be 3
+    /// * `algorithm` - 8 RSA/SHA-256, 10 RSA/SHA-512, 13 ECDSA P-256,
+    ///   14 ECDSA P-384, 15 Ed25519, 16 Ed448
+    /// * `public_key` - Key in DNSKEY wire format, as hex
+    /// 
+    /// # Process
+    /// 1. Validates flags, protocol and the key length for the algorithm
+    /// 2. Computes the key tag (RFC 4034 Appendix B), which registrars
+    ///    ask for alongside the DS digest
+    /// 3. Replaces an entry with the same key, otherwise appends (max 4)
+    /// 
+    /// # Errors
+    /// - Unauthorized: Signer is not the domain authority
+    /// - InvalidDnssecRecord: Bad flags, protocol or algorithm, or the key
+    ///   is not hex of a valid length (RSA keys up to 2048 bits)
+    /// - TooManyDnssecRecords: Domain already has 4 DNSKEY records
+    /// - GovernanceThreshold: Domain has governance and too few members signed
+    /// - DomainLocked: Domain is locked (see `lock_domain`)
+    /// - UpdateTimelocked: Domain has an update timelock in force
+    /// - ProgramPaused: The admin has paused the program
+    pub fn set_dnskey(
+        ctx: Context<SetDnssec>,
+        flags: u16,
+        protocol: u8,
+        algorithm: u8,
+        public_key: String,
+    ) -> Result<()> {
+        ctx.accounts.config.require_not_paused()?;
+        require_record_write(
+            &ctx.accounts.domain_account,
+            &ctx.accounts.governance,
+            &ctx.accounts.lock,
+            &ctx.accounts.timelock,
+            ctx.remaining_accounts,
+        )?;
+        let public_key = validate_dnskey(flags, protocol, algorithm, &public_key)?;
+        let key_tag = dnskey_tag(flags, protocol, algorithm, &public_key);
+        let now = Clock::get()?.unix_timestamp;
+        let domain_account = &ctx.accounts.domain_account;
+        let dnssec_records = &mut ctx.accounts.dnssec_records;
+        dnssec_records.claim(domain_account, ctx.bumps.dnssec_records, now);
+
+        let entry = DnskeyEntry {
+            flags,
+            protocol,
+            algorithm,
+            key_tag,
+            public_key,
+        };
+        match dnssec_records
+            .dnskeys
+            .iter_mut()
+            .find(|e| e.algorithm == algorithm && e.public_key == entry.public_key)
+        {
+            Some(existing) => *existing = entry,
+            None => {
+                require!(
+                    dnssec_records.dnskeys.len() < MAX_DNSSEC_RECORDS,
+                    DnsError::TooManyDnssecRecords
+                );
+                dnssec_records.dnskeys.push(entry);
+            }
+        }
+
+        msg!("🔏 NEURA DNS - DNSKEY Update");
+        msg!("Domain: {}", domain_account.domain_name);
+        msg!("Flags: {} / Algorithm: {} / Key tag: {}", flags, algorithm, key_tag);
+        Ok(())
+    }
+
+    /// Remove the DNSKEY records with one (key_tag, algorithm)
+    /// 
+    /// # Errors
+    /// - Unauthorized: Signer is not the domain authority
+    /// - DnssecRecordNotFound: No DNSKEY with that key tag and algorithm
+    /// - GovernanceThreshold: Domain has governance and too few members signed
+    /// - DomainLocked: Domain is locked (see `lock_domain`)
+    /// - UpdateTimelocked: Domain has an update timelock in force
+    /// - ProgramPaused: The admin has paused the program
+    pub fn remove_dnskey(ctx: Context<RemoveDnssec>, key_tag: u16, algorithm: u8) -> Result<()> {
+        ctx.accounts.config.require_not_paused()?;
+        require_record_write(
+            &ctx.accounts.domain_account,
+            &ctx.accounts.governance,
+            &ctx.accounts.lock,
+            &ctx.accounts.timelock,
+            ctx.remaining_accounts,
+        )?;
+        let dnssec_records = &mut ctx.accounts.dnssec_records;
+        let before = dnssec_records.dnskeys.len();
+        dnssec_records
+            .dnskeys
+            .retain(|e| !(e.key_tag == key_tag && e.algorithm == algorithm));
+        require!(
+            dnssec_records.dnskeys.len() < before,
+            DnsError::DnssecRecordNotFound
+        );
+        dnssec_records.updated_at = Clock::get()?.unix_timestamp;
+
+        msg!("🗑️ NEURA DNS - DNSKEY Removed");
+        msg!("Key tag: {} / Algorithm: {}", key_tag, algorithm);
+        Ok(())
+    }
+
+    /// Resolve a domain's DS and DNSKEY records
+    /// 
+    /// # Returns
+    /// - The DS and DNSKEY entries, as instruction return data
+    /// 
+    /// # Notes
+    /// - Zone-file lines are logged only in `verbose-logs` builds
+    /// 
+    /// # Errors
+    /// - OrphanedRecord: Records were set under a previous registration
+    pub fn resolve_dnssec(ctx: Context<ResolveDnssec>) -> Result<ResolvedDnssec> {
+        let domain_account = &ctx.accounts.domain_account;
+        let dnssec_records = &ctx.accounts.dnssec_records;
+        require!(
+            dnssec_records.updated_at >= domain_account.created_at,
+            DnsError::OrphanedRecord
+        );
+
+        verbose_msg!("🔍 NEURA DNS - DNSSEC Lookup");
+        for ds in dnssec_records.ds.iter() {
+            verbose_msg!(
+                "{}. IN DS {} {} {} {}",
+                domain_account.domain_name,
+                ds.key_tag,
+                ds.algorithm,
+                ds.digest_type,
+                to_hex(&ds.digest)
+            );
+        }
+        for key in dnssec_records.dnskeys.iter() {
+            verbose_msg!(
+                "{}. IN DNSKEY {} {} {} <{} bytes> ; key tag {}",
+                domain_account.domain_name,
+                key.flags,
+                key.protocol,
+                key.algorithm,
+                key.public_key.len(),
+                key.key_tag
+            );
+        }
+        Ok(ResolvedDnssec {
+            ds: dnssec_records.ds.clone(),
+            dnskeys: dnssec_records.dnskeys.clone(),
+        })
+    }
+
+    /// Attach a third-party attestation to a domain
+    /// Signed by the attester, e.g. a KYC provider or security auditor
+    /// 
+    /// # Arguments
+    /// * `claim_type` - Short claim identifier, e.g. "kyc-business" (max 32 chars)
+    /// * `claim_uri` - Where the full credential lives (max 200 chars)
+    /// * `claim_hash` - SHA-256 of the full credential document
+    /// * `expires_at` - Optional Unix timestamp after which the claim lapses
+    /// 
+    /// # Process
+    /// 1. Creates an attestation PDA keyed by domain and attester
+    /// 2. Stores the claim as pending until the domain owner accepts it
+    /// 
+    /// # Errors
+    /// - InvalidAttestation: Empty/oversized fields or expiry in the past
+    pub fn add_attestation(
+        ctx: Context<AddAttestation>,
+        claim_type: String,
+        claim_uri: String,
+        claim_hash: [u8; 32],
+        expires_at: Option<i64>,
+    ) -> Result<()> {
+        let now = Clock::get()?.unix_timestamp;
+        require!(
+            !claim_type.is_empty() && claim_type.len() <= MAX_CLAIM_TYPE_LEN,
+            DnsError::InvalidAttestation
+        );
+        require!(
+            claim_uri.len() <= MAX_CLAIM_URI_LEN,
+            DnsError::InvalidAttestation
+        );
+        require!(
+            expires_at.map_or(true, |t| t > now),
+            DnsError::InvalidAttestation
+        );
+
+        let attestation = &mut ctx.accounts.attestation;
+        attestation.domain = ctx.accounts.domain_account.key();
+        attestation.attester = ctx.accounts.attester.key();
+        attestation.claim_type = claim_type;
+        attestation.claim_uri = claim_uri;
+        attestation.claim_hash = claim_hash;
+        attestation.issued_at = now;
+        attestation.expires_at = expires_at;
+        attestation.accepted = false;
+        attestation.bump = ctx.bumps.attestation;
+
+        msg!("📜 NEURA DNS - Attestation Added");
+        msg!("Domain: {}", ctx.accounts.domain_account.domain_name);
+        msg!("Attester: {}", attestation.attester);
+        msg!("Claim: {}", attestation.claim_type);
+        Ok(())
+    }
+
+    /// Accept a pending attestation so it is shown with the domain
+    /// 
+    /// # Errors
+    /// - Unauthorized: Signer is not the domain authority
+    pub fn accept_attestation(ctx: Context<AcceptAttestation>) -> Result<()> {
+        ctx.accounts.attestation.accepted = true;
+
+        msg!("✅ NEURA DNS - Attestation Accepted");
+        msg!("Attester: {}", ctx.accounts.attestation.attester);
+        Ok(())
+    }
+
+    /// Remove an attestation from a domain (owner side)
+    /// Rent goes back to the attester who paid for it
+    /// 
+    /// # Errors
+    /// - Unauthorized: Signer is not the domain authority
+    pub fn remove_attestation(ctx: Context<RemoveAttestation>) -> Result<()> {
+        msg!("🗑️ NEURA DNS - Attestation Removed by Owner");
+        msg!("Attester: {}", ctx.accounts.attestation.attester);
+        Ok(())
+    }
+
+    /// Withdraw an attestation (attester side)
+    /// 
+    /// # Errors
+    /// - ConstraintSeeds: Signer is not the attester of this claim
+    pub fn revoke_attestation(ctx: Context<RevokeAttestation>) -> Result<()> {
+        msg!("⚠️ NEURA DNS - Attestation Revoked by Attester");
+        msg!("Attester: {}", ctx.accounts.attester.key());
+        Ok(())
+    }
+
+    /// Attach an updater-fed dynamic record to a domain
+    /// Lets monitoring infrastructure repoint the name at a healthy endpoint
+    /// 
+    /// # Arguments
+    /// * `updater` - Key allowed to push new values (e.g. a health checker)
+    /// * `max_staleness` - Seconds a pushed value stays valid (60-604800)
+    /// 
+    /// # Process
+    /// 1. Creates or reconfigures the dynamic record PDA
+    /// 2. Seeds it with the static record so it is valid immediately
+    /// 
+    /// # Notes
+    /// - A fresh dynamic value wins over the static record, so this needs
+    ///   the same governance, lock and timelock checks as `update_record`;
+    ///   the updater's pushes stop while the domain is locked
+    /// 
+    /// # Errors
+    /// - Unauthorized: Signer is not the domain authority
+    /// - InvalidStaleness: Freshness bound outside 60 seconds - 7 days
+    /// - GovernanceThreshold: Domain has governance and too few members signed
+    /// - DomainLocked: Domain is locked (see `lock_domain`)
+    /// - UpdateTimelocked: Domain has an update timelock in force
+    /// - ProgramPaused: The admin has paused the program
+    pub fn configure_dynamic_record(
+        ctx: Context<ConfigureDynamicRecord>,
+        updater: Pubkey,
+        max_staleness: u32,
+    ) -> Result<()> {
+        ctx.accounts.config.require_not_paused()?;
+        require_record_write(
+            &ctx.accounts.domain_account,
+            &ctx.accounts.governance,
+            &ctx.accounts.lock,
+            &ctx.accounts.timelock,
+            ctx.remaining_accounts,
+        )?;
+        require!(
+            (MIN_DYNAMIC_STALENESS..=MAX_DYNAMIC_STALENESS).contains(&max_staleness),
+            DnsError::InvalidStaleness
+        );
+
+        let dynamic = &mut ctx.accounts.dynamic_record;
+        dynamic.domain = ctx.accounts.domain_account.key();
+        dynamic.updater = updater;
+        dynamic.max_staleness = max_staleness;
+        dynamic.value = ctx.accounts.domain_account.record.clone();
+        dynamic.updated_at = Clock::get()?.unix_timestamp;
+        dynamic.bump = ctx.bumps.dynamic_record;
+
+        msg!("⚡ NEURA DNS - Dynamic Record Configured");
+        msg!("Domain: {}", ctx.accounts.domain_account.domain_name);
+        msg!("Updater: {}", updater);
+        msg!("Max staleness: {}s", max_staleness);
+        Ok(())
+    }
+
+    /// Push a new value to a dynamic record
+    /// 
+    /// # Arguments
+    /// * `value` - New IPv4 address to resolve to
+    /// 
+    /// # Errors
+    /// - NotUpdater: Signer is not the configured updater
+    /// - InvalidIp: Value is not a valid IPv4 address
+    /// - DomainLocked: Domain is locked (see `lock_domain`)
+    /// - ProgramPaused: The admin has paused the program
+    pub fn push_dynamic_record(ctx: Context<PushDynamicRecord>, value: String) -> Result<()> {
+        ctx.accounts.config.require_not_paused()?;
+        require_unlocked(&ctx.accounts.domain_account, &ctx.accounts.lock)?;
+        validate_ip(&value)?;
+
+        let dynamic = &mut ctx.accounts.dynamic_record;
+        dynamic.value = value;
+        dynamic.updated_at = Clock::get()?.unix_timestamp;
+
+        msg!("⚡ Dynamic record updated: {}", dynamic.value);
+        Ok(())
+    }
+
+    /// Detach the dynamic record and refund its rent
+    /// 
+    /// # Errors
+    /// - Unauthorized: Signer is not the domain authority
+    /// - GovernanceThreshold: Domain has governance and too few members signed
+    /// - DomainLocked: Domain is locked (see `lock_domain`)
+    /// - UpdateTimelocked: Domain has an update timelock in force
+    /// - ProgramPaused: The admin has paused the program
+    pub fn remove_dynamic_record(ctx: Context<RemoveDynamicRecord>) -> Result<()> {
+        ctx.accounts.config.require_not_paused()?;
+        require_record_write(
+            &ctx.accounts.domain_account,
+            &ctx.accounts.governance,
+            &ctx.accounts.lock,
+            &ctx.accounts.timelock,
+            ctx.remaining_accounts,
+        )?;
+        msg!("🗑️ NEURA DNS - Dynamic Record Removed");
+        msg!("Domain: {}", ctx.accounts.domain_account.domain_name);
+        Ok(())
+    }
+
+    /// Move a domain from the legacy raw-name PDA to the hashed PDA
+    /// For accounts created before seeds were hashed
+    /// 
+    /// # Arguments
+    /// * `domain_name` - Name of the domain (at most 32 bytes for legacy PDAs)
+    /// 
+    /// # Process
+    /// 1. Loads the legacy account at ["domain", domain_name.as_bytes()]
+    /// 2. Creates the new account at ["domain", sha256(domain_name)]
+    /// 3. Copies every field across and adds the new PDA to the index
+    /// 4. Closes the legacy account, refunding its rent to the authority
+    /// 
+    /// # Notes
+    /// - Wrapped domains must be unwrapped first (the escrow cannot sign)
+    /// - Child records (DID, SSHFP, OPENPGPKEY, dynamic, attestations) are
+    ///   keyed by the domain account and must be re-published afterwards
+    /// 
+    /// # Errors
+    /// - Unauthorized: Signer is not the domain authority
+    pub fn migrate_domain_account(
+        ctx: Context<MigrateDomainAccount>,
+        domain_name: String,
+    ) -> Result<()> {
+        let legacy = &ctx.accounts.legacy_account;
+        let domain_account = &mut ctx.accounts.domain_account;
+
+        domain_account.domain_name = legacy.domain_name.clone();
+        domain_account.record = legacy.record.clone();
+        domain_account.authority = legacy.authority;
+        domain_account.created_at = legacy.created_at;
+        domain_account.wrapped_mint = legacy.wrapped_mint;
+        domain_account.verified_external = legacy.verified_external;
+        domain_account.updated_at = legacy.created_at;
+        domain_account.ttl = DEFAULT_TTL;
+        domain_account.owned_since = legacy.created_at;
+        domain_account.expires_at = Clock::get()?.unix_timestamp + REGISTRATION_PERIOD;
+        domain_account.layout_version = DOMAIN_LAYOUT_CURRENT;
+        domain_account.index_page = append_to_index(
+            &mut ctx.accounts.namespace_index,
+            &mut ctx.accounts.index_page,
+            domain_account.key(),
+        )?;
+
+        msg!("🚚 NEURA DNS - Domain Account Migrated");
+        msg!("Domain: {}", domain_name);
+        msg!("Legacy PDA: {}", legacy.key());
+        msg!("New PDA: {}", domain_account.key());
+        Ok(())
+    }
+
+    /// Create the namespace index and its first page
+    /// One-time setup, callable by anyone who pays the rent
+    /// 
+    /// # Process
+    /// 1. Creates the index head PDA
+    /// 2. Creates page 0 as the initial tail
+    pub fn initialize_index(ctx: Context<InitializeIndex>) -> Result<()> {
+        let first_page = &mut ctx.accounts.first_page;
+        first_page.page = 0;
+        first_page.next = None;
+        first_page.bump = ctx.bumps.first_page;
+
+        let namespace_index = &mut ctx.accounts.namespace_index;
+        namespace_index.page_count = 1;
+        namespace_index.total_domains = 0;
+        namespace_index.bump = ctx.bumps.namespace_index;
+
+        msg!("📚 NEURA DNS - Namespace Index Initialized");
+        Ok(())
+    }
+
+    /// Link a new empty page after a full tail page
+    /// Permissionless so registrations never wait on an operator
+    /// 
+    /// # Process
+    /// 1. Checks the current tail page is full
+    /// 2. Creates the next page and links it from the old tail
+    /// 3. Advances the index's page count
+    /// 
+    /// # Errors
+    /// - IndexPageNotFull: Tail page still has room
+    pub fn append_index_page(ctx: Context<AppendIndexPage>) -> Result<()> {
+        require!(
+            ctx.accounts.tail_page.domains.len() >= INDEX_PAGE_CAPACITY,
+            DnsError::IndexPageNotFull
+        );
+
+        let namespace_index = &mut ctx.accounts.namespace_index;
+        let new_page = &mut ctx.accounts.new_page;
+        new_page.page = namespace_index.page_count;
+        new_page.next = None;
+        new_page.bump = ctx.bumps.new_page;
+
+        ctx.accounts.tail_page.next = Some(new_page.key());
+        namespace_index.page_count += 1;
+
+        msg!("📚 NEURA DNS - Index Page {} Appended", new_page.page);
+        Ok(())
+    }
+
+    /// Create the availability Bloom filter
+    /// 
+    /// # Notes
+    /// - Starts in rebuilding mode; the admin loads existing names and
+    ///   then calls `finish_filter_rebuild`
+    /// 
+    /// # Errors
+    /// - NotAdmin: Signer is not the config admin
+    pub fn initialize_availability_filter(ctx: Context<InitializeAvailabilityFilter>) -> Result<()> {
+        let mut filter = ctx.accounts.availability_filter.load_init()?;
+        filter.rebuilding = 1;
+        filter.bump = ctx.bumps.availability_filter;
+
+        msg!("🌸 NEURA DNS - Availability Filter Initialized");
+        Ok(())
+    }
+
+    /// Clear the Bloom filter to purge names that are no longer registered
+    /// 
+    /// # Process
+    /// 1. Zeroes all bits and the item count
+    /// 2. Bumps the generation and enters rebuilding mode
+    /// 
+    /// # Notes
+    /// - Registrations keep inserting while rebuilding
+    /// - Clients must not trust "definitely available" answers until
+    ///   `rebuilding` is cleared
+    pub fn begin_filter_rebuild(ctx: Context<ManageAvailabilityFilter>) -> Result<()> {
+        let mut filter = ctx.accounts.availability_filter.load_mut()?;
+        filter.bits = [0u8; BLOOM_FILTER_BYTES];
+        filter.item_count = 0;
+        filter.generation = filter.generation.wrapping_add(1);
+        filter.rebuilding = 1;
+
+        msg!("🌸 Filter rebuild started (generation {})", filter.generation);
+        Ok(())
+    }
+
+    /// Load a batch of registered name hashes during a rebuild
+    /// 
+    /// # Arguments
+    /// * `name_hashes` - sha256(domain_name) of registered domains
+    /// 
+    /// # Errors
+    /// - FilterNotRebuilding: No rebuild in progress
+    pub fn load_filter_entries(
+        ctx: Context<ManageAvailabilityFilter>,
+        name_hashes: Vec<[u8; 32]>,
+    ) -> Result<()> {
+        let mut filter = ctx.accounts.availability_filter.load_mut()?;
+        require!(filter.rebuilding == 1, DnsError::FilterNotRebuilding);
+        for name_hash in name_hashes.iter() {
+            filter.insert(name_hash);
+        }
+
+        msg!("🌸 Loaded {} entries ({} total)", name_hashes.len(), filter.item_count);
+        Ok(())
+    }
+
+    /// Mark a rebuild complete so clients can trust the filter again
+    /// 
+    /// # Errors
+    /// - FilterNotRebuilding: No rebuild in progress
+    pub fn finish_filter_rebuild(ctx: Context<ManageAvailabilityFilter>) -> Result<()> {
+        let mut filter = ctx.accounts.availability_filter.load_mut()?;
+        require!(filter.rebuilding == 1, DnsError::FilterNotRebuilding);
+        filter.rebuilding = 0;
+
+        msg!("🌸 Filter rebuild finished ({} entries)", filter.item_count);
+        Ok(())
+    }
+
+    /// Upgrade a domain account to the current storage layout
+    /// 
+    /// # Process
+    /// 1. Reallocates the account to the current layout size (zero-filled)
+    /// 2. Converts fields from the stored version, one step at a time
+    /// 3. Stamps `layout_version` and emits `DomainAccountUpgraded`
+    /// 
+    /// # Notes
+    /// - Permissionless: layouts carry the same data, so anyone may pay
+    ///   the rent difference and roll accounts forward in batches
+    /// - Accounts created before `layout_version` existed read 0 from the
+    ///   zeroed growth margin and are treated as unversioned V1
+    /// 
+    /// # Errors
+    /// - LayoutUpToDate: Account already uses the current layout
+    pub fn upgrade_domain_account(ctx: Context<UpgradeDomainAccount>) -> Result<()> {
+        let domain_account = &mut ctx.accounts.domain_account;
+        let from_version = domain_account.layout_version;
+        require!(from_version < DOMAIN_LAYOUT_CURRENT, DnsError::LayoutUpToDate);
+
+        domain_account.migrate_layout(Clock::get()?.unix_timestamp);
+
+        emit!(DomainAccountUpgraded {
+            domain: domain_account.key(),
+            from_version,
+            to_version: DOMAIN_LAYOUT_CURRENT,
+            space: domain_account.to_account_info().data_len() as u32,
+        });
+
+        msg!("⬆️ NEURA DNS - Domain Account Upgraded");
+        msg!("Domain: {}", domain_account.domain_name);
+        msg!("Layout: v{} -> v{}", from_version, DOMAIN_LAYOUT_CURRENT);
+        Ok(())
+    }
+
+    /// Create the protocol stats account
+    /// One-time setup, callable by anyone who pays the rent
+    pub fn initialize_stats(ctx: Context<InitializeStats>) -> Result<()> {
+        ctx.accounts.protocol_stats.bump = ctx.bumps.protocol_stats;
+
+        msg!("📊 NEURA DNS - Protocol Stats Initialized");
+        Ok(())
+    }
+
+    /// Replace a domain's record
+    /// 
+    /// # Arguments
+    /// * `record` - New record value (e.g., "1.1.1.1")
+    /// * `record_type` - Type of the new record
+    /// * `ttl` - Seconds resolvers may cache the answer (30-86400)
+    /// 
+    /// # Process
+    /// 1. Validates the new record and TTL
+    /// 2. Resizes the account to fit it (rent difference paid/refunded)
+    /// 3. Stores the record, TTL and `updated_at` timestamp
+    /// 4. Appends the new value to the domain's record history
+    /// 5. Emits `DomainUpdated`
+    /// 
+    /// # Notes
+    /// - Also brings an older account up to the current layout, since the
+    ///   realloc sizes it for every current field
+    /// - The first update creates the history PDA (signer pays rent) and
+    ///   seeds it with the value being replaced
+    /// - The signer may be the authority or its operator (`set_operator`);
+    ///   pass `operator_grant` when signing as the operator
+    /// - A key with per-type rights (`grant_record_types`) may sign too,
+    ///   passing `record_acl`, if it holds both the current and the new
+    ///   record type, so it can't overwrite a type it wasn't given
+    /// - Governed domains also need `threshold` member signatures, passed
+    ///   as `remaining_accounts`, whoever signs
+    /// - Wrapped domains are owned by escrow; the token holder signs and
+    ///   passes `holder_token_account` instead
+    /// - While a lease runs only the tenant may update, and the owner-side
+    ///   checks (operator, governance, lock, timelock) are skipped
+    /// - Timelocked domains (`set_update_delay`) refuse direct updates;
+    ///   the owner schedules them with `schedule_update` instead
+    /// 
+    /// # Errors
+    /// - InvalidIp, InvalidIpv6, InvalidHostname, InvalidMxRecord,
+    ///   InvalidTxtRecord: Record does not match its type
+    /// - Unauthorized: Signer is not the authority, its operator, a key
+    ///   granted both record types, or the holder of a wrapped domain's token
+    /// - GovernanceThreshold: Domain has governance and too few members signed
+    /// - DomainLocked: Domain is locked (see `lock_domain`)
+    /// - DomainLeased: Domain is leased and signer is not the tenant
+    /// - UpdateTimelocked: Domain has an update timelock in force
+    /// - InvalidTtl: TTL outside 30-86400 seconds
+    /// - CnameConflict: Switching to CNAME while record sets exist
+    /// - ProgramPaused: The admin has paused the program
+    pub fn update_record(
+        ctx: Context<UpdateRecord>,
+        record: String,
+        record_type: RecordType,
+        ttl: u32,
+    ) -> Result<()> {
+        ctx.accounts.config.require_not_paused()?;
+        let signer = ctx.accounts.signer.key();
+        let domain_account = &mut ctx.accounts.domain_account;
+        let now = Clock::get()?.unix_timestamp;
+        if let Some(tenant) = active_lease(domain_account, &ctx.accounts.lease, now)? {
+            // The owner agreed to the lease, governance and lock included
+            require_keys_eq!(signer, tenant, DnsError::DomainLeased);
+        } else {
+            require!(
+                signer == domain_account.authority
+                    || ctx
+                        .accounts
+                        .operator_grant
+                        .as_ref()
+                        .map_or(false, |grant| grant.allows(domain_account, &signer))
+                    || ctx.accounts.record_acl.as_ref().map_or(false, |acl| {
+                        acl.allows(domain_account, &signer, domain_account.record_type)
+                            && acl.allows(domain_account, &signer, record_type)
+                    })
+                    || ctx
+                        .accounts
+                        .holder_token_account
+                        .as_ref()
+                        .map_or(false, |token| holds_domain_token(domain_account, token, &signer)),
+                DnsError::Unauthorized
+            );
+            require_governance(domain_account, &ctx.accounts.governance, ctx.remaining_accounts)?;
+            require_unlocked(domain_account, &ctx.accounts.lock)?;
+            require!(
+                update_delay(domain_account, &ctx.accounts.timelock, now)?.is_none(),
+                DnsError::UpdateTimelocked
+            );
+        }
+        write_record(
+            domain_account,
+            &mut ctx.accounts.record_history,
+            ctx.bumps.record_history,
+            signer,
+            record,
+            record_type,
+            ttl,
+        )
+    }
+
+    /// Read a domain's past records
+    /// 
+    /// # Arguments
+    /// * `skip` - Number of newest entries to skip, for paging
+    /// 
+    /// # Returns
+    /// - History entries, newest first, as instruction return data
+    /// 
+    /// # Notes
+    /// - Keeps the last 8 values; `record_history.total` counts every
+    ///   value ever written
+    /// - Stops early rather than exceed the 1 KiB return data limit;
+    ///   page with `skip`, or fetch the `RecordHistory` account directly
+    /// - History survives unregistration, so it also covers past owners
+    pub fn get_history(ctx: Context<GetHistory>, skip: u8) -> Result<Vec<HistoryEntry>> {
+        let mut size = 4;
+        let entries = ctx
+            .accounts
+            .record_history
+            .newest_first()
+            .skip(skip as usize)
+            .take_while(|entry| {
+                size += entry.serialized_len();
+                size <= MAX_RETURN_DATA
+            })
+            .cloned()
+            .collect();
+        Ok(entries)
+    }
+
+    /// Let a secondary key update the domain's record
+    /// 
+    /// # Arguments
+    /// * `operator` - Key allowed to call `update_record`
+    /// 
+    /// # Notes
+    /// - The operator can only replace the primary record; transfers,
+    ///   unregistering and every other change still need the authority
+    /// - A new call replaces the previous operator
+    /// - Grants lapse when the domain changes owner or is registered anew
+    /// 
+    /// # Errors
+    /// - Unauthorized: Signer is not the domain authority
+    pub fn set_operator(ctx: Context<SetOperator>, operator: Pubkey) -> Result<()> {
+        let now = Clock::get()?.unix_timestamp;
+        let grant = &mut ctx.accounts.operator_grant;
+        grant.domain = ctx.accounts.domain_account.key();
+        grant.operator = operator;
+        grant.granted_by = ctx.accounts.authority.key();
+        grant.granted_at = now;
+        grant.bump = ctx.bumps.operator_grant;
+
+        emit!(OperatorChanged {
+            domain: grant.domain,
+            operator: Some(operator),
+            slot: Clock::get()?.slot,
+        });
+
+        msg!("🔑 NEURA DNS - Operator Set");
+        msg!("Domain: {}", ctx.accounts.domain_account.domain_name);
+        msg!("Operator: {}", operator);
+        Ok(())
+    }
+
+    /// Revoke the domain's operator, refunding the grant's rent
+    /// 
+    /// # Errors
+    /// - Unauthorized: Signer is not the domain authority
+    pub fn revoke_operator(ctx: Context<RevokeOperator>) -> Result<()> {
+        emit!(OperatorChanged {
+            domain: ctx.accounts.domain_account.key(),
+            operator: None,
+            slot: Clock::get()?.slot,
+        });
+
+        msg!("🔑 NEURA DNS - Operator Revoked");
+        msg!("Domain: {}", ctx.accounts.domain_account.domain_name);
+        msg!("Operator: {}", ctx.accounts.operator_grant.operator);
+        Ok(())
+    }
+
+    /// Let a key update the domain's record for some record types only
+    /// 
+    /// # Arguments
+    /// * `key` - Key being granted rights, e.g. an ops key
+    /// * `record_types` - Record types the key may write, e.g. [A, Aaaa]
+    /// 
+    /// # Notes
+    /// - Replaces the key's previous rights; a domain holds up to 8 keys
+    /// - Like operators, ACL keys can only call `update_record`, and only
+    ///   when both the current and the new record type are theirs
+    /// - The whole ACL lapses when the domain changes owner or is
+    ///   registered anew
+    /// 
+    /// # Errors
+    /// - Unauthorized: Signer is not the domain authority
+    /// - InvalidAclGrant: No record types given
+    /// - TooManyAclEntries: Domain already grants 8 other keys
+    pub fn grant_record_types(
+        ctx: Context<GrantRecordTypes>,
+        key: Pubkey,
+        record_types: Vec<RecordType>,
+    ) -> Result<()> {
+        require!(!record_types.is_empty(), DnsError::InvalidAclGrant);
+        let now = Clock::get()?.unix_timestamp;
+        let domain_account = &ctx.accounts.domain_account;
+        let acl = &mut ctx.accounts.record_acl;
+        if !acl.is_current(domain_account) {
+            acl.entries.clear();
+        }
+        acl.domain = domain_account.key();
+        acl.granted_by = domain_account.authority;
+        acl.granted_at = now;
+        acl.bump = ctx.bumps.record_acl;
+
+        let mask = record_types
+            .iter()
+            .fold(0u16, |mask, record_type| mask | RecordAcl::bit(*record_type));
+        match acl.entries.iter_mut().find(|entry| entry.key == key) {
+            Some(entry) => entry.record_types = mask,
+            None => {
+                require!(
+                    acl.entries.len() < MAX_ACL_ENTRIES,
+                    DnsError::TooManyAclEntries
+                );
+                acl.entries.push(AclEntry {
+                    key,
+                    record_types: mask,
+                });
+            }
+        }
+
+        emit!(RecordAclChanged {
+            domain: acl.domain,
+            key,
+            record_types: record_types.clone(),
+            slot: Clock::get()?.slot,
+        });
+
+        msg!("🔑 NEURA DNS - Record Types Granted");
+        msg!("Domain: {}", domain_account.domain_name);
+        msg!("Key: {}", key);
+        for record_type in record_types.iter() {
+            msg!("Type: {}", record_type.mnemonic());
+        }
+        Ok(())
+    }
+
+    /// Revoke a key's per-type rights
+    /// 
+    /// # Errors
+    /// - Unauthorized: Signer is not the domain authority
+    /// - AclEntryNotFound: The key holds no rights on this domain
+    pub fn revoke_record_types(ctx: Context<RevokeRecordTypes>, key: Pubkey) -> Result<()> {
+        let entries = &mut ctx.accounts.record_acl.entries;
+        let before = entries.len();
+        entries.retain(|entry| entry.key != key);
+        require!(entries.len() < before, DnsError::AclEntryNotFound);
+
+        emit!(RecordAclChanged {
+            domain: ctx.accounts.domain_account.key(),
+            key,
+            record_types: Vec::new(),
+            slot: Clock::get()?.slot,
+        });
+
+        msg!("🔑 NEURA DNS - Record Types Revoked");
+        msg!("Domain: {}", ctx.accounts.domain_account.domain_name);
+        msg!("Key: {}", key);
+        Ok(())
+    }
+
+    /// Require M-of-N member signatures for a domain's high-risk actions
+    /// 
+    /// # Arguments
+    /// * `members` - Keys that can approve (up to 10, no duplicates)
+    /// * `threshold` - Member signatures required (1 to `members.len()`)
+    /// 
+    /// # Notes
+    /// - Once set, `update_record`, `propose_transfer`, `wrap_domain` and
+    ///   `unregister_domain` need `threshold` members to sign alongside
+    ///   the usual signer; members sign as `remaining_accounts`
+    /// - Changing existing governance needs its current threshold too
+    /// - The authority may also be a member and counts when it is
+    /// - Governance lapses when the domain changes owner or is registered anew
+    /// 
+    /// # Errors
+    /// - Unauthorized: Signer is not the domain authority
+    /// - InvalidGovernance: Bad threshold, duplicate or too many members
+    /// - GovernanceThreshold: Existing governance not satisfied
+    pub fn set_governance(
+        ctx: Context<SetGovernance>,
+        members: Vec<Pubkey>,
+        threshold: u8,
+    ) -> Result<()> {
+        require!(
+            !members.is_empty()
+                && members.len() <= MAX_GOVERNANCE_MEMBERS
+                && threshold >= 1
+                && threshold as usize <= members.len(),
+            DnsError::InvalidGovernance
+        );
+        for (i, member) in members.iter().enumerate() {
+            require!(!members[..i].contains(member), DnsError::InvalidGovernance);
+        }
+
+        let domain_account = &ctx.accounts.domain_account;
+        let governance = &mut ctx.accounts.governance;
+        if governance.applies_to(domain_account) {
+            require!(
+                governance.approvals(ctx.remaining_accounts) >= governance.threshold,
+                DnsError::GovernanceThreshold
+            );
+        }
+        governance.domain = domain_account.key();
+        governance.authority = domain_account.authority;
+        governance.members = members;
+        governance.threshold = threshold;
+        governance.created_at = Clock::get()?.unix_timestamp;
+        governance.bump = ctx.bumps.governance;
+
+        msg!("🏛️ NEURA DNS - Governance Set");
+        msg!("Domain: {}", domain_account.domain_name);
+        msg!("Threshold: {} of {}", threshold, governance.members.len());
+        Ok(())
+    }
+
+    /// Drop a domain's governance, refunding its rent to the authority
+    /// 
+    /// # Errors
+    /// - Unauthorized: Signer is not the domain authority
+    /// - GovernanceThreshold: Too few members signed
+    pub fn remove_governance(ctx: Context<RemoveGovernance>) -> Result<()> {
+        let governance = &ctx.accounts.governance;
+        if governance.applies_to(&ctx.accounts.domain_account) {
+            require!(
+                governance.approvals(ctx.remaining_accounts) >= governance.threshold,
+                DnsError::GovernanceThreshold
+            );
+        }
+
+        msg!("🏛️ NEURA DNS - Governance Removed");
+        msg!("Domain: {}", ctx.accounts.domain_account.domain_name);
+        Ok(())
+    }
+
+    /// Freeze a domain's record and ownership behind an unlock delay
+    /// 
+    /// # Arguments
+    /// * `unlock_delay` - Seconds between `request_unlock` and the lock
+    ///   lifting (1 day to 365 days)
+    /// 
+    /// # Notes
+    /// - Blocks `update_record` (owner and operator), `propose_transfer`
+    ///   and `wrap_domain`; renewal keeps working
+    /// - Clears any pending transfer proposal
+    /// - A stolen key can only start the unlock clock, which emits
+    ///   `DomainLockChanged` and gives the owner `unlock_delay` to react
+    /// - The delay can't be shortened while the lock exists
+    /// 
+    /// # Errors
+    /// - Unauthorized: Signer is not the domain authority
+    /// - InvalidUnlockDelay: Delay outside 1-365 days
+    pub fn lock_domain(ctx: Context<LockDomain>, unlock_delay: i64) -> Result<()> {
+        require!(
+            (MIN_UNLOCK_DELAY..=MAX_UNLOCK_DELAY).contains(&unlock_delay),
+            DnsError::InvalidUnlockDelay
+        );
+        let now = Clock::get()?.unix_timestamp;
+        let domain_account = &mut ctx.accounts.domain_account;
+        domain_account.pending_authority = None;
+
+        let lock = &mut ctx.accounts.lock;
+        lock.domain = domain_account.key();
+        lock.locked_by = domain_account.authority;
+        lock.locked_at = now;
+        lock.unlock_delay = unlock_delay;
+        lock.unlock_requested_at = 0;
+        lock.bump = ctx.bumps.lock;
+
+        emit!(DomainLockChanged {
+            domain: lock.domain,
+            locked: true,
+            unlocks_at: None,
+            slot: Clock::get()?.slot,
+        });
+
+        msg!("🔒 NEURA DNS - Domain Locked");
+        msg!("Domain: {}", domain_account.domain_name);
+        msg!("Unlock delay: {}s", unlock_delay);
+        Ok(())
+    }
+
+    /// Start the unlock delay of a locked domain
+    /// 
+    /// # Notes
+    /// - Repeated requests keep the first request time
+    /// 
+    /// # Errors
+    /// - Unauthorized: Signer is not the domain authority
+    pub fn request_unlock(ctx: Context<ModifyLock>) -> Result<()> {
+        let lock = &mut ctx.accounts.lock;
+        if lock.unlock_requested_at == 0 {
+            lock.unlock_requested_at = Clock::get()?.unix_timestamp;
+        }
+        let unlocks_at = lock.unlock_requested_at + lock.unlock_delay;
+
+        emit!(DomainLockChanged {
+            domain: lock.domain,
+            locked: true,
+            unlocks_at: Some(unlocks_at),
+            slot: Clock::get()?.slot,
+        });
+
+        msg!("🔓 NEURA DNS - Unlock Requested");
+        msg!("Domain: {}", ctx.accounts.domain_account.domain_name);
+        msg!("Unlocks at: {}", unlocks_at);
+        Ok(())
+    }
+
+    /// Stop a pending unlock, keeping the domain locked
+    /// 
+    /// # Errors
+    /// - Unauthorized: Signer is not the domain authority
+    pub fn cancel_unlock(ctx: Context<ModifyLock>) -> Result<()> {
+        let lock = &mut ctx.accounts.lock;
+        lock.unlock_requested_at = 0;
+
+        emit!(DomainLockChanged {
+            domain: lock.domain,
+            locked: true,
+            unlocks_at: None,
+            slot: Clock::get()?.slot,
+        });
+
+        msg!("🔒 NEURA DNS - Unlock Cancelled");
+        msg!("Domain: {}", ctx.accounts.domain_account.domain_name);
+        Ok(())
+    }
+
+    /// Remove a lock whose unlock delay has elapsed, refunding its rent
+    /// 
+    /// # Errors
+    /// - Unauthorized: Signer is not the domain authority
+    /// - DomainLocked: Unlock not requested, or the delay hasn't passed
+    pub fn unlock_domain(ctx: Context<UnlockDomain>) -> Result<()> {
+        let lock = &ctx.accounts.lock;
+        require!(
+            !lock.is_locked(Clock::get()?.unix_timestamp),
+            DnsError::DomainLocked
+        );
+
+        emit!(DomainLockChanged {
+            domain: lock.domain,
+            locked: false,
+            unlocks_at: None,
+            slot: Clock::get()?.slot,
+        });
+
+        msg!("🔓 NEURA DNS - Domain Unlocked");
+        msg!("Domain: {}", ctx.accounts.domain_account.domain_name);
+        Ok(())
+    }
+
+    /// Make record updates wait out a delay the owner can cancel within
+    /// 
+    /// # Arguments
+    /// * `delay` - Seconds between `schedule_update` and the change taking
+    ///   effect (1 hour to 30 days)
+    /// 
+    /// # Notes
+    /// - Once set, `update_record` is refused; changes go through
+    ///   `schedule_update` and `execute_update`, so a briefly compromised
+    ///   key can't repoint traffic without `ScheduledUpdateChanged` firing
+    ///   a delay ahead
+    /// - The delay can't be shortened while the timelock is in force;
+    ///   calling again with the same or a longer delay also cancels a
+    ///   pending `request_timelock_removal`
+    /// - The timelock lapses when the domain changes owner or is
+    ///   registered anew
+    /// 
+    /// # Errors
+    /// - Unauthorized: Signer is not the domain authority
+    /// - InvalidUpdateDelay: Delay outside 1 hour-30 days, or shorter than
+    ///   the delay in force
+    pub fn set_update_delay(ctx: Context<SetUpdateDelay>, delay: i64) -> Result<()> {
+        require!(
+            (MIN_UPDATE_DELAY..=MAX_UPDATE_DELAY).contains(&delay),
+            DnsError::InvalidUpdateDelay
+        );
+        let now = Clock::get()?.unix_timestamp;
+        let domain_account = &ctx.accounts.domain_account;
+        let timelock = &mut ctx.accounts.timelock;
+        if timelock.applies_to(domain_account) && timelock.is_in_force(now) {
+            require!(delay >= timelock.delay, DnsError::InvalidUpdateDelay);
+        }
+        timelock.domain = domain_account.key();
+        timelock.set_by = domain_account.authority;
+        timelock.set_at = now;
+        timelock.delay = delay;
+        timelock.removal_requested_at = 0;
+        timelock.bump = ctx.bumps.timelock;
+
+        emit!(UpdateTimelockChanged {
+            domain: timelock.domain,
+            delay: Some(delay),
+            removed_at: None,
+            slot: Clock::get()?.slot,
+        });
+
+        msg!("⏳ NEURA DNS - Update Timelock Set");
+        msg!("Domain: {}", domain_account.domain_name);
+        msg!("Delay: {}s", delay);
+        Ok(())
+    }
+
+    /// Start the clock on removing the update timelock
+    /// 
+    /// # Notes
+    /// - The timelock stays in force for one more `delay`; repeated
+    ///   requests keep the first request time
+    /// - Cancel with `set_update_delay`
+    /// 
+    /// # Errors
+    /// - Unauthorized: Signer is not the domain authority
+    pub fn request_timelock_removal(ctx: Context<ModifyTimelock>) -> Result<()> {
+        let timelock = &mut ctx.accounts.timelock;
+        if timelock.removal_requested_at == 0 {
+            timelock.removal_requested_at = Clock::get()?.unix_timestamp;
+        }
+        let removed_at = timelock.removal_requested_at + timelock.delay;
+
+        emit!(UpdateTimelockChanged {
+            domain: timelock.domain,
+            delay: Some(timelock.delay),
+            removed_at: Some(removed_at),
+            slot: Clock::get()?.slot,
+        });
+
+        msg!("⏳ NEURA DNS - Timelock Removal Requested");
+        msg!("Domain: {}", ctx.accounts.domain_account.domain_name);
+        msg!("Lifts at: {}", removed_at);
+        Ok(())
+    }
+
+    /// Close an update timelock that is no longer in force, refunding
+    /// its rent
+    /// 
+    /// # Errors
+    /// - Unauthorized: Signer is not the domain authority
+    /// - UpdateTimelocked: Removal not requested, or the delay hasn't passed
+    pub fn remove_update_timelock(ctx: Context<RemoveTimelock>) -> Result<()> {
+        let timelock = &ctx.accounts.timelock;
+        require!(
+            !(timelock.applies_to(&ctx.accounts.domain_account)
+                && timelock.is_in_force(Clock::get()?.unix_timestamp)),
+            DnsError::UpdateTimelocked
+        );
+
+        emit!(UpdateTimelockChanged {
+            domain: timelock.domain,
+            delay: None,
+            removed_at: None,
+            slot: Clock::get()?.slot,
+        });
+
+        msg!("⏳ NEURA DNS - Update Timelock Removed");
+        msg!("Domain: {}", ctx.accounts.domain_account.domain_name);
+        Ok(())
+    }
+
+    /// Schedule a record change on a timelocked domain
+    /// 
+    /// # Arguments
+    /// * `record`, `record_type`, `ttl` - As for `update_record`
+    /// 
+    /// # Process
+    /// 1. Checks the signer, governance, lock and the new record
+    /// 2. Stores the change to take effect once the timelock's delay has
+    ///    passed, replacing (and restarting) any change already pending
+    /// 3. Emits `ScheduledUpdateChanged` so monitors can alert the owner
+    /// 
+    /// # Notes
+    /// - Only the authority schedules; operators and per-type keys can't
+    ///   change a timelocked domain
+    /// 
+    /// # Errors
+    /// - Unauthorized: Signer is not the domain authority
+    /// - NotTimelocked: Domain has no update timelock in force; use
+    ///   `update_record`
+    /// - GovernanceThreshold: Domain has governance and too few members signed
+    /// - DomainLocked: Domain is locked (see `lock_domain`)
+    /// - InvalidIp, InvalidHostname, ..., InvalidTtl: As for `update_record`
+    pub fn schedule_update(
+        ctx: Context<ScheduleUpdate>,
+        record: String,
+        record_type: RecordType,
+        ttl: u32,
+    ) -> Result<()> {
+        let now = Clock::get()?.unix_timestamp;
+        let domain_account = &ctx.accounts.domain_account;
+        let delay = update_delay(domain_account, &ctx.accounts.timelock, now)?
+            .ok_or(DnsError::NotTimelocked)?;
+        require_governance(domain_account, &ctx.accounts.governance, ctx.remaining_accounts)?;
+        require_unlocked(domain_account, &ctx.accounts.lock)?;
+        validate_record(record_type, &record)?;
+        validate_ttl(ttl)?;
+
+        let pending = &mut ctx.accounts.pending_update;
+        pending.domain = domain_account.key();
+        pending.record = canonical_record(record_type, record);
+        pending.record_type = record_type;
+        pending.ttl = ttl;
+        pending.scheduled_by = domain_account.authority;
+        pending.scheduled_at = now;
+        pending.executes_at = now + delay;
+        pending.bump = ctx.bumps.pending_update;
+
+        emit!(ScheduledUpdateChanged {
+            domain: pending.domain,
+            record: pending.record.clone(),
+            record_type,
+            ttl,
+            executes_at: Some(pending.executes_at),
+            slot: Clock::get()?.slot,
+        });
+
+        msg!("⏳ NEURA DNS - Update Scheduled");
+        msg!("Domain: {}", domain_account.domain_name);
+        msg!("Record: {} {} (TTL {})", record_type.mnemonic(), pending.record, ttl);
+        msg!("Takes effect at: {}", pending.executes_at);
+        Ok(())
+    }
+
+    /// Cancel a scheduled record change, refunding its rent
+    /// 
+    /// # Errors
+    /// - Unauthorized: Signer is not the domain authority
+    pub fn cancel_scheduled_update(ctx: Context<CancelScheduledUpdate>) -> Result<()> {
+        let pending = &ctx.accounts.pending_update;
+        emit!(ScheduledUpdateChanged {
+            domain: pending.domain,
+            record: pending.record.clone(),
+            record_type: pending.record_type,
+            ttl: pending.ttl,
+            executes_at: None,
+            slot: Clock::get()?.slot,
+        });
+
+        msg!("⏳ NEURA DNS - Scheduled Update Cancelled");
+        msg!("Domain: {}", ctx.accounts.domain_account.domain_name);
+        Ok(())
+    }
+
+    /// Apply a scheduled record change whose delay has passed
+    /// 
+    /// # Notes
+    /// - Permissionless, so the change lands even if the owner is offline;
+    ///   `payer` covers any rent increase and the pending update's rent
+    ///   returns to the authority
+    /// - Changes scheduled by a previous owner or registration never apply
+    /// 
+    /// # Errors
+    /// - UpdateNotReady: The delay hasn't passed yet
+    /// - Unauthorized: Change was scheduled by a previous owner
+    /// - DomainLocked: Domain was locked in the meantime
+    /// - DomainLeased: Domain was leased in the meantime
+    /// - CnameConflict: Switching to CNAME while record sets exist
+    /// - ProgramPaused: The admin has paused the program
+    pub fn execute_update(ctx: Context<ExecuteUpdate>) -> Result<()> {
+        ctx.accounts.config.require_not_paused()?;
+        let now = Clock::get()?.unix_timestamp;
+        let pending = &ctx.accounts.pending_update;
+        let domain_account = &mut ctx.accounts.domain_account;
+        require!(now >= pending.executes_at, DnsError::UpdateNotReady);
+        require!(
+            pending.scheduled_by == domain_account.authority
+                && pending.scheduled_at >= domain_account.created_at,
+            DnsError::Unauthorized
+        );
+        require_unlocked(domain_account, &ctx.accounts.lock)?;
+        require!(
+            active_lease(domain_account, &ctx.accounts.lease, now)?.is_none(),
+            DnsError::DomainLeased
+        );
+
+        write_record(
+            domain_account,
+            &mut ctx.accounts.record_history,
+            ctx.bumps.record_history,
+            pending.scheduled_by,
+            pending.record.clone(),
+            pending.record_type,
+            pending.ttl,
+        )
+    }
+
+    /// Lease a domain's records to a tenant for a fixed term
+    /// 
+    /// # Arguments
+    /// * `tenant` - Wallet that may update the record during the lease
+    /// * `duration` - Lease length in seconds (1-365 days)
+    /// * `rent` - Lamports the tenant pays the owner up front
+    /// 
+    /// # Process
+    /// 1. Checks no lease is running
+    /// 2. Transfers the rent from the tenant (who co-signs) to the owner
+    /// 3. Stores the lease; until it expires only the tenant can
+    ///    `update_record`, and control reverts without a transaction
+    /// 
+    /// # Notes
+    /// - The lease survives a transfer of the domain; it ends early only if
+    ///   the tenant surrenders it or the name is re-registered
+    /// - The domain cannot be unregistered while leased
+    /// 
+    /// # Errors
+    /// - Unauthorized: Signer is not the domain authority
+    /// - GovernanceThreshold: Domain has governance and too few members signed
+    /// - DomainLocked: Domain is locked
+    /// - DomainLeased: A lease is already running
+    /// - InvalidLeaseDuration: Duration outside 1-365 days
+    /// - FeatureDisabled: The admin has turned off leasing
+    /// - ProgramPaused: The admin has paused the program
+    pub fn lease_domain(
+        ctx: Context<LeaseDomain>,
+        tenant: Pubkey,
+        duration: i64,
+        rent: u64,
+    ) -> Result<()> {
+        ctx.accounts.config.require_not_paused()?;
+        ctx.accounts.config.require_feature(Feature::Leasing)?;
+        require_governance(
+            &ctx.accounts.domain_account,
+            &ctx.accounts.governance,
+            ctx.remaining_accounts,
+        )?;
+        require_unlocked(&ctx.accounts.domain_account, &ctx.accounts.lock)?;
+        let now = Clock::get()?.unix_timestamp;
+        require!(
+            !ctx.accounts.lease.is_active(&ctx.accounts.domain_account, now),
+            DnsError::DomainLeased
+        );
+        require!(
+            (MIN_LEASE_DURATION..=MAX_LEASE_DURATION).contains(&duration),
+            DnsError::InvalidLeaseDuration
+        );
+        require_keys_eq!(ctx.accounts.tenant.key(), tenant, DnsError::Unauthorized);
+
+        if rent > 0 {
+            system_program::transfer(
+                CpiContext::new(
+                    ctx.accounts.system_program.to_account_info(),
+                    system_program::Transfer {
+                        from: ctx.accounts.tenant.to_account_info(),
+                        to: ctx.accounts.authority.to_account_info(),
+                    },
+                ),
+                rent,
+            )?;
+        }
+
+        let lease = &mut ctx.accounts.lease;
+        lease.domain = ctx.accounts.domain_account.key();
+        lease.lessor = ctx.accounts.authority.key();
+        lease.tenant = tenant;
+        lease.rent = rent;
+        lease.starts_at = now;
+        lease.expires_at = now + duration;
+        lease.bump = ctx.bumps.lease;
+
+        emit!(LeaseChanged {
+            domain: lease.domain,
+            tenant,
+            expires_at: Some(lease.expires_at),
+            slot: Clock::get()?.slot,
+        });
+
+        msg!("🏠 NEURA DNS - Domain Leased");
+        msg!("Domain: {}", ctx.accounts.domain_account.domain_name);
+        msg!("Tenant: {} until {}", tenant, lease.expires_at);
+        Ok(())
+    }
+
+    /// Close a lease, refunding its rent to the lessor
+    /// 
+    /// # Notes
+    /// - The tenant may surrender a running lease; anyone may close one
+    ///   that has expired or been voided by a re-registration
+    /// 
+    /// # Errors
+    /// - DomainLeased: Lease is still running and signer is not the tenant
+    pub fn end_lease(ctx: Context<EndLease>) -> Result<()> {
+        let lease = &ctx.accounts.lease;
+        require!(
+            ctx.accounts.signer.key() == lease.tenant
+                || !lease.is_active(&ctx.accounts.domain_account, Clock::get()?.unix_timestamp),
+            DnsError::DomainLeased
+        );
+
+        emit!(LeaseChanged {
+            domain: lease.domain,
+            tenant: lease.tenant,
+            expires_at: None,
+            slot: Clock::get()?.slot,
+        });
+
+        msg!("🏠 NEURA DNS - Lease Ended");
+        msg!("Domain: {}", ctx.accounts.domain_account.domain_name);
+        Ok(())
+    }
+
+    /// Unregister a domain, closing its account and refunding the rent
+    /// 
+    /// # Process
+    /// 1. Checks the domain has no subdomains, record sets, DID, SSHFP,
+    ///    dynamic or pending verification records left
+    /// 2. Removes the domain from its namespace index page
+    /// 3. Counts the deletion in the protocol stats
+    /// 4. Closes the domain account, refunding rent to the authority
+    /// 
+    /// # Notes
+    /// - The name becomes available for registration again
+    /// - Wrapped domains are owned by escrow; unwrap first
+    /// - Timelocked domains can't be unregistered until the timelock is
+    ///   removed, which waits out its delay, so a re-registration can't
+    ///   skip the delay
+    /// - Legacy raw-name accounts must be migrated first
+    /// - The Bloom filter keeps reporting "maybe taken" until rebuilt
+    /// - Per-key records (OpenPGP keys, attestations) cannot be enumerated;
+    ///   ones older than a later registration are ignored as orphans
+    /// 
+    /// # Errors
+    /// - Unauthorized: Signer is not the domain authority
+    /// - GovernanceThreshold: Domain has governance and too few members signed
+    /// - DomainLocked: Domain is locked (see `lock_domain`)
+    /// - UpdateTimelocked: Domain has an update timelock in force; remove it first
+    /// - ChildRecordsExist: A fixed child record is still open
+    /// - DomainLeased: A lease is still running
+    /// - ProgramPaused: The admin has paused the program
+    pub fn unregister_domain(ctx: Context<UnregisterDomain>) -> Result<()> {
+        ctx.accounts.config.require_not_paused()?;
+        require_record_write(
+            &ctx.accounts.domain_account,
+            &ctx.accounts.governance,
+            &ctx.accounts.lock,
+            &ctx.accounts.timelock,
+            ctx.remaining_accounts,
+        )?;
+        require!(
+            active_lease(
+                &ctx.accounts.domain_account,
+                &ctx.accounts.lease,
+                Clock::get()?.unix_timestamp
+            )?
+            .is_none(),
+            DnsError::DomainLeased
+        );
+        remove_from_index(
+            &mut ctx.accounts.namespace_index,
+            &mut ctx.accounts.index_page,
+            &ctx.accounts.domain_account.key(),
+        );
+
+        ctx.accounts.protocol_stats.record_deletion(Clock::get()?.epoch);
+
+        msg!("🗑️ NEURA DNS - Domain Unregistered");
+        msg!("Domain: {}", ctx.accounts.domain_account.domain_name);
+        msg!("Rent refunded to: {}", ctx.accounts.authority.key());
+        Ok(())
+    }
+
+    /// Propose handing a domain over to a new owner
+    /// 
+    /// # Arguments
+    /// * `new_authority` - Wallet that must accept to become the owner
+    /// 
+    /// # Process
+    /// 1. Resizes the account for the pending owner if needed
+    /// 2. Stores `new_authority` as `pending_authority`
+    /// 
+    /// # Notes
+    /// - Nothing changes hands until `accept_transfer` is signed by the
+    ///   pending owner, so a mistyped key can't capture the domain
+    /// - A new proposal replaces any earlier one
+    /// - Wrapped domains are owned by escrow; transfer the NFT instead
+    /// 
+    /// # Errors
+    /// - Unauthorized: Signer is not the domain authority
+    /// - GovernanceThreshold: Domain has governance and too few members signed
+    /// - DomainLocked: Domain is locked (see `lock_domain`)
+    /// - ProgramPaused: The admin has paused the program
+    pub fn propose_transfer(ctx: Context<ProposeTransfer>, new_authority: Pubkey) -> Result<()> {
+        ctx.accounts.config.require_not_paused()?;
+        require_governance(
+            &ctx.accounts.domain_account,
+            &ctx.accounts.governance,
+            ctx.remaining_accounts,
+        )?;
+        require_unlocked(&ctx.accounts.domain_account, &ctx.accounts.lock)?;
+        let domain_account = &mut ctx.accounts.domain_account;
+        domain_account.migrate_layout(Clock::get()?.unix_timestamp);
+        domain_account.pending_authority = Some(new_authority);
+
+        msg!("🤝 NEURA DNS - Transfer Proposed");
+        msg!("Domain: {}", domain_account.domain_name);
+        msg!("Pending owner: {}", new_authority);
+        Ok(())
+    }
+
+    /// Accept a proposed transfer and become the domain owner
+    /// 
+    /// # Process
+    /// 1. Checks the signer is the pending owner
+    /// 2. Sets the authority, clears the pending owner and the external
+    ///    verification badge
+    /// 3. Emits `DomainTransferred` for ownership history
+    /// 
+    /// # Errors
+    /// - NotPendingAuthority: Signer is not the pending owner
+    /// - ProgramPaused: The admin has paused the program
+    pub fn accept_transfer(ctx: Context<AcceptTransfer>) -> Result<()> {
+        ctx.accounts.config.require_not_paused()?;
+        let domain_account = &mut ctx.accounts.domain_account;
+        let previous_authority = domain_account.authority;
+        let new_authority = ctx.accounts.new_authority.key();
+        domain_account.hand_over(new_authority, Clock::get()?.unix_timestamp);
+
+        emit!(DomainTransferred {
+            domain: domain_account.key(),
+            from: previous_authority,
+            to: new_authority,
+            timestamp: Clock::get()?.unix_timestamp,
+            slot: Clock::get()?.slot,
+        });
+
+        msg!("🤝 NEURA DNS - Domain Transferred");
+        msg!("Domain: {}", domain_account.domain_name);
+        msg!("From: {}", previous_authority);
+        msg!("To: {}", new_authority);
+        Ok(())
+    }
+
+    /// Withdraw a pending transfer proposal
+    /// 
+    /// # Errors
+    /// - Unauthorized: Signer is not the domain authority
+    pub fn cancel_transfer(ctx: Context<CancelTransfer>) -> Result<()> {
+        let domain_account = &mut ctx.accounts.domain_account;
+        domain_account.pending_authority = None;
+
+        msg!("🤝 NEURA DNS - Transfer Cancelled");
+        msg!("Domain: {}", domain_account.domain_name);
+        Ok(())
+    }
+
+    /// Name guardians who can recover the domain if its key is lost
+    /// 
+    /// # Arguments
+    /// * `guardians` - Guardian keys (up to 10, no duplicates)
+    /// * `threshold` - Guardians needed to start a recovery (1 to
+    ///   `guardians.len()`)
+    /// 
+    /// # Notes
+    /// - Replaces any previous guardian set
+    /// - Guardians can move ownership, so this needs governance approval
+    ///   and an unlocked domain, as a transfer would
+    /// - The set lapses when the domain changes owner (recovery included)
+    ///   or is registered anew
+    /// 
+    /// # Errors
+    /// - Unauthorized: Signer is not the domain authority
+    /// - InvalidGuardians: Bad threshold, duplicate or too many guardians
+    /// - GovernanceThreshold: Domain has governance and too few members signed
+    /// - DomainLocked: Domain is locked (see `lock_domain`)
+    pub fn set_guardians(
+        ctx: Context<SetGuardians>,
+        guardians: Vec<Pubkey>,
+        threshold: u8,
+    ) -> Result<()> {
+        require!(
+            !guardians.is_empty()
+                && guardians.len() <= MAX_GUARDIANS
+                && threshold >= 1
+                && threshold as usize <= guardians.len(),
+            DnsError::InvalidGuardians
+        );
+        for (i, guardian) in guardians.iter().enumerate() {
+            require!(!guardians[..i].contains(guardian), DnsError::InvalidGuardians);
+        }
+        let domain_account = &ctx.accounts.domain_account;
+        require_governance(domain_account, &ctx.accounts.governance, ctx.remaining_accounts)?;
+        require_unlocked(domain_account, &ctx.accounts.lock)?;
+
+        let guardian_set = &mut ctx.accounts.guardian_set;
+        guardian_set.domain = domain_account.key();
+        guardian_set.set_by = domain_account.authority;
+        guardian_set.guardians = guardians;
+        guardian_set.threshold = threshold;
+        guardian_set.set_at = Clock::get()?.unix_timestamp;
+        guardian_set.bump = ctx.bumps.guardian_set;
+
+        msg!("🛟 NEURA DNS - Guardians Set");
+        msg!("Domain: {}", domain_account.domain_name);
+        msg!("Threshold: {} of {}", threshold, guardian_set.guardians.len());
+        Ok(())
+    }
+
+    /// Drop the domain's guardians, refunding the rent to the authority
+    /// 
+    /// # Errors
+    /// - Unauthorized: Signer is not the domain authority
+    pub fn remove_guardians(ctx: Context<RemoveGuardians>) -> Result<()> {
+        msg!("🛟 NEURA DNS - Guardians Removed");
+        msg!("Domain: {}", ctx.accounts.domain_account.domain_name);
+        Ok(())
+    }
+
+    /// Start recovering a domain to a new key
+    /// 
+    /// # Arguments
+    /// * `new_authority` - Key that will own the domain
+    /// 
+    /// # Process
+    /// 1. Checks `threshold` guardians signed (as `remaining_accounts`)
+    /// 2. Opens a recovery request; after a 7-day challenge period anyone
+    ///    can `complete_recovery`
+    /// 3. Emits `RecoveryChanged` so the owner can `veto_recovery` in time
+    /// 
+    /// # Errors
+    /// - NoGuardians: Domain has no guardian set for its current owner
+    /// - GuardianThreshold: Too few guardians signed
+    /// - RecoveryPending: A recovery for the current owner is already open
+    /// - InvalidGuardians: `new_authority` is already the owner
+    pub fn start_recovery(ctx: Context<StartRecovery>, new_authority: Pubkey) -> Result<()> {
+        let now = Clock::get()?.unix_timestamp;
+        let domain_account = &ctx.accounts.domain_account;
+        let guardian_set = &ctx.accounts.guardian_set;
+        require!(guardian_set.applies_to(domain_account), DnsError::NoGuardians);
+        require!(
+            guardian_set.approvals(ctx.remaining_accounts) >= guardian_set.threshold,
+            DnsError::GuardianThreshold
+        );
+        require!(
+            new_authority != domain_account.authority,
+            DnsError::InvalidGuardians
+        );
+        let recovery = &mut ctx.accounts.recovery;
+        require!(!recovery.applies_to(domain_account), DnsError::RecoveryPending);
+
+        recovery.domain = domain_account.key();
+        recovery.owner = domain_account.authority;
+        recovery.new_authority = new_authority;
+        recovery.initiated_by = ctx.accounts.initiator.key();
+        recovery.started_at = now;
+        recovery.bump = ctx.bumps.recovery;
+
+        emit!(RecoveryChanged {
+            domain: recovery.domain,
+            new_authority,
+            completes_at: now + RECOVERY_PERIOD,
+            status: RecoveryStatus::Started,
+            slot: Clock::get()?.slot,
+        });
+
+        msg!("🛟 NEURA DNS - Recovery Started");
+        msg!("Domain: {}", domain_account.domain_name);
+        msg!("New authority: {}", new_authority);
+        msg!("Completes at: {}", now + RECOVERY_PERIOD);
+        Ok(())
+    }
+
+    /// Stop a recovery, as the current owner
+    /// 
+    /// # Notes
+    /// - Also clears requests left over from a previous owner
+    /// - Refunds the request's rent to whoever started it
+    /// 
+    /// # Errors
+    /// - Unauthorized: Signer is not the domain authority
+    pub fn veto_recovery(ctx: Context<VetoRecovery>) -> Result<()> {
+        let recovery = &ctx.accounts.recovery;
+        emit!(RecoveryChanged {
+            domain: recovery.domain,
+            new_authority: recovery.new_authority,
+            completes_at: recovery.started_at + RECOVERY_PERIOD,
+            status: RecoveryStatus::Vetoed,
+            slot: Clock::get()?.slot,
+        });
+
+        msg!("🛟 NEURA DNS - Recovery Vetoed");
+        msg!("Domain: {}", ctx.accounts.domain_account.domain_name);
+        Ok(())
+    }
+
+    /// Hand the domain to the recovered key once the challenge period
+    /// has passed without a veto
+    /// 
+    /// # Notes
+    /// - Permissionless; the request's rent returns to whoever started it
+    /// - Clears any pending transfer; locks, governance, operators and
+    ///   guardians of the old owner stop applying
+    /// 
+    /// # Errors
+    /// - RecoveryStale: The domain changed owner since the recovery began
+    /// - RecoveryNotReady: The 7-day challenge period hasn't passed
+    /// - ProgramPaused: The admin has paused the program
+    pub fn complete_recovery(ctx: Context<CompleteRecovery>) -> Result<()> {
+        ctx.accounts.config.require_not_paused()?;
+        let now = Clock::get()?.unix_timestamp;
+        let recovery = &ctx.accounts.recovery;
+        let domain_account = &mut ctx.accounts.domain_account;
+        require!(recovery.applies_to(domain_account), DnsError::RecoveryStale);
+        require!(
+            now >= recovery.started_at + RECOVERY_PERIOD,
+            DnsError::RecoveryNotReady
+        );
+
+        let previous_authority = domain_account.authority;
+        domain_account.hand_over(recovery.new_authority, now);
+
+        let slot = Clock::get()?.slot;
+        emit!(RecoveryChanged {
+            domain: domain_account.key(),
+            new_authority: recovery.new_authority,
+            completes_at: recovery.started_at + RECOVERY_PERIOD,
+            status: RecoveryStatus::Completed,
+            slot,
+        });
+        emit!(DomainTransferred {
+            domain: domain_account.key(),
+            from: previous_authority,
+            to: recovery.new_authority,
+            timestamp: now,
+            slot,
+        });
+
+        msg!("🛟 NEURA DNS - Domain Recovered");
+        msg!("Domain: {}", domain_account.domain_name);
+        msg!("From: {}", previous_authority);
+        msg!("To: {}", recovery.new_authority);
+        Ok(())
+    }
+
+    /// Name a beneficiary who can claim the domain if the owner goes quiet
+    /// 
+    /// # Arguments
+    /// * `beneficiary` - Key that may claim the domain
+    /// * `period` - Seconds without a `heartbeat` after which it may
+    ///   (30 days to 5 years)
+    /// 
+    /// # Notes
+    /// - Counts as a heartbeat; replaces any previous beneficiary
+    /// - Only `heartbeat` (or calling this again) resets the clock; other
+    ///   owner actions don't
+    /// - Needs governance approval and an unlocked domain, as a transfer
+    ///   would; lapses when the domain changes owner or is registered anew
+    /// 
+    /// # Errors
+    /// - Unauthorized: Signer is not the domain authority
+    /// - InvalidInheritance: Period out of range, or the beneficiary is the owner
+    /// - GovernanceThreshold: Domain has governance and too few members signed
+    /// - DomainLocked: Domain is locked (see `lock_domain`)
+    pub fn set_beneficiary(
+        ctx: Context<SetBeneficiary>,
+        beneficiary: Pubkey,
+        period: i64,
+    ) -> Result<()> {
+        let domain_account = &ctx.accounts.domain_account;
+        require!(
+            (MIN_HEARTBEAT_PERIOD..=MAX_HEARTBEAT_PERIOD).contains(&period)
+                && beneficiary != domain_account.authority,
+            DnsError::InvalidInheritance
+        );
+        require_governance(domain_account, &ctx.accounts.governance, ctx.remaining_accounts)?;
+        require_unlocked(domain_account, &ctx.accounts.lock)?;
+
+        let now = Clock::get()?.unix_timestamp;
+        let inheritance = &mut ctx.accounts.inheritance;
+        inheritance.domain = domain_account.key();
+        inheritance.owner = domain_account.authority;
+        inheritance.beneficiary = beneficiary;
+        inheritance.period = period;
+        inheritance.set_at = now;
+        inheritance.last_heartbeat = now;
+        inheritance.bump = ctx.bumps.inheritance;
+
+        emit!(InheritanceChanged {
+            domain: inheritance.domain,
+            beneficiary: Some(beneficiary),
+            claimable_at: Some(now + period),
+            slot: Clock::get()?.slot,
+        });
+
+        msg!("🕯️ NEURA DNS - Beneficiary Set");
+        msg!("Domain: {}", domain_account.domain_name);
+        msg!("Beneficiary: {}", beneficiary);
+        msg!("Claimable at: {}", now + period);
+        Ok(())
+    }
+
+    /// Prove the owner is still around, pushing the claim date back
+    /// 
+    /// # Errors
+    /// - Unauthorized: Signer is not the domain authority
+    pub fn heartbeat(ctx: Context<Heartbeat>) -> Result<()> {
+        let now = Clock::get()?.unix_timestamp;
+        let inheritance = &mut ctx.accounts.inheritance;
+        inheritance.last_heartbeat = now;
+
+        emit!(InheritanceChanged {
+            domain: inheritance.domain,
+            beneficiary: Some(inheritance.beneficiary),
+            claimable_at: Some(now + inheritance.period),
+            slot: Clock::get()?.slot,
+        });
+
+        msg!("🕯️ NEURA DNS - Heartbeat");
+        msg!("Domain: {}", ctx.accounts.domain_account.domain_name);
+        msg!("Claimable at: {}", now + inheritance.period);
+        Ok(())
+    }
+
+    /// Drop the domain's beneficiary, refunding the rent to the authority
+    /// 
+    /// # Errors
+    /// - Unauthorized: Signer is not the domain authority
+    pub fn remove_beneficiary(ctx: Context<RemoveBeneficiary>) -> Result<()> {
+        emit!(InheritanceChanged {
+            domain: ctx.accounts.inheritance.domain,
+            beneficiary: None,
+            claimable_at: None,
+            slot: Clock::get()?.slot,
+        });
+
+        msg!("🕯️ NEURA DNS - Beneficiary Removed");
+        msg!("Domain: {}", ctx.accounts.domain_account.domain_name);
+        Ok(())
+    }
+
+    /// Take over a domain whose owner missed the heartbeat period
+    /// 
+    /// # Notes
+    /// - Works during the grace and redemption periods, so the
+    ///   beneficiary can still renew a lapsing name
+    /// - The inheritance account closes to the beneficiary
+    /// 
+    /// # Errors
+    /// - Unauthorized: Signer is not the beneficiary, or the designation
+    ///   was made by a previous owner or registration
+    /// - HeartbeatNotMissed: The owner's last heartbeat is too recent
+    /// - DomainExpired: Redemption period is over
+    /// - ProgramPaused: The admin has paused the program
+    pub fn claim_inheritance(ctx: Context<ClaimInheritance>) -> Result<()> {
+        ctx.accounts.config.require_not_paused()?;
+        let now = Clock::get()?.unix_timestamp;
+        let inheritance = &ctx.accounts.inheritance;
+        let domain_account = &mut ctx.accounts.domain_account;
+        require!(inheritance.applies_to(domain_account), DnsError::Unauthorized);
+        require!(
+            now >= inheritance.last_heartbeat + inheritance.period,
+            DnsError::HeartbeatNotMissed
+        );
+        require!(
+            domain_account.phase(now) != DomainPhase::Expired,
+            DnsError::DomainExpired
+        );
+
+        let previous_authority = domain_account.authority;
+        domain_account.hand_over(inheritance.beneficiary, now);
+
+        emit!(DomainTransferred {
+            domain: domain_account.key(),
+            from: previous_authority,
+            to: inheritance.beneficiary,
+            timestamp: now,
+            slot: Clock::get()?.slot,
+        });
+
+        msg!("🕯️ NEURA DNS - Inheritance Claimed");
+        msg!("Domain: {}", domain_account.domain_name);
+        msg!("From: {}", previous_authority);
+        msg!("To: {}", inheritance.beneficiary);
+        Ok(())
+    }
+
+    /// Add a value to one of the domain's record sets
+    /// 
+    /// # Arguments
+    /// * `record_type` - Which set to add to (one set per type)
+    /// * `value` - Record value, validated for its type
+    /// 
+    /// # Process
+    /// 1. Validates (and for AAAA canonicalizes) the value
+    /// 2. Creates the set PDA on first use and counts it on the domain
+    /// 3. Appends the value (max 8 per set)
+    /// 4. Grows the set to fit, the authority paying the extra rent
+    /// 
+    /// # Notes
+    /// - Record sets hold values in addition to the primary `record`
+    /// - CNAME can't share a name with other records, so it is only
+    ///   allowed as the primary record of a domain without record sets
+    /// 
+    /// # Errors
+    /// - Unauthorized: Signer is not the domain authority
+    /// - GovernanceThreshold: Domain has governance and too few members signed
+    /// - DomainLocked: Domain is locked (see `lock_domain`)
+    /// - UpdateTimelocked: Domain has an update timelock in force
+    /// - CnameConflict: Adding a CNAME set, or any set next to a CNAME
+    /// - DuplicateRecord: Value is already in the set
+    /// - TooManyRecords: Set already holds 8 values
+    /// - ProgramPaused: The admin has paused the program
+    pub fn add_record(ctx: Context<AddRecord>, record_type: RecordType, value: String) -> Result<()> {
+        ctx.accounts.config.require_not_paused()?;
+        require_record_write(
+            &ctx.accounts.domain_account,
+            &ctx.accounts.governance,
+            &ctx.accounts.lock,
+            &ctx.accounts.timelock,
+            ctx.remaining_accounts,
+        )?;
+        let domain_account = &mut ctx.accounts.domain_account;
+        require!(
+            record_type != RecordType::Cname && domain_account.record_type != RecordType::Cname,
+            DnsError::CnameConflict
+        );
+        validate_record(record_type, &value)?;
+        let value = canonical_record(record_type, value);
+
+        let record_set = &mut ctx.accounts.record_set;
+        if record_set.domain == Pubkey::default() {
+            record_set.domain = domain_account.key();
+            record_set.record_type = record_type;
+            record_set.bump = ctx.bumps.record_set;
+            domain_account.record_set_count = domain_account.record_set_count.saturating_add(1);
+        }
+        require!(!record_set.values.contains(&value), DnsError::DuplicateRecord);
+        require!(
+            record_set.values.len() < MAX_RECORDS_PER_SET,
+            DnsError::TooManyRecords
+        );
+        record_set.values.push(value);
+        record_set.updated_at = Clock::get()?.unix_timestamp;
+        fit_account(
+            &record_set.to_account_info(),
+            RecordSet::space_for(&record_set.values),
+            &ctx.accounts.authority,
+            &ctx.accounts.system_program,
+        )?;
+
+        msg!("➕ NEURA DNS - Record Added");
+        msg!("Domain: {}", domain_account.domain_name);
+        msg!("{} set: {} value(s)", record_type.mnemonic(), record_set.values.len());
+        Ok(())
+    }
+
+    /// Remove a value from one of the domain's record sets
+    /// 
+    /// # Process
+    /// 1. Removes the matching value
+    /// 2. Shrinks the set, refunding the freed rent to the authority, or
+    ///    closes it once it is empty
+    /// 
+    /// # Errors
+    /// - Unauthorized: Signer is not the domain authority
+    /// - GovernanceThreshold: Domain has governance and too few members signed
+    /// - DomainLocked: Domain is locked (see `lock_domain`)
+    /// - UpdateTimelocked: Domain has an update timelock in force
+    /// - RecordNotFound: Value is not in the set
+    /// - ProgramPaused: The admin has paused the program
+    pub fn remove_record(ctx: Context<ModifyRecordSet>, record_type: RecordType, value: String) -> Result<()> {
+        ctx.accounts.config.require_not_paused()?;
+        require_record_write(
+            &ctx.accounts.domain_account,
+            &ctx.accounts.governance,
+            &ctx.accounts.lock,
+            &ctx.accounts.timelock,
+            ctx.remaining_accounts,
+        )?;
+        let value = canonical_record(record_type, value);
+        let record_set = &mut ctx.accounts.record_set;
+        let before = record_set.values.len();
+        record_set.values.retain(|v| v != &value);
+        require!(record_set.values.len() < before, DnsError::RecordNotFound);
+        record_set.updated_at = Clock::get()?.unix_timestamp;
+
+        msg!("➖ NEURA DNS - Record Removed");
+        msg!("Domain: {}", ctx.accounts.domain_account.domain_name);
+        msg!("{} set: {} value(s)", record_type.mnemonic(), record_set.values.len());
+
+        if record_set.values.is_empty() {
+            let domain_account = &mut ctx.accounts.domain_account;
+            domain_account.record_set_count = domain_account.record_set_count.saturating_sub(1);
+            ctx.accounts
+                .record_set
+                .close(ctx.accounts.authority.to_account_info())?;
+        } else {
+            fit_account(
+                &record_set.to_account_info(),
+                RecordSet::space_for(&record_set.values),
+                &ctx.accounts.authority,
+                &ctx.accounts.system_program,
+            )?;
+        }
+        Ok(())
+    }
+
+    /// Replace all values of one of the domain's record sets
+    /// 
+    /// # Arguments
+    /// * `record_type` - Which set to replace
+    /// * `values` - New values (1-8, no duplicates)
+    /// 
+    /// # Notes
+    /// - The set is resized to fit; the authority pays for growth and
+    ///   gets freed rent back
+    /// 
+    /// # Errors
+    /// - Unauthorized: Signer is not the domain authority
+    /// - GovernanceThreshold: Domain has governance and too few members signed
+    /// - DomainLocked: Domain is locked (see `lock_domain`)
+    /// - UpdateTimelocked: Domain has an update timelock in force
+    /// - TooManyRecords: No values (use `remove_record`) or more than 8
+    /// - DuplicateRecord: A value is listed twice
+    /// - ProgramPaused: The admin has paused the program
+    pub fn replace_records(
+        ctx: Context<ModifyRecordSet>,
+        record_type: RecordType,
+        values: Vec<String>,
+    ) -> Result<()> {
+        ctx.accounts.config.require_not_paused()?;
+        require_record_write(
+            &ctx.accounts.domain_account,
+            &ctx.accounts.governance,
+            &ctx.accounts.lock,
+            &ctx.accounts.timelock,
+            ctx.remaining_accounts,
+        )?;
+        require!(
+            !values.is_empty() && values.len() <= MAX_RECORDS_PER_SET,
+            DnsError::TooManyRecords
+        );
+        let mut canonical = Vec::with_capacity(values.len());
+        for value in values {
+            validate_record(record_type, &value)?;
+            let value = canonical_record(record_type, value);
+            require!(!canonical.contains(&value), DnsError::DuplicateRecord);
+            canonical.push(value);
+        }
+
+        let record_set = &mut ctx.accounts.record_set;
+        record_set.values = canonical;
+        record_set.updated_at = Clock::get()?.unix_timestamp;
+        fit_account(
+            &record_set.to_account_info(),
+            RecordSet::space_for(&record_set.values),
+            &ctx.accounts.authority,
+            &ctx.accounts.system_program,
+        )?;
+
+        msg!("🔁 NEURA DNS - Record Set Replaced");
+        msg!("Domain: {}", ctx.accounts.domain_account.domain_name);
+        msg!("{} set: {} value(s)", record_type.mnemonic(), record_set.values.len());
+        Ok(())
+    }
+
+    /// Resolve one of the domain's record sets
+    /// 
+    /// # Returns
+    /// - The set's values, as instruction return data
+    /// 
+    /// # Notes
+    /// - Read-only operation, no state modification
+    /// - Zone-file lines are logged only in `verbose-logs` builds
+    /// - Return data is capped at 1 KiB, which a full set of long TXT
+    ///   values can exceed; read such sets from the PDA instead
+    /// 
+    /// # Errors
+    /// - DomainExpired: Registration lapsed (grace period or later)
+    pub fn resolve_records(ctx: Context<ResolveRecordSet>, record_type: RecordType) -> Result<Vec<String>> {
+        require!(
+            ctx.accounts.domain_account.phase(Clock::get()?.unix_timestamp) == DomainPhase::Active,
+            DnsError::DomainExpired
+        );
+        let domain_name = &ctx.accounts.domain_account.domain_name;
+        verbose_msg!("🔍 NEURA DNS - {} Lookup", record_type.mnemonic());
+        for value in ctx.accounts.record_set.values.iter() {
+            verbose_msg!("{}. IN {} {}", domain_name, record_type.mnemonic(), value);
+        }
+        Ok(ctx.accounts.record_set.values.clone())
+    }
+
+    /// Renew a domain for another year
+    /// 
+    /// # Process
+    /// 1. Resizes an older account for `expires_at` and migrates it
+    /// 2. Charges the renewal fee (lamports, or the fee mint when token
+    ///    accounts are passed, with the premium multiple for short names),
+    ///    plus the redemption fee in lamports if the grace period has passed
+    /// 3. Extends `expires_at` by a year from the old expiry
+    /// 4. Counts the renewal in the protocol stats
+    /// 
+    /// # Notes
+    /// - Phases after `expires_at`: 30-day grace period (renew at no
+    ///   extra cost), then 30-day redemption period (renew with a further
+    ///   0.1 SOL penalty), then anyone may `reclaim_expired`
+    /// - Only the authority can renew, so lapsed names stay with their
+    ///   owner until the redemption period ends
+    /// - Renewing from the old expiry means late renewals gain no time
+    /// - Wrapped domains are owned by escrow; unwrap to renew
+    /// 
+    /// # Errors
+    /// - Unauthorized: Signer is not the domain authority
+    /// - DomainExpired: Redemption period is over
+    /// - RenewalTooEarly: Domain would be paid up more than 10 years ahead
+    /// - InsufficientFee: Authority can't cover the fees
+    /// - WrongFeeMint: Token accounts passed for a mint that isn't configured,
+    ///   or the treasury token account isn't the treasury's
+    /// - WrongTldTreasury: The TLD takes a share and its treasury (or
+    ///   treasury token account) was not passed
+    pub fn renew_domain(ctx: Context<RenewDomain>) -> Result<()> {
+        let now = Clock::get()?.unix_timestamp;
+        let domain_account = &mut ctx.accounts.domain_account;
+        domain_account.migrate_layout(now);
+
+        let phase = domain_account.phase(now);
+        require!(phase != DomainPhase::Expired, DnsError::DomainExpired);
+        let expires_at = domain_account.expires_at + REGISTRATION_PERIOD;
+        require!(
+            expires_at - now <= MAX_REGISTRATION_AHEAD,
+            DnsError::RenewalTooEarly
+        );
+
+        let fee = match (
+            &ctx.accounts.token_program,
+            &ctx.accounts.fee_mint,
+            &ctx.accounts.payer_token_account,
+            &ctx.accounts.treasury_token_account,
+        ) {
+            (Some(token_program), Some(mint), Some(from), Some(to)) => {
+                let amount = ctx.accounts.config.premium_price(
+                    &domain_account.domain_name,
+                    ctx.accounts.config.token_renewal_fee,
+                );
+                let tld_to = ctx.accounts.tld_treasury_token_account.as_ref();
+                let cut = tld_cut(&ctx.accounts.tld, tld_to.map(|to| to.owner), amount)?;
+                collect_token_fee(
+                    &ctx.accounts.config,
+                    token_program,
+                    mint,
+                    from,
+                    to,
+                    ctx.accounts.config.treasury,
+                    &ctx.accounts.authority,
+                    amount - cut,
+                )?;
+                if let Some(tld_to) = tld_to {
+                    collect_token_fee(
+                        &ctx.accounts.config,
+                        token_program,
+                        mint,
+                        from,
+                        tld_to,
+                        tld_to.owner,
+                        &ctx.accounts.authority,
+                        cut,
+                    )?;
+                }
+                0
+            }
+            _ => ctx
+                .accounts
+                .config
+                .premium_price(&domain_account.domain_name, ctx.accounts.config.renewal_fee),
+        };
+        let penalty = if phase == DomainPhase::Redemption {
+            REDEMPTION_FEE_LAMPORTS
+        } else {
+            0
+        };
+        let tld_treasury = ctx.accounts.tld_treasury.as_ref();
+        let cut = tld_cut(&ctx.accounts.tld, tld_treasury.map(|t| t.key()), fee)?;
+        collect_fee(
+            &ctx.accounts.system_program,
+            &ctx.accounts.authority,
+            &ctx.accounts.treasury,
+            fee - cut + penalty,
+        )?;
+        if let Some(tld_treasury) = tld_treasury {
+            collect_fee(
+                &ctx.accounts.system_program,
+                &ctx.accounts.authority,
+                tld_treasury,
+                cut,
+            )?;
+        }
+        domain_account.expires_at = expires_at;
+
+        let epoch = Clock::get()?.epoch;
+        ctx.accounts.protocol_stats.record_renewal(epoch);
+        ctx.accounts.protocol_stats.record_fee(epoch, fee + penalty);
+
+        emit!(DomainRenewed {
+            domain: domain_account.key(),
+            expires_at,
+            fee,
+            penalty,
+        });
+
+        msg!("🔄 NEURA DNS - Domain Renewed");
+        msg!("Domain: {}", domain_account.domain_name);
+        msg!("Expires at: {}", expires_at);
+        msg!("Fee: {} lamports", fee);
+        if penalty > 0 {
+            msg!("Redemption fee: {} lamports", penalty);
+        }
+        Ok(())
+    }
+
+    /// Reap a domain whose redemption period has ended
+    /// 
+    /// # Process
+    /// 1. Checks the domain is past grace and redemption
+    /// 2. Removes the domain from its namespace index page
+    /// 3. Counts the deletion in the protocol stats
+    /// 4. Starts the name's release: re-registering it costs
+    ///    `config.release_start_premium`, halving every day for 21 days
+    /// 5. Emits `DomainExpired`
+    /// 6. Closes the domain account, refunding rent to the lapsed owner,
+    ///    or to the reclaimer if the domain is wrapped
+    /// 
+    /// # Notes
+    /// - Permissionless: anyone may free the name for re-registration;
+    ///   the reclaimer pays rent for the release PDA the first time
+    /// - The decaying premium is a Dutch auction: bots gain nothing by
+    ///   racing to the first slot, since the price only goes down
+    /// - Like `unregister_domain`, subdomains, record sets and fixed child
+    ///   records must be closed first so they can't attach to the next
+    ///   owner; anyone may remove the subdomains of a reclaimable domain
+    /// - A wrapped domain's authority is its wrap escrow, which has no way
+    ///   to spend lamports, so its rent goes to the reclaimer instead
+    /// 
+    /// # Errors
+    /// - DomainNotExpired: Domain is active or still redeemable
+    /// - ChildRecordsExist: A record set or fixed child record is still open
+    pub fn reclaim_expired(ctx: Context<ReclaimExpired>) -> Result<()> {
+        let domain_account = &ctx.accounts.domain_account;
+        require!(
+            domain_account.phase(Clock::get()?.unix_timestamp) == DomainPhase::Expired,
+            DnsError::DomainNotExpired
+        );
+
+        remove_from_index(
+            &mut ctx.accounts.namespace_index,
+            &mut ctx.accounts.index_page,
+            &domain_account.key(),
+        );
+        ctx.accounts.protocol_stats.record_deletion(Clock::get()?.epoch);
+
+        let release = &mut ctx.accounts.release;
+        release.released_at = Clock::get()?.unix_timestamp;
+        release.start_premium = ctx.accounts.config.release_start_premium;
+        release.bump = ctx.bumps.release;
+
+        emit!(DomainExpired {
+            domain: domain_account.key(),
+            domain_name: domain_account.domain_name.clone(),
+            authority: domain_account.authority,
+            expires_at: domain_account.expires_at,
+            slot: Clock::get()?.slot,
+        });
+
+        let rent_recipient = if domain_account.wrapped_mint.is_some() {
+            ctx.accounts.reclaimer.to_account_info()
+        } else {
+            ctx.accounts.authority.to_account_info()
+        };
+
+        msg!("♻️ NEURA DNS - Expired Domain Reclaimed");
+        msg!("Domain: {}", domain_account.domain_name);
+        msg!("Rent refunded to: {}", rent_recipient.key());
+        ctx.accounts.domain_account.close(rent_recipient)
+    }
+
+    /// Open or close subdomain registration to everyone
+    /// 
+    /// # Arguments
+    /// * `open` - Whether anyone may `register_subdomain` under this domain
+    /// 
+    /// # Errors
+    /// - Unauthorized: Signer is not the domain authority
+    pub fn set_subdomain_policy(ctx: Context<SetSubdomainPolicy>, open: bool) -> Result<()> {
+        let domain_account = &mut ctx.accounts.domain_account;
+        domain_account.migrate_layout(Clock::get()?.unix_timestamp);
+        domain_account.open_subdomains = open;
+
+        msg!("🌿 NEURA DNS - Subdomain Policy Updated");
+        msg!("Domain: {}", domain_account.domain_name);
+        msg!("Open subdomains: {}", open);
+        Ok(())
+    }
+
+    /// Register a subdomain under a parent domain
+    /// 
+    /// # Arguments
+    /// * `label` - Leftmost label, e.g. "api" for "api.example.com"
+    /// * `record` - Record value, validated for its type
+    /// * `record_type` - How `record` is interpreted
+    /// * `ttl` - Seconds resolvers may cache the answer (30-86400)
+    /// * `owner` - Authority of the new subdomain
+    /// 
+    /// # Process
+    /// 1. Checks the parent is active and the signer may mint under it
+    /// 2. Validates the label, full name, record and TTL
+    /// 3. Creates the subdomain PDA and counts it on the parent
+    /// 4. Emits `SubdomainRegistered`
+    /// 
+    /// # Notes
+    /// - The parent's authority must sign unless the parent has opened
+    ///   subdomain registration with `set_subdomain_policy`
+    /// - Subdomains live under the parent's PDA, not in the top-level
+    ///   namespace; resolve them with `resolve_subdomain`
+    /// 
+    /// # Errors
+    /// - SubdomainsClosed: Signer is not the parent authority and
+    ///   registration is not open
+    /// - DomainExpired: Parent registration has lapsed
+    /// - InvalidLabel: Label is not a valid DNS label
+    /// - DomainTooLong: Full name exceeds 253 characters
+    /// - Type-specific record errors, InvalidTtl
+    /// - FeatureDisabled: The admin has turned off subdomains
+    /// - ProgramPaused: The admin has paused the program
+    pub fn register_subdomain(
+        ctx: Context<RegisterSubdomain>,
+        label: String,
+        record: String,
+        record_type: RecordType,
+        ttl: u32,
+        owner: Pubkey,
+    ) -> Result<()> {
+        ctx.accounts.config.require_not_paused()?;
+        ctx.accounts.config.require_feature(Feature::Subdomains)?;
+        let now = Clock::get()?.unix_timestamp;
+        let parent = &mut ctx.accounts.parent_account;
+        require!(
+            parent.open_subdomains || ctx.accounts.payer.key() == parent.authority,
+            DnsError::SubdomainsClosed
+        );
+        require!(parent.phase(now) == DomainPhase::Active, DnsError::DomainExpired);
+        validate_label(&label)?;
+        require!(
+            label.len() + 1 + parent.domain_name.len() <= MAX_DOMAIN_LEN,
+            DnsError::DomainTooLong
+        );
+        validate_record(record_type, &record)?;
+        validate_ttl(ttl)?;
+
+        let subdomain = &mut ctx.accounts.subdomain;
+        subdomain.parent = parent.key();
+        subdomain.label = label;
+        subdomain.record = canonical_record(record_type, record);
+        subdomain.record_type = record_type;
+        subdomain.ttl = ttl;
+        subdomain.authority = owner;
+        subdomain.created_at = now;
+        subdomain.updated_at = now;
+        subdomain.bump = ctx.bumps.subdomain;
+        parent.subdomain_count = parent.subdomain_count.saturating_add(1);
+
+        emit!(SubdomainRegistered {
+            parent: parent.key(),
+            subdomain: subdomain.key(),
+            label: subdomain.label.clone(),
+            authority: owner,
+        });
+
+        msg!("🌿 NEURA DNS - Subdomain Registered");
+        msg!("Name: {}.{}", subdomain.label, parent.domain_name);
+        msg!("Owner: {}", owner);
+        Ok(())
+    }
+
+    /// Register several subdomains of one parent in a single transaction
+    /// 
+    /// # Arguments
+    /// * `entries` - Subdomains to create (label, record, type, TTL, owner)
+    /// * `all_or_nothing` - Fail the whole batch on the first bad entry;
+    ///   when false, bad entries are logged and skipped
+    /// 
+    /// # Process
+    /// 1. Checks the parent is active and the signer may mint under it
+    /// 2. For each entry, validates it like `register_subdomain` and checks
+    ///    the matching remaining account is its unused subdomain PDA
+    /// 3. Creates and writes each subdomain PDA, emitting
+    ///    `SubdomainRegistered`, and counts them on the parent
+    /// 
+    /// # Notes
+    /// - `remaining_accounts` holds one writable subdomain PDA per entry,
+    ///   in the same order: ["subdomain", parent, sha256(label)]
+    /// - At most 16 entries; the 1232-byte transaction limit usually binds
+    ///   first, so pass the PDAs through an address lookup table
+    /// - Skipping only covers entry checks; a payer that runs out of
+    ///   lamports still fails the transaction
+    /// 
+    /// # Errors
+    /// - InvalidBatch: Empty, over 16 entries, or wrong account count
+    /// - SubdomainsClosed, DomainExpired: As for `register_subdomain`
+    /// - Per entry (fatal only with `all_or_nothing`): BatchAccountMismatch,
+    ///   SubdomainTaken, InvalidLabel, DomainTooLong, record errors, InvalidTtl
+    /// - FeatureDisabled: The admin has turned off subdomains
+    /// - ProgramPaused: The admin has paused the program
+    pub fn register_batch<'info>(
+        ctx: Context<'_, '_, 'info, 'info, RegisterBatch<'info>>,
+        entries: Vec<BatchEntry>,
+        all_or_nothing: bool,
+    ) -> Result<()> {
+        ctx.accounts.config.require_not_paused()?;
+        ctx.accounts.config.require_feature(Feature::Subdomains)?;
+        require!(
+            !entries.is_empty()
+                && entries.len() <= MAX_BATCH_ENTRIES
+                && entries.len() == ctx.remaining_accounts.len(),
+            DnsError::InvalidBatch
+        );
+        let now = Clock::get()?.unix_timestamp;
+        let parent = &mut ctx.accounts.parent_account;
+        require!(
+            parent.open_subdomains || ctx.accounts.payer.key() == parent.authority,
+            DnsError::SubdomainsClosed
+        );
+        require!(parent.phase(now) == DomainPhase::Active, DnsError::DomainExpired);
+
+        let parent_key = parent.key();
+        let mut registered: u32 = 0;
+        for (entry, target) in entries.iter().zip(ctx.remaining_accounts.iter()) {
+            match create_batch_subdomain(
+                parent_key,
+                &parent.domain_name,
+                target,
+                &ctx.accounts.payer,
+                &ctx.accounts.system_program,
+                entry,
+                now,
+            ) {
+                Ok(()) => registered += 1,
+                Err(err) if !all_or_nothing => msg!("⚠️ Skipped {}: {}", entry.label, err),
+                Err(err) => return Err(err),
+            }
+        }
+        parent.subdomain_count = parent.subdomain_count.saturating_add(registered);
+
+        msg!("🌿 NEURA DNS - Subdomain Batch Registered");
+        msg!("Parent: {}", parent.domain_name);
+        msg!("Registered: {} of {}", registered, entries.len());
+        Ok(())
+    }
+
+    /// Resolve a subdomain
+    /// 
+    /// # Arguments
+    /// * `_label` - Leftmost label (used for PDA derivation)
+    /// 
+    /// # Returns
+    /// - The record type, value and TTL, as instruction return data
+    /// 
+    /// # Errors
+    /// - DomainExpired: Parent registration has lapsed
+    pub fn resolve_subdomain(ctx: Context<ResolveSubdomain>, _label: String) -> Result<ResolvedRecord> {
+        let parent = &ctx.accounts.parent_account;
+        require!(
+            parent.phase(Clock::get()?.unix_timestamp) == DomainPhase::Active,
+            DnsError::DomainExpired
+        );
+        let subdomain = &ctx.accounts.subdomain;
+        verbose_msg!("🔍 NEURA DNS - Subdomain Resolution Request");
+        verbose_msg!("Looking up: {}.{}", subdomain.label, parent.domain_name);
+        verbose_msg!("✅ Resolved to: {} {}", subdomain.record_type.mnemonic(), subdomain.record);
+        Ok(ResolvedRecord {
+            record_type: subdomain.record_type,
+            value: subdomain.record.clone(),
+            ttl: subdomain.ttl,
+        })
+    }
+
+    /// Remove a subdomain, refunding its rent to the subdomain owner
+    /// 
+    /// # Notes
+    /// - Either owner may remove it: the subdomain's or the parent's
+    /// - Once the parent can be reclaimed, anyone may remove its
+    ///   subdomains so they don't block `reclaim_expired`
+    /// 
+    /// # Errors
+    /// - Unauthorized: Signer is neither owner and the parent has not expired
+    pub fn remove_subdomain(ctx: Context<RemoveSubdomain>, _label: String) -> Result<()> {
+        let signer = ctx.accounts.signer.key();
+        let parent = &mut ctx.accounts.parent_account;
+        require!(
+            signer == ctx.accounts.subdomain.authority
+                || signer == parent.authority
+                || parent.phase(Clock::get()?.unix_timestamp) == DomainPhase::Expired,
+            DnsError::Unauthorized
+        );
+        parent.subdomain_count = parent.subdomain_count.saturating_sub(1);
+
+        msg!("🗑️ NEURA DNS - Subdomain Removed");
+        msg!("Name: {}.{}", ctx.accounts.subdomain.label, parent.domain_name);
+        Ok(())
+    }
+
+    /// Create one chunk of the reserved-name list
+    /// 
+    /// # Arguments
+    /// * `chunk` - Chunk number (0-15); a name lives in chunk
+    ///   `sha256(name)[0] % 16`
+    /// 
+    /// # Notes
+    /// - All 16 chunks must exist before registration works, since every
+    ///   registration reads its name's chunk
+    /// 
+    /// # Errors
+    /// - NotAdmin: Signer is not the config admin
+    /// - WrongReservedChunk: Chunk number out of range
+    pub fn initialize_reserved_names(ctx: Context<InitializeReservedNames>, chunk: u8) -> Result<()> {
+        require!((chunk as usize) < RESERVED_CHUNKS, DnsError::WrongReservedChunk);
+        let reserved_names = &mut ctx.accounts.reserved_names;
+        reserved_names.chunk = chunk;
+        reserved_names.bump = ctx.bumps.reserved_names;
+
+        msg!("🚫 NEURA DNS - Reserved Names Chunk {} Initialized", chunk);
+        Ok(())
+    }
+
+    /// Reserve or block names from registration
+    /// 
+    /// # Arguments
+    /// * `chunk` - Chunk the names belong to
+    /// * `name_hashes` - sha256 of each lowercase name
+    /// 
+    /// # Notes
+    /// - Hashes keep blocked terms (slurs) out of plain view on-chain
+    /// - Names already registered are unaffected
+    /// - Already-listed hashes are skipped
+    /// 
+    /// # Errors
+    /// - NotAdmin: Signer is not the config admin
+    /// - WrongReservedChunk: A hash belongs to a different chunk
+    /// - ReservedListFull: Chunk already holds 256 names
+    pub fn reserve_names(
+        ctx: Context<ManageReservedNames>,
+        chunk: u8,
+        name_hashes: Vec<[u8; 32]>,
+    ) -> Result<()> {
+        let reserved_names = &mut ctx.accounts.reserved_names;
+        for name_hash in name_hashes {
+            require!(
+                reserved_chunk_of(&name_hash) == chunk,
+                DnsError::WrongReservedChunk
+            );
+            if !reserved_names.contains(&name_hash) {
+                require!(
+                    reserved_names.name_hashes.len() < RESERVED_NAMES_PER_CHUNK,
+                    DnsError::ReservedListFull
+                );
+                reserved_names.name_hashes.push(name_hash);
+            }
+        }
+
+        msg!("🚫 NEURA DNS - Names Reserved");
+        msg!("Chunk {}: {} name(s)", chunk, reserved_names.name_hashes.len());
+        Ok(())
+    }
+
+    /// Release reserved names so they can be registered
+    /// 
+    /// # Errors
+    /// - NotAdmin: Signer is not the config admin
+    pub fn release_names(
+        ctx: Context<ManageReservedNames>,
+        chunk: u8,
+        name_hashes: Vec<[u8; 32]>,
+    ) -> Result<()> {
+        let reserved_names = &mut ctx.accounts.reserved_names;
+        reserved_names
+            .name_hashes
+            .retain(|name_hash| !name_hashes.contains(name_hash));
+
+        msg!("✅ NEURA DNS - Names Released");
+        msg!("Chunk {}: {} name(s)", chunk, reserved_names.name_hashes.len());
+        Ok(())
+    }
+
+    /// Make a domain the wallet's primary name for reverse resolution
+    /// 
+    /// # Process
+    /// 1. Checks the signer owns the domain
+    /// 2. Creates or overwrites the reverse PDA for the signer's key
+    /// 
+    /// # Notes
+    /// - Wallets show the name from `resolve_primary`, which returns
+    ///   nothing once the wallet no longer owns the domain or it lapses,
+    ///   so a stale entry never claims a name for the wrong wallet
+    /// - Wrapped domains are owned by escrow; unwrap to set them as primary
+    /// 
+    /// # Errors
+    /// - Unauthorized: Signer is not the domain authority
+    pub fn set_primary_domain(ctx: Context<SetPrimaryDomain>) -> Result<()> {
+        let reverse = &mut ctx.accounts.reverse;
+        reverse.owner = ctx.accounts.authority.key();
+        reverse.domain = ctx.accounts.domain_account.key();
+        reverse.domain_name = ctx.accounts.domain_account.domain_name.clone();
+        reverse.set_at = Clock::get()?.unix_timestamp;
+        reverse.bump = ctx.bumps.reverse;
+
+        emit!(PrimaryDomainChanged {
+            owner: reverse.owner,
+            domain: Some(reverse.domain),
+            slot: Clock::get()?.slot,
+        });
+
+        msg!("🪪 NEURA DNS - Primary Domain Set");
+        msg!("Wallet: {}", reverse.owner);
+        msg!("Domain: {}", reverse.domain_name);
+        Ok(())
+    }
+
+    /// Remove the wallet's primary name, refunding the reverse PDA's rent
+    pub fn clear_primary(ctx: Context<ClearPrimary>) -> Result<()> {
+        emit!(PrimaryDomainChanged {
+            owner: ctx.accounts.owner.key(),
+            domain: None,
+            slot: Clock::get()?.slot,
+        });
+
+        msg!("🪪 NEURA DNS - Primary Domain Cleared");
+        msg!("Wallet: {}", ctx.accounts.owner.key());
+        Ok(())
+    }
+
+    /// Look up a wallet's primary domain name
+    /// 
+    /// # Returns
+    /// - The name, as instruction return data, if the wallet still owns
+    ///   the domain and it is active; otherwise `None`
+    pub fn resolve_primary(ctx: Context<ResolvePrimary>) -> Result<Option<String>> {
+        let reverse = &ctx.accounts.reverse;
+        let domain = &ctx.accounts.domain_account;
+        if domain.data_is_empty() || domain.owner != &crate::ID {
+            return Ok(None);
+        }
+        let domain = Account::<DomainRecord>::try_from(domain.as_ref())?;
+        let current = domain.authority == reverse.owner
+            && reverse.set_at >= domain.created_at
+            && domain.phase(Clock::get()?.unix_timestamp) == DomainPhase::Active;
+        Ok(current.then(|| reverse.domain_name.clone()))
+    }
+
+    /// List a domain in the reverse (PTR) index of the IPv4 address its
+    /// A record points at
+    /// 
+    /// # Process
+    /// 1. Checks the domain's primary record is an A record
+    /// 2. Creates the IP's reverse PDA if needed and adds the domain
+    /// 
+    /// # Notes
+    /// - Send it right after `register_request` or `update_record` in the
+    ///   same transaction to register the PTR entry together with the record
+    /// - Any number of domains may point at one IP, but the index lists at
+    ///   most 16, first come first served; entries whose domain has since
+    ///   moved away or been unregistered can be pruned by anyone with
+    ///   `remove_ip_pointer`, freeing their slot
+    /// - Claiming again is a no-op
+    /// 
+    /// # Errors
+    /// - Unauthorized: Signer is not the domain authority
+    /// - NotARecord: Domain's record is not an A record
+    /// - IpPointersFull: 16 domains already listed for the IP
+    pub fn claim_ip_pointer(ctx: Context<ClaimIpPointer>) -> Result<()> {
+        let domain = ctx.accounts.domain_account.key();
+        let pointers = &mut ctx.accounts.ip_pointers;
+        pointers.ip = parse_ipv4(&ctx.accounts.domain_account.record).ok_or(DnsError::NotARecord)?;
+        pointers.bump = ctx.bumps.ip_pointers;
+        if !pointers.domains.contains(&domain) {
+            require!(
+                pointers.domains.len() < MAX_IP_POINTERS,
+                DnsError::IpPointersFull
+            );
+            pointers.domains.push(domain);
+        }
+
+        msg!("↩️ NEURA DNS - PTR Claimed");
+        msg!("Domain: {}", ctx.accounts.domain_account.domain_name);
+        msg!("IP: {}", ctx.accounts.domain_account.record);
+        Ok(())
+    }
+
+    /// Take a domain out of an IP's reverse index
+    /// 
+    /// # Arguments
+    /// * `ip` - IPv4 address whose index lists the domain
+    /// 
+    /// # Notes
+    /// - The domain's authority can always remove it; anyone can once
+    ///   the domain no longer points at `ip` or has been unregistered
+    /// - Closing the PDA when it empties is left out, so its rent stays
+    ///   with the address for the next claimant
+    /// 
+    /// # Errors
+    /// - Unauthorized: Domain still points at `ip` and signer is not its authority
+    pub fn remove_ip_pointer(ctx: Context<RemoveIpPointer>, ip: String) -> Result<()> {
+        let pointers = &mut ctx.accounts.ip_pointers;
+        if points_at(&ctx.accounts.domain_account, pointers.ip)? {
+            let domain = Account::<DomainRecord>::try_from(ctx.accounts.domain_account.as_ref())?;
+            require_keys_eq!(
+                ctx.accounts.signer.key(),
+                domain.authority,
+                DnsError::Unauthorized
+            );
+        }
+        let domain = ctx.accounts.domain_account.key();
+        pointers.domains.retain(|listed| listed != &domain);
+
+        msg!("↩️ NEURA DNS - PTR Removed");
+        msg!("IP: {}", ip);
+        msg!("Domain: {}", domain);
+        Ok(())
+    }
+
+    /// Look up the domains listed for an IPv4 address
+    /// 
+    /// # Arguments
+    /// * `ip` - IPv4 address, e.g. "8.8.8.8"
+    /// 
+    /// # Returns
+    /// - Domain account addresses, oldest claim first, as instruction
+    ///   return data
+    /// 
+    /// # Notes
+    /// - Entries are not re-checked here; clients should confirm each
+    ///   domain's A record still matches before trusting it
+    pub fn resolve_ip(ctx: Context<ResolveIp>, _ip: String) -> Result<Vec<Pubkey>> {
+        verbose_msg!("↩️ NEURA DNS - PTR Lookup");
+        Ok(ctx.accounts.ip_pointers.domains.clone())
+    }
+
+    /// Resolve the wallet address payments to a domain should go to
+    /// 
+    /// # Returns
+    /// - The domain's SOL record as a `Pubkey`, as instruction return data
+    /// 
+    /// # Notes
+    /// - Uses the primary record if it is a SOL record, otherwise the
+    ///   first value of the domain's SOL record set (pass it as
+    ///   `record_set`), so a domain can keep an A record and a wallet
+    /// - Wallets should show the domain's owner alongside, since the
+    ///   owner can repoint the address at any time
+    /// 
+    /// # Errors
+    /// - DomainExpired: Registration lapsed (grace period or later)
+    /// - NoWalletRecord: Domain has no SOL record
+    pub fn resolve_wallet(ctx: Context<ResolveWallet>) -> Result<Pubkey> {
+        let domain_account = &ctx.accounts.domain_account;
+        require!(
+            domain_account.phase(Clock::get()?.unix_timestamp) == DomainPhase::Active,
+            DnsError::DomainExpired
+        );
+        let record = if domain_account.record_type == RecordType::Sol {
+            Some(&domain_account.record)
+        } else {
+            ctx.accounts
+                .record_set
+                .as_ref()
+                .and_then(|set| set.values.first())
+        };
+        let wallet = record
+            .and_then(|value| Pubkey::from_str(value).ok())
+            .ok_or(DnsError::NoWalletRecord)?;
+
+        verbose_msg!("💸 NEURA DNS - Wallet Lookup");
+        verbose_msg!("{} -> {}", domain_account.domain_name, wallet);
+        Ok(wallet)
+    }
+
+    /// Resolve the domain's mail exchangers
+    /// 
+    /// # Returns
+    /// - `(priority, exchange)` pairs sorted by priority, lowest first, as
+    ///   instruction return data
+    /// 
+    /// # Notes
+    /// - Collects the primary record if it is an MX record and the values
+    ///   of the domain's MX record set (pass it as `record_set`); publish
+    ///   several exchangers with `add_record(Mx, "<priority> <exchange>")`
+    /// - Exchanges are checked as domain names when written, so each can be
+    ///   looked up with `resolve_domain` in turn
+    /// 
+    /// # Errors
+    /// - DomainExpired: Registration lapsed (grace period or later)
+    /// - InvalidMxRecord: Domain has no MX record
+    pub fn resolve_mx(ctx: Context<ResolveMx>) -> Result<Vec<MxExchange>> {
+        let domain_account = &ctx.accounts.domain_account;
+        require!(
+            domain_account.phase(Clock::get()?.unix_timestamp) == DomainPhase::Active,
+            DnsError::DomainExpired
+        );
+        let primary = Some(&domain_account.record)
+            .filter(|_| domain_account.record_type == RecordType::Mx);
+        let mut exchanges = primary
+            .into_iter()
+            .chain(ctx.accounts.record_set.iter().flat_map(|set| set.values.iter()))
+            .map(|value| parse_mx(value))
+            .collect::<Result<Vec<_>>>()?;
+        require!(!exchanges.is_empty(), DnsError::InvalidMxRecord);
+        exchanges.sort_by_key(|mx| mx.priority);
+
+        verbose_msg!("📬 NEURA DNS - MX Lookup");
+        for mx in exchanges.iter() {
+            verbose_msg!(
+                "{}. {} IN MX {} {}",
+                domain_account.domain_name,
+                domain_account.ttl,
+                mx.priority,
+                mx.exchange
+            );
+        }
+        Ok(exchanges)
+    }
+
+    /// Resolve the nameservers a delegated domain refers resolvers to
+    /// 
+    /// # Returns
+    /// - NS host names, primary record first, as instruction return data
+    /// 
+    /// # Notes
+    /// - A domain is delegated when its primary record is an NS record;
+    ///   further nameservers go in its NS record set (pass it as
+    ///   `record_set`), e.g. `add_record(Ns, "ns2.example.com")`
+    /// - Resolvers should answer with a referral to these hosts rather
+    ///   than treat the record as the final answer
+    /// 
+    /// # Errors
+    /// - DomainExpired: Registration lapsed (grace period or later)
+    /// - NotDelegated: The primary record is not an NS record
+    pub fn resolve_delegation(ctx: Context<ResolveDelegation>) -> Result<Vec<String>> {
+        let domain_account = &ctx.accounts.domain_account;
+        require!(
+            domain_account.phase(Clock::get()?.unix_timestamp) == DomainPhase::Active,
+            DnsError::DomainExpired
+        );
+        require!(
+            domain_account.record_type == RecordType::Ns,
+            DnsError::NotDelegated
+        );
+        let mut nameservers = vec![domain_account.record.clone()];
+        for value in ctx.accounts.record_set.iter().flat_map(|set| set.values.iter()) {
+            if !nameservers.contains(value) {
+                nameservers.push(value.clone());
+            }
+        }
+
+        verbose_msg!("🧭 NEURA DNS - Delegation");
+        for ns in nameservers.iter() {
+            verbose_msg!("{}. {} IN NS {}", domain_account.domain_name, domain_account.ttl, ns);
+        }
+        Ok(nameservers)
+    }
+
+    /// Set the domain's address on another chain
+    /// 
+    /// # Arguments
+    /// * `coin_type` - SLIP-0044 coin type (0 BTC, 60 ETH, 501 SOL, ...)
+    /// * `address` - Address in the chain's usual text form
+    /// 
+    /// # Storage
+    /// - Uses PDA with seeds: ["address", domain_account, coin_type (u32 LE)]
+    /// 
+    /// # Errors
+    /// - Unauthorized: Signer is not the domain authority
+    /// - GovernanceThreshold: Domain has governance and too few members signed
+    /// - DomainLocked: Domain is locked (see `lock_domain`)
+    /// - UpdateTimelocked: Domain has an update timelock in force
+    /// - InvalidChainAddress: Address fails the coin's format check (see
+    ///   `validate_chain_address`)
+    /// - ProgramPaused: The admin has paused the program
+    pub fn set_address(ctx: Context<SetAddress>, coin_type: u32, address: String) -> Result<()> {
+        ctx.accounts.config.require_not_paused()?;
+        require_record_write(
+            &ctx.accounts.domain_account,
+            &ctx.accounts.governance,
+            &ctx.accounts.lock,
+            &ctx.accounts.timelock,
+            ctx.remaining_accounts,
+        )?;
+        validate_chain_address(coin_type, &address)?;
+
+        let record = &mut ctx.accounts.address_record;
+        record.domain = ctx.accounts.domain_account.key();
+        record.coin_type = coin_type;
+        record.address = address;
+        record.updated_at = Clock::get()?.unix_timestamp;
+        record.bump = ctx.bumps.address_record;
+
+        msg!("🪙 NEURA DNS - Address Set");
+        msg!("Domain: {}", ctx.accounts.domain_account.domain_name);
+        msg!("Coin {}: {}", coin_type, record.address);
+        Ok(())
+    }
+
+    /// Remove the domain's address for a coin type and refund its rent
+    /// 
+    /// # Errors
+    /// - Unauthorized: Signer is not the domain authority
+    /// - GovernanceThreshold: Domain has governance and too few members signed
+    /// - DomainLocked: Domain is locked (see `lock_domain`)
+    /// - UpdateTimelocked: Domain has an update timelock in force
+    /// - ProgramPaused: The admin has paused the program
+    pub fn remove_address(ctx: Context<RemoveAddress>, coin_type: u32) -> Result<()> {
+        ctx.accounts.config.require_not_paused()?;
+        require_record_write(
+            &ctx.accounts.domain_account,
+            &ctx.accounts.governance,
+            &ctx.accounts.lock,
+            &ctx.accounts.timelock,
+            ctx.remaining_accounts,
+        )?;
+        msg!("🪙 NEURA DNS - Address Removed");
+        msg!("Domain: {}", ctx.accounts.domain_account.domain_name);
+        msg!("Coin: {}", coin_type);
+        Ok(())
+    }
+
+    /// Look up the domain's address for a coin type
+    /// 
+    /// # Arguments
+    /// * `coin_type` - SLIP-0044 coin type
+    /// 
+    /// # Returns
+    /// - The address text, as instruction return data
+    /// 
+    /// # Errors
+    /// - DomainExpired: Registration lapsed (grace period or later)
+    /// - OrphanedRecord: Address was set under a previous registration
+    pub fn address_of(ctx: Context<AddressOf>, _coin_type: u32) -> Result<String> {
+        let domain_account = &ctx.accounts.domain_account;
+        require!(
+            domain_account.phase(Clock::get()?.unix_timestamp) == DomainPhase::Active,
+            DnsError::DomainExpired
+        );
+        let record = &ctx.accounts.address_record;
+        require!(
+            record.updated_at >= domain_account.created_at,
+            DnsError::OrphanedRecord
+        );
+
+        verbose_msg!("🪙 NEURA DNS - Address Lookup");
+        verbose_msg!("{} -> {}", domain_account.domain_name, record.address);
+        Ok(record.address.clone())
+    }
+
+    /// Set one of the domain's profile text records
+    /// 
+    /// # Arguments
+    /// * `key` - Whitelisted profile key (avatar, url, email, ...)
+    /// * `value` - Value, checked against the key's format and size limit
+    /// 
+    /// # Storage
+    /// - Uses PDA with seeds: ["text", domain_account, key as u8]
+    /// 
+    /// # Errors
+    /// - Unauthorized: Signer is not the domain authority
+    /// - GovernanceThreshold: Domain has governance and too few members signed
+    /// - DomainLocked: Domain is locked (see `lock_domain`)
+    /// - UpdateTimelocked: Domain has an update timelock in force
+    /// - InvalidTextValue: Value fails the key's check (see `validate_text`)
+    /// - ProgramPaused: The admin has paused the program
+    pub fn set_text(ctx: Context<SetText>, key: TextKey, value: String) -> Result<()> {
+        ctx.accounts.config.require_not_paused()?;
+        require_record_write(
+            &ctx.accounts.domain_account,
+            &ctx.accounts.governance,
+            &ctx.accounts.lock,
+            &ctx.accounts.timelock,
+            ctx.remaining_accounts,
+        )?;
+        validate_text(key, &value)?;
+
+        let record = &mut ctx.accounts.text_record;
+        record.domain = ctx.accounts.domain_account.key();
+        record.key = key;
+        record.value = value;
+        record.updated_at = Clock::get()?.unix_timestamp;
+        record.bump = ctx.bumps.text_record;
+
+        msg!("👤 NEURA DNS - Profile Text Set");
+        msg!("Domain: {}", ctx.accounts.domain_account.domain_name);
+        msg!("{}: {}", key.name(), record.value);
+        Ok(())
+    }
+
+    /// Remove one of the domain's profile text records and refund its rent
+    /// 
+    /// # Errors
+    /// - Unauthorized: Signer is not the domain authority
+    /// - GovernanceThreshold: Domain has governance and too few members signed
+    /// - DomainLocked: Domain is locked (see `lock_domain`)
+    /// - UpdateTimelocked: Domain has an update timelock in force
+    /// - ProgramPaused: The admin has paused the program
+    pub fn remove_text(ctx: Context<RemoveText>, key: TextKey) -> Result<()> {
+        ctx.accounts.config.require_not_paused()?;
+        require_record_write(
+            &ctx.accounts.domain_account,
+            &ctx.accounts.governance,
+            &ctx.accounts.lock,
+            &ctx.accounts.timelock,
+            ctx.remaining_accounts,
+        )?;
+        msg!("👤 NEURA DNS - Profile Text Removed");
+        msg!("Domain: {}", ctx.accounts.domain_account.domain_name);
+        msg!("Key: {}", key.name());
+        Ok(())
+    }
+
+    /// Look up one of the domain's profile text records
+    /// 
+    /// # Returns
+    /// - The value, as instruction return data
+    /// 
+    /// # Notes
+    /// - To render a full profile, fetch every `["text", domain, key]` PDA
+    ///   in one `getMultipleAccounts` call instead
+    /// 
+    /// # Errors
+    /// - DomainExpired: Registration lapsed (grace period or later)
+    /// - OrphanedRecord: Value was set under a previous registration
+    pub fn text_of(ctx: Context<TextOf>, _key: TextKey) -> Result<String> {
+        let domain_account = &ctx.accounts.domain_account;
+        require!(
+            domain_account.phase(Clock::get()?.unix_timestamp) == DomainPhase::Active,
+            DnsError::DomainExpired
+        );
+        let record = &ctx.accounts.text_record;
+        require!(
+            record.updated_at >= domain_account.created_at,
+            DnsError::OrphanedRecord
+        );
+        Ok(record.value.clone())
+    }
+
+    /// Publish SRV records for a service at this domain
+    /// Mirrors DNS SRV records (RFC 2782), e.g. `_rpc._tcp.mynode.neura`
+    /// 
+    /// # Arguments
+    /// * `service` - Service label, e.g. "_rpc"
+    /// * `protocol` - "_tcp", "_udp" or "_tls"
+    /// * `targets` - 1-8 (priority, weight, port, target) entries
+    /// * `ttl` - Seconds resolvers may cache the answer (30-86400)
+    /// 
+    /// # Process
+    /// 1. Validates the owner labels, every target host and the TTL
+    /// 2. Stores the targets sorted by priority, replacing any earlier set
+    /// 
+    /// # Storage
+    /// - Uses PDA with seeds: ["srv", domain_account, service, protocol]
+    /// 
+    /// # Errors
+    /// - Unauthorized: Signer is not the domain authority
+    /// - GovernanceThreshold: Domain has governance and too few members signed
+    /// - DomainLocked: Domain is locked (see `lock_domain`)
+    /// - UpdateTimelocked: Domain has an update timelock in force
+    /// - InvalidSrvRecord: Bad service/protocol label, no or too many
+    ///   targets, port 0, or "." alongside other targets
+    /// - InvalidHostname: A target is not a valid host name
+    /// - InvalidTtl: TTL outside 30-86400 seconds
+    /// - ProgramPaused: The admin has paused the program
+    pub fn set_srv(
+        ctx: Context<SetSrv>,
+        service: String,
+        protocol: String,
+        mut targets: Vec<SrvTarget>,
+        ttl: u32,
+    ) -> Result<()> {
+        ctx.accounts.config.require_not_paused()?;
+        require_record_write(
+            &ctx.accounts.domain_account,
+            &ctx.accounts.governance,
+            &ctx.accounts.lock,
+            &ctx.accounts.timelock,
+            ctx.remaining_accounts,
+        )?;
+        validate_srv_name(&service, &protocol)?;
+        validate_srv_targets(&targets)?;
+        validate_ttl(ttl)?;
+        targets.sort_by_key(|target| target.priority);
+
+        msg!("🛰️ NEURA DNS - SRV Update");
+        for target in targets.iter() {
+            msg!(
+                "{}.{}.{}. {} IN SRV {} {} {} {}",
+                service,
+                protocol,
+                ctx.accounts.domain_account.domain_name,
+                ttl,
+                target.priority,
+                target.weight,
+                target.port,
+                target.target
+            );
+        }
+
+        let srv_record = &mut ctx.accounts.srv_record;
+        srv_record.domain = ctx.accounts.domain_account.key();
+        srv_record.service = service;
+        srv_record.protocol = protocol;
+        srv_record.targets = targets;
+        srv_record.ttl = ttl;
+        srv_record.updated_at = Clock::get()?.unix_timestamp;
+        srv_record.bump = ctx.bumps.srv_record;
+        Ok(())
+    }
+
+    /// Remove a service's SRV records and refund the rent
+    /// 
+    /// # Errors
+    /// - Unauthorized: Signer is not the domain authority
+    /// - GovernanceThreshold: Domain has governance and too few members signed
+    /// - DomainLocked: Domain is locked (see `lock_domain`)
+    /// - UpdateTimelocked: Domain has an update timelock in force
+    /// - ProgramPaused: The admin has paused the program
+    pub fn remove_srv(ctx: Context<RemoveSrv>, service: String, protocol: String) -> Result<()> {
+        ctx.accounts.config.require_not_paused()?;
+        require_record_write(
+            &ctx.accounts.domain_account,
+            &ctx.accounts.governance,
+            &ctx.accounts.lock,
+            &ctx.accounts.timelock,
+            ctx.remaining_accounts,
+        )?;
+        msg!("🛰️ NEURA DNS - SRV Removed");
+        msg!(
+            "Owner name: {}.{}.{}",
+            service,
+            protocol,
+            ctx.accounts.domain_account.domain_name
+        );
+        Ok(())
+    }
+
+    /// Resolve a service's SRV records
+    /// 
+    /// # Returns
+    /// - Targets sorted by priority with the TTL, as instruction return data;
+    ///   clients pick among equal priorities by weight (RFC 2782)
+    /// 
+    /// # Notes
+    /// - Zone-file lines are logged only in `verbose-logs` builds
+    /// 
+    /// # Errors
+    /// - DomainExpired: Registration lapsed (grace period or later)
+    /// - OrphanedRecord: Records were set under a previous registration
+    pub fn resolve_srv(
+        ctx: Context<ResolveSrv>,
+        _service: String,
+        _protocol: String,
+    ) -> Result<ResolvedSrv> {
+        let domain_account = &ctx.accounts.domain_account;
+        require!(
+            domain_account.phase(Clock::get()?.unix_timestamp) == DomainPhase::Active,
+            DnsError::DomainExpired
+        );
+        let srv_record = &ctx.accounts.srv_record;
+        require!(
+            srv_record.updated_at >= domain_account.created_at,
+            DnsError::OrphanedRecord
+        );
+
+        verbose_msg!("🛰️ NEURA DNS - SRV Lookup");
+        for target in srv_record.targets.iter() {
+            verbose_msg!(
+                "{}.{}.{}. {} IN SRV {} {} {} {}",
+                srv_record.service,
+                srv_record.protocol,
+                domain_account.domain_name,
+                srv_record.ttl,
+                target.priority,
+                target.weight,
+                target.port,
+                target.target
+            );
+        }
+        Ok(ResolvedSrv {
+            targets: srv_record.targets.clone(),
+            ttl: srv_record.ttl,
+        })
+    }
+}
+
+/// Symbol used for wrapped domain NFTs
+const WRAPPED_DOMAIN_SYMBOL: &str = "NEURA";
+
+/// Additional metadata key holding the domain PDA address
+const WRAPPED_DOMAIN_FIELD: &str = "domain";
+
+/// Upper bound on the record-set descriptor URI
+const MAX_METADATA_URI_LEN: usize = 200;
+
+/// Extra mint bytes needed for a Token-2022 token metadata extension
+/// 
+/// # Layout
+/// - TLV header: 2 bytes (type) + 2 bytes (length)
+/// - update_authority + mint: 32 + 32 bytes
+/// - name, symbol, uri: 4 bytes (length) + string bytes each
+/// - additional_metadata: 4 bytes (vector length); fields added later
+///   take `metadata_field_space` each
+fn token_metadata_space(name_len: usize, symbol_len: usize, uri_len: usize) -> usize {
+    4 + 32 + 32 + (4 + name_len) + (4 + symbol_len) + (4 + uri_len) + 4
+}
+
+/// Extra mint bytes for one additional metadata key/value pair
+fn metadata_field_space(key: &str, value: &str) -> usize {
+    (4 + key.len()) + (4 + value.len())
+}
+
+/// PDA seed for a domain name
+/// 
+/// Solana caps each seed at 32 bytes, so the raw name cannot be used for
+/// names longer than that. The SHA-256 digest always fits; the plaintext
+/// name is stored in the account for display.
+fn domain_seed(domain_name: &str) -> [u8; 32] {
+    hash(domain_name.as_bytes()).to_bytes()
+}
+
+/// Reserved-name chunk holding a name, from its PDA seed
+fn reserved_chunk(domain_name: &str) -> u8 {
+    reserved_chunk_of(&domain_seed(domain_name))
+}
+
+/// Reserved-name chunk for a name hash
+fn reserved_chunk_of(name_hash: &[u8; 32]) -> u8 {
+    name_hash[0] % RESERVED_CHUNKS as u8
+}
+
+/// Validator function - checks domain and record format
+/// 
+/// # Arguments
+/// * `domain` - Domain name string to validate
+/// * `record_type` - Type the record is validated as
+/// * `record` - Record value to validate (see `validate_record`)
+/// 
+/// # Domain Validation Rules
+/// - Not empty, max 253 octets
+/// - At least two labels, each valid per RFC 1035 (see `check_label`),
+///   so no leading, trailing or repeated dots
+/// - Lowercase only, so each name has exactly one PDA (clients should
+///   lowercase names before deriving seeds)
+/// 
+/// # Errors
+/// - DnsError::InvalidDomain: Domain is empty or has a single label
+/// - DnsError::DomainTooLong, EmptyLabel, LabelTooLong,
+///   InvalidLabelCharacter, LabelHyphen: The specific rule violated
+/// - DnsError::UppercaseDomain: Domain contains uppercase letters
+/// - Type-specific record errors from `validate_record`
+/// 
+/// # Performance
+/// - Runs on every registration, so all checks work on raw bytes in a
+///   single pass with no heap allocation
+fn validate_domain_and_record(domain: &str, record_type: RecordType, record: &str) -> Result<()> {
+    validate_domain(domain)?;
+    require!(
+        !domain.bytes().any(|b| b.is_ascii_uppercase()),
+        DnsError::UppercaseDomain
+    );
+    validate_record(record_type, record)
+}
+
+/// Type-specific record value check
+/// 
+/// # Rules
+/// - A: dotted-quad IPv4 (see `validate_ip`)
+/// - AAAA: IPv6 text form, including "::" and embedded IPv4
+///   (see `validate_ipv6`); stored in canonical form
+/// - CNAME, NS: target host name, same rules as a domain name
+/// - MX: "<preference> <exchange>", e.g. "10 mail.example.com"
+/// - TXT: 1-255 printable ASCII characters; SPF and DMARC policies
+///   are also checked for syntax
+/// - SOL: base58 Solana public key (32 bytes)
+/// - CONTENTHASH: IPFS/IPNS CID or Arweave transaction id URI (see
+///   `validate_content_hash`)
+/// 
+/// # Errors
+/// - InvalidIp, InvalidIpv6, InvalidHostname, InvalidMxRecord,
+///   InvalidTxtRecord, InvalidWalletAddress, InvalidContentHash
+fn validate_record(record_type: RecordType, record: &str) -> Result<()> {
+    match record_type {
+        RecordType::A => validate_ip(record),
+        RecordType::Aaaa => validate_ipv6(record),
+        RecordType::Cname | RecordType::Ns => validate_hostname(record),
+        RecordType::Mx => validate_mx(record),
+        RecordType::Txt => validate_txt(record),
+        RecordType::Sol => validate_wallet(record),
+        RecordType::ContentHash => validate_content_hash(record),
+    }
+}
+
+/// SLIP-0044 coin type of Bitcoin
+const COIN_TYPE_BTC: u32 = 0;
+
+/// SLIP-0044 coin type of Ethereum
+const COIN_TYPE_ETH: u32 = 60;
+
+/// SLIP-0044 coin type of Solana
+const COIN_TYPE_SOL: u32 = 501;
+
+/// Longest address stored for a coin type
+const MAX_CHAIN_ADDRESS_LEN: usize = 128;
+
+/// Per-coin address check for `set_address`
+/// 
+/// # Rules
+/// - BTC (0): bech32 / bech32m segwit address on mainnet (`bc1...`)
+/// - ETH (60): "0x" and 40 hex digits, EIP-55 checksum if mixed case
+/// - SOL (501): base58 public key
+/// - Other coin types: 1-128 printable ASCII characters, unchecked
+fn validate_chain_address(coin_type: u32, address: &str) -> Result<()> {
+    let valid = match coin_type {
+        COIN_TYPE_BTC => is_btc_address(address),
+        COIN_TYPE_ETH => is_eth_address(address),
+        COIN_TYPE_SOL => Pubkey::from_str(address).is_ok(),
+        _ => {
+            !address.is_empty()
+                && address.len() <= MAX_CHAIN_ADDRESS_LEN
+                && address.bytes().all(|b| b.is_ascii_graphic())
+        }
+    };
+    require!(valid, DnsError::InvalidChainAddress);
+    Ok(())
+}
+
+/// Bech32 data alphabet (BIP 173)
+const BECH32_CHARSET: &[u8; 32] = b"qpzry9x8gf2tvdw0s3jn54khce6mua7l";
+
+/// Checksum residue of bech32 (witness v0) addresses
+const BECH32_CONST: u32 = 1;
+
+/// Checksum residue of bech32m (witness v1+) addresses (BIP 350)
+const BECH32M_CONST: u32 = 0x2bc8_30a3;
+
+/// BIP 173 checksum over 5-bit values
+fn bech32_polymod(values: impl Iterator<Item = u8>) -> u32 {
+    const GENERATOR: [u32; 5] = [0x3b6a_57b2, 0x2650_8e6d, 0x1ea1_19fa, 0x3d42_33dd, 0x2a14_62b3];
+    let mut checksum = 1u32;
+    for value in values {
+        let top = checksum >> 25;
+        checksum = ((checksum & 0x01ff_ffff) << 5) ^ u32::from(value);
+        for (i, generator) in GENERATOR.iter().enumerate() {
+            if (top >> i) & 1 == 1 {
+                checksum ^= generator;
+            }
+        }
+    }
+    checksum
+}
+
+/// Mainnet segwit address check (BIP 173 / BIP 350)
+/// 
+/// Lowercase only, so each address has one stored form. Checks the
+/// checksum variant matches the witness version and the witness
+/// program decodes to a valid length.
+fn is_btc_address(address: &str) -> bool {
+    let bytes = address.as_bytes();
+    if !(14..=90).contains(&bytes.len()) || bytes.iter().any(|b| b.is_ascii_uppercase()) {
+        return false;
+    }
+    let split = match address.rfind('1') {
+        Some(split) => split,
+        None => return false,
+    };
+    let (hrp, data) = (&bytes[..split], &bytes[split + 1..]);
+    if hrp != b"bc" || data.len() < 7 {
+        return false;
+    }
+    let mut values = [0u8; 90];
+    for (value, c) in values.iter_mut().zip(data) {
+        match BECH32_CHARSET.iter().position(|x| x == c) {
+            Some(index) => *value = index as u8,
+            None => return false,
+        }
+    }
+    let values = &values[..data.len()];
+    let expanded = hrp
+        .iter()
+        .map(|c| c >> 5)
+        .chain([0])
+        .chain(hrp.iter().map(|c| c & 31));
+    let checksum = bech32_polymod(expanded.chain(values.iter().copied()));
+    let version = values[0];
+    let expected = if version == 0 { BECH32_CONST } else { BECH32M_CONST };
+    if version > 16 || checksum != expected {
+        return false;
+    }
+
+    // Witness program: 5-bit groups back to bytes, with at most 4 zero
+    // bits of padding
+    let program = &values[1..values.len() - 6];
+    let bits = program.len() * 5;
+    let padding = bits % 8;
+    if padding > 4 || program.last().map_or(false, |last| last & ((1 << padding) - 1) != 0) {
+        return false;
+    }
+    let len = bits / 8;
+    (2..=40).contains(&len) && (version != 0 || len == 20 || len == 32)
+}
+
+/// Ethereum address check, with the EIP-55 checksum for mixed case
+/// 
+/// All-lowercase and all-uppercase addresses carry no checksum and are
+/// accepted as they are.
+fn is_eth_address(address: &str) -> bool {
+    let hex = match address.strip_prefix("0x") {
+        Some(hex) => hex,
+        None => return false,
+    };
+    if hex.len() != 40 || !hex.bytes().all(|b| b.is_ascii_hexdigit()) {
+        return false;
+    }
+    let has_lower = hex.bytes().any(|b| b.is_ascii_lowercase());
+    let has_upper = hex.bytes().any(|b| b.is_ascii_uppercase());
+    if !(has_lower && has_upper) {
+        return true;
+    }
+    let digest = keccak::hash(hex.to_ascii_lowercase().as_bytes()).to_bytes();
+    hex.bytes().enumerate().all(|(i, b)| {
+        let nibble = (digest[i / 2] >> if i % 2 == 0 { 4 } else { 0 }) & 0x0f;
+        !b.is_ascii_alphabetic() || b.is_ascii_uppercase() == (nibble >= 8)
+    })
+}
+
+/// Content-hash check for decentralized hosting
+/// 
+/// # Rules
+/// - "ipfs://" or "ipns://" followed by a CID: CIDv0 ("Qm...", base58
+///   SHA-256 multihash) or CIDv1 in base32 ("b..."), whose version,
+///   codec and multihash must decode with a matching digest length
+/// - "ar://" followed by a 43-character base64url Arweave transaction id
+fn validate_content_hash(record: &str) -> Result<()> {
+    let valid = if let Some(cid) = record
+        .strip_prefix("ipfs://")
+        .or_else(|| record.strip_prefix("ipns://"))
+    {
+        is_cid(cid)
+    } else if let Some(tx_id) = record.strip_prefix("ar://") {
+        tx_id.len() == 43
+            && tx_id
+                .bytes()
+                .all(|b| b.is_ascii_alphanumeric() || b == b'-' || b == b'_')
+    } else {
+        false
+    };
+    require!(valid, DnsError::InvalidContentHash);
+    Ok(())
+}
+
+/// Base58 alphabet (Bitcoin ordering, as IPFS uses)
+const BASE58_ALPHABET: &[u8; 58] = b"123456789ABCDEFGHJKLMNPQRSTUVWXYZabcdefghijkmnopqrstuvwxyz";
+
+/// Largest binary CID accepted: version, codec and a 64-byte digest
+const MAX_CID_BYTES: usize = 80;
+
+/// Decode base58 text into `out`, returning the byte length
+fn decode_base58(text: &[u8], out: &mut [u8; MAX_CID_BYTES]) -> Option<usize> {
+    let mut len = 0;
+    for c in text {
+        let mut carry = BASE58_ALPHABET.iter().position(|x| x == c)? as u32;
+        for byte in out[..len].iter_mut().rev() {
+            carry += u32::from(*byte) * 58;
+            *byte = carry as u8;
+            carry >>= 8;
+        }
+        while carry > 0 {
+            if len == MAX_CID_BYTES {
+                return None;
+            }
+            out.copy_within(0..len, 1);
+            out[0] = carry as u8;
+            len += 1;
+            carry >>= 8;
+        }
+    }
+    // Each leading '1' stands for a zero byte
+    let zeros = text.iter().take_while(|&&c| c == b'1').count();
+    if len + zeros > MAX_CID_BYTES {
+        return None;
+    }
+    out.copy_within(0..len, zeros);
+    out[..zeros].fill(0);
+    Some(len + zeros)
+}
+
+/// Decode unpadded lowercase RFC 4648 base32 into `out`, returning the
+/// byte length; leftover bits must be zero padding
+fn decode_base32(text: &[u8], out: &mut [u8; MAX_CID_BYTES]) -> Option<usize> {
+    let (mut buffer, mut bits, mut len) = (0u32, 0u32, 0usize);
+    for &c in text {
+        let value = match c {
+            b'a'..=b'z' => c - b'a',
+            b'2'..=b'7' => c - b'2' + 26,
+            _ => return None,
+        };
+        buffer = (buffer << 5) | u32::from(value);
+        bits += 5;
+        if bits >= 8 {
+            bits -= 8;
+            if len == MAX_CID_BYTES {
+                return None;
+            }
+            out[len] = (buffer >> bits) as u8;
+            len += 1;
+        }
+    }
+    (bits < 5 && buffer & ((1 << bits) - 1) == 0).then_some(len)
+}
+
+/// Read one unsigned LEB128 varint (multiformats style) off the front
+fn read_varint(bytes: &mut &[u8]) -> Option<u64> {
+    let mut value = 0u64;
+    for i in 0..9 {
+        let (&byte, rest) = bytes.split_first()?;
+        *bytes = rest;
+        value |= u64::from(byte & 0x7f) << (7 * i);
+        if byte & 0x80 == 0 {
+            return Some(value);
+        }
+    }
+    None
+}
+
+/// Whether `bytes` is exactly one multihash: code, digest length, digest
+fn is_multihash(mut bytes: &[u8]) -> bool {
+    let digest_len = read_varint(&mut bytes).and_then(|_| read_varint(&mut bytes));
+    matches!(digest_len, Some(len) if (1..=64).contains(&len) && bytes.len() as u64 == len)
+}
+
+/// Whether `cid` is a CIDv0 or base32 CIDv1 in text form
+fn is_cid(cid: &str) -> bool {
+    let mut buf = [0u8; MAX_CID_BYTES];
+    let bytes = cid.as_bytes();
+    if bytes.len() == 46 && bytes.starts_with(b"Qm") {
+        // CIDv0 is a bare SHA-256 multihash: 0x12, 0x20, 32-byte digest
+        return decode_base58(bytes, &mut buf) == Some(34) && buf[0] == 0x12 && buf[1] == 0x20;
+    }
+    match bytes.split_first() {
+        Some((b'b', rest)) => match decode_base32(rest, &mut buf) {
+            Some(len) => {
+                let mut cid = &buf[..len];
+                read_varint(&mut cid) == Some(1)
+                    && read_varint(&mut cid).is_some()
+                    && is_multihash(cid)
+            }
+            None => false,
+        },
+        _ => false,
+    }
+}
+
+/// Longest value any profile key accepts
+const MAX_TEXT_VALUE_LEN: usize = 256;
+
+/// Per-key profile value check for `set_text`
+/// 
+/// # Rules
+/// - Avatar: "https://", "ipfs://" or "ar://" URI, up to 256 characters
+/// - Url: "https://" or "http://" URL, up to 256 characters
+/// - Email: "<local>@<domain>", up to 254 characters, domain per RFC 1035
+/// - Description: up to 256 bytes of UTF-8 without control characters
+/// - Twitter: 1-15 letters, digits or underscores
+/// - Github: 1-39 letters, digits or inner hyphens
+/// - Discord: 2-32 lowercase letters, digits, underscores or periods
+/// - Telegram: 5-32 letters, digits or underscores
+fn validate_text(key: TextKey, value: &str) -> Result<()> {
+    let printable_uri = |schemes: &[&str]| {
+        value.len() <= MAX_TEXT_VALUE_LEN
+            && value.bytes().all(|b| b.is_ascii_graphic())
+            && schemes
+                .iter()
+                .any(|scheme| value.len() > scheme.len() && value.starts_with(scheme))
+    };
+    let handle = |len: std::ops::RangeInclusive<usize>, allowed: fn(u8) -> bool| {
+        len.contains(&value.len()) && value.bytes().all(allowed)
+    };
+    let valid = match key {
+        TextKey::Avatar => printable_uri(&["https://", "ipfs://", "ar://"]),
+        TextKey::Url => printable_uri(&["https://", "http://"]),
+        TextKey::Email => {
+            value.len() <= 254
+                && value.bytes().all(|b| b.is_ascii_graphic())
+                && match value.split_once('@') {
+                    Some((local, domain)) => {
+                        validate_localpart(local).is_ok() && validate_domain(domain).is_ok()
+                    }
+                    None => false,
+                }
+        }
+        TextKey::Description => {
+            !value.is_empty()
+                && value.len() <= MAX_TEXT_VALUE_LEN
+                && !value.chars().any(char::is_control)
+        }
+        TextKey::Twitter => handle(1..=15, |b| b.is_ascii_alphanumeric() || b == b'_'),
+        TextKey::Github => {
+            handle(1..=39, |b| b.is_ascii_alphanumeric() || b == b'-')
+                && !value.starts_with('-')
+                && !value.ends_with('-')
+        }
+        TextKey::Discord => handle(2..=32, |b| {
+            b.is_ascii_lowercase() || b.is_ascii_digit() || b == b'_' || b == b'.'
+        }),
+        TextKey::Telegram => handle(5..=32, |b| b.is_ascii_alphanumeric() || b == b'_'),
+    };
+    require!(valid, DnsError::InvalidTextValue);
+    Ok(())
+}
+
+/// Wallet address check: base58 text of a 32-byte public key
+fn validate_wallet(record: &str) -> Result<()> {
+    require!(Pubkey::from_str(record).is_ok(), DnsError::InvalidWalletAddress);
+    Ok(())
+}
+
+/// TTL check: resolvers get at least 30 seconds and at most a day
+fn validate_ttl(ttl: u32) -> Result<()> {
+    require!((MIN_TTL..=MAX_TTL).contains(&ttl), DnsError::InvalidTtl);
+    Ok(())
+}
+
+/// Host name check for record targets (same rules as a domain name)
+fn validate_hostname(host: &str) -> Result<()> {
+    validate_domain(host).map_err(|_| error!(DnsError::InvalidHostname))
+}
+
+/// Most targets one SRV record set holds
+const MAX_SRV_TARGETS: usize = 8;
+
+/// SRV owner-name check: "_<service>" and "_<protocol>" labels
+/// 
+/// # Rules
+/// - Service: "_" and 1-15 letters, digits or hyphens, with at least one
+///   letter and no leading, trailing or doubled hyphen (RFC 6335)
+/// - Protocol: "_tcp", "_udp" or "_tls"
+/// - Lowercase only, so each service has one PDA
+fn validate_srv_name(service: &str, protocol: &str) -> Result<()> {
+    let name = service.strip_prefix('_').unwrap_or_default().as_bytes();
+    require!(
+        (1..=15).contains(&name.len())
+            && name
+                .iter()
+                .all(|b| b.is_ascii_lowercase() || b.is_ascii_digit() || *b == b'-')
+            && name.iter().any(|b| b.is_ascii_lowercase())
+            && name[0] != b'-'
+            && name[name.len() - 1] != b'-'
+            && !name.windows(2).any(|pair| pair == b"--"),
+        DnsError::InvalidSrvRecord
+    );
+    require!(
+        matches!(protocol, "_tcp" | "_udp" | "_tls"),
+        DnsError::InvalidSrvRecord
+    );
+    Ok(())
+}
+
+/// SRV target check: 1-8 targets, each a host name or "." (RFC 2782's
+/// "service not available here"), port 0 only with "."
+fn validate_srv_targets(targets: &[SrvTarget]) -> Result<()> {
+    require!(
+        (1..=MAX_SRV_TARGETS).contains(&targets.len()),
+        DnsError::InvalidSrvRecord
+    );
+    for target in targets {
+        if target.target == "." {
+            require!(targets.len() == 1, DnsError::InvalidSrvRecord);
+        } else {
+            validate_hostname(&target.target)?;
+            require!(target.port != 0, DnsError::InvalidSrvRecord);
+        }
+    }
+    Ok(())
+}
+
+/// MX value check: 16-bit preference, one space, exchange host name
+fn validate_mx(record: &str) -> Result<()> {
+    parse_mx(record).map(|_| ())
+}
+
+/// Split an MX value into its (priority, exchange) pair
+fn parse_mx(record: &str) -> Result<MxExchange> {
+    let (preference, exchange) = record.split_once(' ').ok_or(DnsError::InvalidMxRecord)?;
+    require!(
+        !preference.is_empty()
+            && preference.len() <= 5
+            && preference.bytes().all(|b| b.is_ascii_digit()),
+        DnsError::InvalidMxRecord
+    );
+    let priority = preference
+        .parse::<u16>()
+        .map_err(|_| error!(DnsError::InvalidMxRecord))?;
+    validate_domain(exchange).map_err(|_| error!(DnsError::InvalidMxRecord))?;
+    Ok(MxExchange {
+        priority,
+        exchange: exchange.to_string(),
+    })
+}
+
+/// Longest TXT value: one DNS character-string
+const MAX_TXT_LEN: usize = 255;
+
+/// TXT value check, with syntax checks for SPF and DMARC policies
+fn validate_txt(record: &str) -> Result<()> {
+    let bytes = record.as_bytes();
+    require!(
+        !bytes.is_empty() && bytes.len() <= MAX_TXT_LEN,
+        DnsError::InvalidTxtRecord
+    );
+    require!(
+        bytes.iter().all(|b| (0x20..0x7f).contains(b)),
+        DnsError::InvalidTxtRecord
+    );
+
+    if starts_with_ignore_case(record, "v=spf1") {
+        validate_spf(&record["v=spf1".len()..])
+    } else if starts_with_ignore_case(record, "v=DMARC1") {
+        validate_dmarc(record)
+    } else {
+        Ok(())
+    }
+}
+
+fn starts_with_ignore_case(s: &str, prefix: &str) -> bool {
+    s.len() >= prefix.len() && s.as_bytes()[..prefix.len()].eq_ignore_ascii_case(prefix.as_bytes())
+}
+
+/// SPF term syntax (RFC 7208 section 4.6)
+/// 
+/// # Rules
+/// - Terms follow "v=spf1", each preceded by a space
+/// - Mechanisms take an optional qualifier (+ - ~ ?)
+/// - all: no argument; include, exists, ip4, ip6: ":<arg>" required;
+///   a, mx, ptr: optional ":<domain>" and/or "/<cidr>"
+/// - Modifiers are "<name>=<value>" (e.g. redirect=, exp=)
+fn validate_spf(terms: &str) -> Result<()> {
+    require!(terms.is_empty() || terms.starts_with(' '), DnsError::InvalidTxtRecord);
+    for term in terms.split(' ').filter(|t| !t.is_empty()) {
+        let mechanism = term.trim_start_matches(['+', '-', '~', '?']);
+        let name_end = mechanism.find([':', '/', '=']).unwrap_or(mechanism.len());
+        let (name, rest) = mechanism.split_at(name_end);
+        require!(
+            !name.is_empty() && name.bytes().all(|b| b.is_ascii_alphanumeric() || b == b'-' || b == b'_' || b == b'.'),
+            DnsError::InvalidTxtRecord
+        );
+
+        if rest.starts_with('=') {
+            // Modifier: no qualifier, non-empty value
+            require!(mechanism.len() == term.len() && rest.len() > 1, DnsError::InvalidTxtRecord);
+            continue;
+        }
+
+        let is = |mechanism: &str| name.eq_ignore_ascii_case(mechanism);
+        let valid = if is("all") {
+            rest.is_empty()
+        } else if is("include") || is("exists") || is("ip4") || is("ip6") {
+            rest.len() > 1 && rest.starts_with(':')
+        } else if is("a") || is("mx") || is("ptr") {
+            rest.is_empty() || rest.len() > 1
+        } else {
+            false
+        };
+        require!(valid, DnsError::InvalidTxtRecord);
+    }
+    Ok(())
+}
+
+/// DMARC policy syntax (RFC 7489 section 6.4)
+/// 
+/// # Rules
+/// - Tags are "<name>=<value>" separated by semicolons
+/// - The first tag is v=DMARC1 and the second is p=none|quarantine|reject
+fn validate_dmarc(record: &str) -> Result<()> {
+    let mut tags = record.split(';').map(str::trim).filter(|t| !t.is_empty());
+    require!(
+        tags.next().is_some_and(|t| t.eq_ignore_ascii_case("v=DMARC1")),
+        DnsError::InvalidTxtRecord
+    );
+    let policy = tags.next().and_then(|t| t.strip_prefix("p=")).map(str::trim);
+    require!(
+        matches!(policy, Some("none") | Some("quarantine") | Some("reject")),
+        DnsError::InvalidTxtRecord
+    );
+    for tag in tags {
+        let (name, value) = tag.split_once('=').ok_or(DnsError::InvalidTxtRecord)?;
+        require!(
+            !name.trim().is_empty()
+                && name.trim().bytes().all(|b| b.is_ascii_alphanumeric())
+                && !value.trim().is_empty(),
+            DnsError::InvalidTxtRecord
+        );
+    }
+    Ok(())
+}
+
+/// IPv6 text form check (RFC 4291 section 2.2)
+/// 
+/// # Rules
+/// - Eight 16-bit groups of 1-4 hex digits separated by colons
+/// - One "::" may stand in for one or more zero groups
+/// - The last 32 bits may be written as a dotted-quad IPv4 address
+fn validate_ipv6(ip: &str) -> Result<()> {
+    require!(parse_ipv6(ip).is_some(), DnsError::InvalidIpv6);
+    Ok(())
+}
+
+/// Longest IPv6 text form: six groups plus an embedded IPv4 address
+const MAX_IPV6_TEXT_LEN: usize = 45;
+
+/// Parse IPv6 text into its eight groups
+/// 
+/// Single pass over the bytes; returns None for anything that is not a
+/// valid address (see `validate_ipv6` for the accepted forms).
+fn parse_ipv6(ip: &str) -> Option<[u16; 8]> {
+    let bytes = ip.as_bytes();
+    let len = bytes.len();
+    if !(2..=MAX_IPV6_TEXT_LEN).contains(&len) {
+        return None;
+    }
+
+    let mut groups = [0u16; 8];
+    let mut count = 0;
+    let mut gap: Option<usize> = None;
+    let mut i = 0;
+
+    if bytes.starts_with(b"::") {
+        gap = Some(0);
+        i = 2;
+        if i == len {
+            return Some(groups);
+        }
+    } else if bytes[0] == b':' {
+        return None;
+    }
+
+    loop {
+        let start = i;
+        let mut value: u32 = 0;
+        // Reading a fifth digit is enough to reject the group
+        while i < len && i - start < 5 && bytes[i].is_ascii_hexdigit() {
+            value = (value << 4) | (bytes[i] as char).to_digit(16)?;
+            i += 1;
+        }
+
+        if i < len && bytes[i] == b'.' {
+            // Embedded IPv4 takes the last two groups
+            if count > 6 {
+                return None;
+            }
+            let v4 = &ip[start..];
+            validate_ip(v4).ok()?;
+            let mut octets = v4.split('.').map(|o| o.parse::<u16>().unwrap_or(0));
+            for _ in 0..2 {
+                groups[count] = (octets.next()? << 8) | octets.next()?;
+                count += 1;
+            }
+            break;
+        }
+
+        let digits = i - start;
+        if digits == 0 || digits > 4 || count == 8 {
+            return None;
+        }
+        groups[count] = value as u16;
+        count += 1;
+
+        if i == len {
+            break;
+        }
+        if bytes[i] != b':' {
+            return None;
+        }
+        i += 1;
+        if i < len && bytes[i] == b':' {
+            if gap.is_some() {
+                return None;
+            }
+            gap = Some(count);
+            i += 1;
+            if i == len {
+                break;
+            }
+        } else if i == len {
+            return None;
+        }
+    }
+
+    match gap {
+        // "::" must stand in for at least one group
+        Some(_) if count >= 8 => None,
+        Some(at) => {
+            let tail = count - at;
+            for k in (0..tail).rev() {
+                groups[8 - tail + k] = groups[at + k];
+            }
+            for group in groups.iter_mut().take(8 - tail).skip(at) {
+                *group = 0;
+            }
+            Some(groups)
+        }
+        None if count == 8 => Some(groups),
+        None => None,
+    }
+}
+
+/// Recommended IPv6 text form (RFC 5952)
+/// 
+/// # Rules
+/// - Lowercase hex, no leading zeros in a group
+/// - The longest run of two or more zero groups becomes "::" (the
+///   first one on a tie); a single zero group is written as "0"
+/// - Embedded IPv4 is written in hex so the result is never longer
+///   than the input, which keeps the account size computed from it
+fn canonical_ipv6(groups: &[u16; 8]) -> String {
+    use std::fmt::Write;
+
+    let (mut best_start, mut best_len) = (8, 0);
+    let mut i = 0;
+    while i < 8 {
+        if groups[i] == 0 {
+            let start = i;
+            while i < 8 && groups[i] == 0 {
+                i += 1;
+            }
+            if i - start >= 2 && i - start > best_len {
+                best_start = start;
+                best_len = i - start;
+            }
+        } else {
+            i += 1;
+        }
+    }
+
+    let mut out = String::with_capacity(39);
+    let mut i = 0;
+    while i < 8 {
+        if i == best_start {
+            out.push_str("::");
+            i += best_len;
+            continue;
+        }
+        if !out.is_empty() && !out.ends_with(':') {
+            out.push(':');
+        }
+        let _ = write!(out, "{:x}", groups[i]);
+        i += 1;
+    }
+    out
+}
+
+/// Store-ready form of a validated record value
+/// 
+/// AAAA values are canonicalized so the same address is always stored
+/// (and compared) the same way; other types are stored as given.
+fn canonical_record(record_type: RecordType, record: String) -> String {
+    match record_type {
+        RecordType::Aaaa => parse_ipv6(&record)
+            .map(|groups| canonical_ipv6(&groups))
+            .unwrap_or(record),
+        _ => record,
+    }
+}
+
+/// Domain format check (see `validate_domain_and_record` for the rules)
+fn validate_domain(domain: &str) -> Result<()> {
+    let bytes = domain.as_bytes();
+    require!(!bytes.is_empty(), DnsError::InvalidDomain);
+    require!(bytes.len() <= MAX_DOMAIN_LEN, DnsError::DomainTooLong);
+
+    let mut labels = 0u8;
+    for label in bytes.split(|&b| b == b'.') {
+        check_label(label)?;
+        labels = labels.saturating_add(1);
+    }
+    require!(labels >= 2, DnsError::InvalidDomain);
+    Ok(())
+}
+
+/// RFC 1035 label check, with an error code per violation
+/// 
+/// # Rules
+/// - 1-63 octets (so no empty labels as in "a..b" or ".com")
+/// - ASCII letters, digits and hyphens only
+/// - Cannot start or end with a hyphen
+fn check_label(label: &[u8]) -> Result<()> {
+    require!(!label.is_empty(), DnsError::EmptyLabel);
+    require!(label.len() <= MAX_LABEL_LEN, DnsError::LabelTooLong);
+    require!(
+        label.iter().all(|b| b.is_ascii_alphanumeric() || *b == b'-'),
+        DnsError::InvalidLabelCharacter
+    );
+    require!(
+        label[0] != b'-' && label[label.len() - 1] != b'-',
+        DnsError::LabelHyphen
+    );
+    Ok(())
+}
+
+/// DNS label check for subdomains (`check_label` rules, lowercase only)
+/// 
+/// # Rules
+/// - 1-63 characters
+/// - Lowercase ASCII letters, digits and hyphens only (so no dots, and
+///   one PDA per label)
+/// - Cannot start or end with a hyphen
+fn validate_label(label: &str) -> Result<()> {
+    check_label(label.as_bytes()).map_err(|_| error!(DnsError::InvalidLabel))?;
+    require!(
+        !label.bytes().any(|b| b.is_ascii_uppercase()),
+        DnsError::InvalidLabel
+    );
+    Ok(())
+}
+
+/// IPv4 format check shared by static and dynamic records
+/// 
+/// # Rules
+/// - Not empty, max 15 characters
+/// - Exactly 4 octets, each 0-255
+/// - Octets are ASCII digits only (no signs or whitespace)
+/// 
+/// # Performance
+/// - Single byte-level pass, accumulating each octet's value in place
+///   instead of splitting into a `Vec` and parsing substrings
+fn validate_ip(ip: &str) -> Result<()> {
+    let bytes = ip.as_bytes();
+    require!(
+        !bytes.is_empty() && bytes.len() <= 15,
+        DnsError::InvalidIp
+    );
+
+    let mut dots = 0u8;
+    let mut octet: u16 = 0;
+    let mut digits = 0u8;
+    for &byte in bytes {
+        match byte {
+            b'0'..=b'9' => {
+                octet = octet * 10 + u16::from(byte - b'0');
+                digits += 1;
+                require!(octet <= 255, DnsError::InvalidIp);
+            }
+            b'.' => {
+                require!(digits > 0 && dots < 3, DnsError::InvalidIp);
+                dots += 1;
+                octet = 0;
+                digits = 0;
+            }
+            _ => return err!(DnsError::InvalidIp),
+        }
+    }
+    require!(dots == 3 && digits > 0, DnsError::InvalidIp);
+
+    Ok(())
+}
+
+/// Octets of a dotted-quad IPv4 address, for the reverse (PTR) PDA seed
+/// 
+/// Accepts exactly what `validate_ip` accepts, so "8.8.8.8" and
+/// "008.8.8.8" share one reverse entry.
+fn parse_ipv4(ip: &str) -> Option<[u8; 4]> {
+    validate_ip(ip).ok()?;
+    let mut octets = [0u8; 4];
+    for (octet, part) in octets.iter_mut().zip(ip.split('.')) {
+        *octet = part.parse().ok()?;
+    }
+    Some(octets)
+}
+
+/// Most domains one IPv4 address lists in its reverse PDA
+const MAX_IP_POINTERS: usize = 16;
+
+/// Whether `domain` is a live domain whose A record is `ip`, as a
+/// reverse entry requires
+fn points_at(domain: &UncheckedAccount, ip: [u8; 4]) -> Result<bool> {
+    if domain.data_is_empty() || domain.owner != &crate::ID {
+        return Ok(false);
+    }
+    let domain = Account::<DomainRecord>::try_from(domain.as_ref())?;
+    Ok(domain.record_type == RecordType::A && parse_ipv4(&domain.record) == Some(ip))
+}
+
+/// DID document limits
+const MAX_DID_KEYS: usize = 4;
+const MAX_DID_URI_LEN: usize = 200;
+
+/// DID method name used for domains
+const DID_METHOD: &str = "neura";
+
+/// Build the decentralized identifier for a domain
+/// 
+/// # Example
+/// - "example.com" -> "did:neura:example.com"
+fn did_for_domain(domain: &str) -> String {
+    format!("did:{}:{}", DID_METHOD, domain)
+}
+
+/// External verification challenge size and TXT label
+const CHALLENGE_LEN: usize = 16;
+const CHALLENGE_TXT_LABEL: &str = "_neura-challenge";
+
+/// Lowercase hex encoding for logging byte strings
+fn to_hex(bytes: &[u8]) -> String {
+    const DIGITS: &[u8; 16] = b"0123456789abcdef";
+    let mut out = String::with_capacity(bytes.len() * 2);
+    for byte in bytes {
+        out.push(DIGITS[(byte >> 4) as usize] as char);
+        out.push(DIGITS[(byte & 0x0f) as usize] as char);
+    }
+    out
+}
+
+/// OPENPGPKEY limits (RFC 7929 / RFC 5321)
+const OPENPGPKEY_HASH_LEN: usize = 28;
+const MAX_LOCALPART_LEN: usize = 64;
+const MAX_OPENPGP_KEY_LEN: usize = 900;
+
+/// OpenPGP packet tag of a public-key packet
+const OPENPGP_PUBLIC_KEY_TAG: u8 = 6;
+
+/// Hash an email local part the way OPENPGPKEY owner names do
+/// 
+/// SHA2-256 of the local part, truncated to 28 octets. Also used as the
+/// PDA seed, so it always fits the 32-byte seed limit.
+fn openpgpkey_hash(localpart: &str) -> [u8; OPENPGPKEY_HASH_LEN] {
+    let digest = hash(localpart.as_bytes()).to_bytes();
+    let mut out = [0u8; OPENPGPKEY_HASH_LEN];
+    out.copy_from_slice(&digest[..OPENPGPKEY_HASH_LEN]);
+    out
+}
+
+/// DNS owner name a resolver would query for this address
+/// 
+/// # Example
+/// - ("alice", "example.com") -> "<56 hex chars>._openpgpkey.example.com"
+fn openpgpkey_owner_name(localpart: &str, domain: &str) -> String {
+    format!("{}._openpgpkey.{}", to_hex(&openpgpkey_hash(localpart)), domain)
+}
+
+/// Validate an email local part used for key discovery
+fn validate_localpart(localpart: &str) -> Result<()> {
+    require!(
+        !localpart.is_empty() && localpart.len() <= MAX_LOCALPART_LEN && !localpart.contains('@'),
+        DnsError::InvalidLocalPart
+    );
+    Ok(())
+}
+
+/// Check that key data starts with an OpenPGP public-key packet
+/// 
+/// # Rules
+/// - Not empty, at most 900 bytes (fits a single transaction)
+/// - First byte is a packet header (high bit set)
+/// - Packet tag is 6 (public key), in either old or new header format
+fn validate_openpgp_key(key: &[u8]) -> Result<()> {
+    require!(
+        !key.is_empty() && key.len() <= MAX_OPENPGP_KEY_LEN,
+        DnsError::InvalidOpenPgpKey
+    );
+    let header = key[0];
+    require!(header & 0x80 != 0, DnsError::InvalidOpenPgpKey);
+    let tag = if header & 0x40 != 0 {
+        header & 0x3f
+    } else {
+        (header >> 2) & 0x0f
+    };
+    require!(tag == OPENPGP_PUBLIC_KEY_TAG, DnsError::InvalidOpenPgpKey);
+    Ok(())
+}
+
+/// Maximum SSHFP fingerprints stored per domain
+const MAX_SSHFP_RECORDS: usize = 8;
+
+/// Validate an SSHFP entry
+/// 
+/// # Rules
+/// - Algorithm: 1 (RSA), 2 (DSA), 3 (ECDSA), 4 (Ed25519), 6 (Ed448)
+/// - Fingerprint type: 1 (SHA-1, 20 bytes) or 2 (SHA-256, 32 bytes)
+/// - Fingerprint length must match the digest type
+fn validate_sshfp(algorithm: u8, fingerprint_type: u8, fingerprint: &[u8]) -> Result<()> {
+    require!(
+        matches!(algorithm, 1 | 2 | 3 | 4 | 6),
+        DnsError::InvalidSshfp
+    );
+    let expected_len = match fingerprint_type {
+        1 => 20,
+        2 => 32,
+        _ => return err!(DnsError::InvalidSshfp),
+    };
+    require!(fingerprint.len() == expected_len, DnsError::InvalidSshfp);
+    Ok(())
+}
+
+/// Maximum DS and DNSKEY records stored per domain (each)
+const MAX_DNSSEC_RECORDS: usize = 4;
+
+/// Longest DNSKEY public key: RSA with a 2048-bit modulus and a
+/// 4-byte exponent
+const MAX_DNSKEY_LEN: usize = 1 + 4 + 256;
+
+/// Strict hex decoding: even length, hex digits only (either case)
+fn decode_hex(hex: &str) -> Option<Vec<u8>> {
+    let bytes = hex.as_bytes();
+    if bytes.len() % 2 != 0 {
+        return None;
+    }
+    let nibble = |b: u8| (b as char).to_digit(16).map(|d| d as u8);
+    bytes
+        .chunks(2)
+        .map(|pair| Some((nibble(pair[0])? << 4) | nibble(pair[1])?))
+        .collect()
+}
+
+/// DNSSEC signing algorithms accepted for DS and DNSKEY records
+/// (RFC 8624 "MUST" / "RECOMMENDED" ones)
+fn is_dnssec_algorithm(algorithm: u8) -> bool {
+    matches!(algorithm, 8 | 10 | 13 | 14 | 15 | 16)
+}
+
+/// Validate a DS entry and decode its digest
+/// 
+/// # Rules
+/// - Algorithm: 8, 10, 13, 14, 15 or 16
+/// - Digest type: 2 (SHA-256, 32 bytes) or 4 (SHA-384, 48 bytes);
+///   SHA-1 (1) must no longer be published (RFC 8624)
+/// - Digest: hex of exactly the digest type's length
+fn validate_ds(algorithm: u8, digest_type: u8, digest: &str) -> Result<Vec<u8>> {
+    require!(is_dnssec_algorithm(algorithm), DnsError::InvalidDnssecRecord);
+    let expected_len = match digest_type {
+        2 => 32,
+        4 => 48,
+        _ => return err!(DnsError::InvalidDnssecRecord),
+    };
+    let digest = decode_hex(digest).ok_or(DnsError::InvalidDnssecRecord)?;
+    require!(digest.len() == expected_len, DnsError::InvalidDnssecRecord);
+    Ok(digest)
+}
+
+/// Validate a DNSKEY entry and decode its public key
+/// 
+/// # Rules
+/// - Flags: 256 (ZSK) or 257 (KSK, the one a registrar's DS points at)
+/// - Protocol: 3 (RFC 4034)
+/// - Key: hex; 64 bytes for P-256, 96 for P-384, 32 for Ed25519, 57 for
+///   Ed448; RSA keys (RFC 3110) with a 1-4 byte exponent and a
+///   1024-2048 bit modulus
+fn validate_dnskey(flags: u16, protocol: u8, algorithm: u8, public_key: &str) -> Result<Vec<u8>> {
+    require!(
+        matches!(flags, 256 | 257) && protocol == 3,
+        DnsError::InvalidDnssecRecord
+    );
+    let key = decode_hex(public_key).ok_or(DnsError::InvalidDnssecRecord)?;
+    let valid = match algorithm {
+        8 | 10 => match key.first() {
+            Some(&exponent_len) if (1..=4).contains(&exponent_len) => {
+                let modulus_len = key.len().saturating_sub(1 + exponent_len as usize);
+                (128..=256).contains(&modulus_len) && key[1] != 0
+            }
+            _ => false,
+        },
+        13 => key.len() == 64,
+        14 => key.len() == 96,
+        15 => key.len() == 32,
+        16 => key.len() == 57,
+        _ => false,
+    };
+    require!(valid, DnsError::InvalidDnssecRecord);
+    Ok(key)
+}
+
+/// DNSKEY key tag (RFC 4034 Appendix B) over the record's RDATA
+fn dnskey_tag(flags: u16, protocol: u8, algorithm: u8, public_key: &[u8]) -> u16 {
+    let [flags_hi, flags_lo] = flags.to_be_bytes();
+    let rdata = [flags_hi, flags_lo, protocol, algorithm];
+    let mut sum: u32 = 0;
+    for (i, byte) in rdata.iter().chain(public_key.iter()).enumerate() {
+        sum += if i % 2 == 0 { (*byte as u32) << 8 } else { *byte as u32 };
+    }
+    sum += (sum >> 16) & 0xffff;
+    (sum & 0xffff) as u16
+}
+
+/// Attestation field limits
+const MAX_CLAIM_TYPE_LEN: usize = 32;
+const MAX_CLAIM_URI_LEN: usize = 200;
+
+/// Freshness bounds for dynamic records (seconds)
+const MIN_DYNAMIC_STALENESS: u32 = 60;
+const MAX_DYNAMIC_STALENESS: u32 = 7 * 24 * 60 * 60;
+
+/// Domains listed per namespace index page
+const INDEX_PAGE_CAPACITY: usize = 64;
+
+/// Append a domain to the tail index page
+/// 
+/// # Returns
+/// - The page number the domain was written to
+/// 
+/// # Errors
+/// - IndexPageFull: Tail page is full; `append_index_page` must run first
+fn append_to_index(
+    namespace_index: &mut NamespaceIndex,
+    index_page: &mut IndexPage,
+    domain: Pubkey,
+) -> Result<u32> {
+    require!(
+        index_page.domains.len() < INDEX_PAGE_CAPACITY,
+        DnsError::IndexPageFull
+    );
+    index_page.domains.push(domain);
+    namespace_index.total_domains += 1;
+    Ok(index_page.page)
+}
+
+/// Move a fee from the payer to the treasury
+/// 
+/// Checks the balance first so an underfunded payer gets
+/// `InsufficientFee` instead of a bare system program error.
+fn collect_fee<'info>(
+    system_program: &Program<'info, System>,
+    payer: &Signer<'info>,
+    treasury: &UncheckedAccount<'info>,
+    lamports: u64,
+) -> Result<()> {
+    if lamports == 0 {
+        return Ok(());
+    }
+    require!(payer.lamports() >= lamports, DnsError::InsufficientFee);
+    system_program::transfer(
+        CpiContext::new(
+            system_program.to_account_info(),
+            system_program::Transfer {
+                from: payer.to_account_info(),
+                to: treasury.to_account_info(),
+            },
+        ),
+        lamports,
+    )
+}
+
+/// Move an SPL token fee from the payer's account to `recipient`'s
+/// 
+/// The token accounts are optional in the contexts, so the mint and
+/// owner checks live here rather than in account constraints.
+#[allow(clippy::too_many_arguments)]
+fn collect_token_fee<'info>(
+    config: &ProgramConfig,
+    token_program: &Interface<'info, TokenInterface>,
+    mint: &InterfaceAccount<'info, Mint>,
+    from: &InterfaceAccount<'info, TokenAccount>,
+    to: &InterfaceAccount<'info, TokenAccount>,
+    recipient: Pubkey,
+    payer: &Signer<'info>,
+    amount: u64,
+) -> Result<()> {
+    require!(
+        config.fee_mint == Some(mint.key()) && from.mint == mint.key() && to.mint == mint.key(),
+        DnsError::WrongFeeMint
+    );
+    require!(from.owner == payer.key(), DnsError::Unauthorized);
+    require!(to.owner == recipient, DnsError::WrongFeeMint);
+    if amount == 0 {
+        return Ok(());
+    }
+    require!(from.amount >= amount, DnsError::InsufficientFee);
+    token_interface::transfer_checked(
+        CpiContext::new(
+            token_program.to_account_info(),
+            TransferChecked {
+                from: from.to_account_info(),
+                mint: mint.to_account_info(),
+                to: to.to_account_info(),
+                authority: payer.to_account_info(),
+            },
+        ),
+        amount,
+        mint.decimals,
+    )
+}
+
+/// Remove a domain from its namespace index page, if listed
+/// 
+/// Swap-removes, so page order is not preserved.
+fn remove_from_index(namespace_index: &mut NamespaceIndex, index_page: &mut IndexPage, domain: &Pubkey) {
+    if let Some(pos) = index_page.domains.iter().position(|d| d == domain) {
+        index_page.domains.swap_remove(pos);
+        namespace_index.total_domains = namespace_index.total_domains.saturating_sub(1);
+    }
+}
+
+/// Bloom filter geometry: 65,536 bits probed 4 times per name
+/// 
+/// With m = 2^16 bits each probe is a little-endian u16 taken straight
+/// from the name's SHA-256 digest, so no extra hashing is needed. False
+/// positives stay under ~2.5% up to ~6,000 names; rebuild into a larger
+/// filter generation beyond that.
+const BLOOM_FILTER_BYTES: usize = 8192;
+const BLOOM_HASHES: usize = 4;
+
+/// DomainRecord layout versions
+/// 
+/// Layout changes are append-only: new fields go after the existing ones
+/// so older accounts still deserialize, and `upgrade_domain_account`
+/// reallocates and converts them.
+/// 
+/// - V1 (1, or 0 for accounts created before versioning): original fields
+/// - V2: appends `updated_at`
+/// - V3: appends `pending_authority` (the zeroed margin reads as `None`)
+/// - V4: appends `record_type` (the zeroed margin reads as `A`)
+/// - V5: appends `record_set_count`
+/// - V6: appends `ttl`
+/// - V7: appends `expires_at` (older accounts get a full term on upgrade)
+/// - V8: appends `open_subdomains` and `subdomain_count`
+/// - V9: appends `owned_since` (older accounts: `created_at`)
+const DOMAIN_LAYOUT_V2: u8 = 2;
+const DOMAIN_LAYOUT_V3: u8 = 3;
+const DOMAIN_LAYOUT_V4: u8 = 4;
+const DOMAIN_LAYOUT_V5: u8 = 5;
+const DOMAIN_LAYOUT_V6: u8 = 6;
+const DOMAIN_LAYOUT_V7: u8 = 7;
+const DOMAIN_LAYOUT_V8: u8 = 8;
+const DOMAIN_LAYOUT_V9: u8 = 9;
+const DOMAIN_LAYOUT_CURRENT: u8 = DOMAIN_LAYOUT_V9;
+
+/// Epochs of per-epoch stats kept in the ring buffer
+const STATS_HISTORY_EPOCHS: usize = 8;
+
+/// HyperLogLog registers for the unique-registrant estimate (~13% error)
+const REGISTRANT_SKETCH_REGISTERS: usize = 64;
+
+/// Maximum values per record set
+const MAX_RECORDS_PER_SET: usize = 8;
+
+/// Record TTL bounds in seconds, and the TTL given to pre-TTL accounts
+const MIN_TTL: u32 = 30;
+const MAX_TTL: u32 = 86_400;
+const DEFAULT_TTL: u32 = 3_600;
+
+/// Registration term, post-expiry phases and renewal limits
+/// 
+/// After `expires_at` a domain stops resolving and enters a grace period
+/// (renewable by its owner), then a redemption period (renewable by its
+/// owner for a fee), after which anyone may reclaim it.
+const REGISTRATION_PERIOD: i64 = 365 * 24 * 60 * 60;
+const GRACE_PERIOD: i64 = 30 * 24 * 60 * 60;
+const REDEMPTION_PERIOD: i64 = 30 * 24 * 60 * 60;
+const REDEMPTION_FEE_LAMPORTS: u64 = 100_000_000;
+const MAX_REGISTRATION_AHEAD: i64 = 10 * REGISTRATION_PERIOD;
+
+/// RFC 1035 name limits: 253 octets in text form, 63 per label
+const MAX_DOMAIN_LEN: usize = 253;
+const MAX_LABEL_LEN: usize = 63;
+
+/// Reserved-name list geometry: 16 chunks of up to 256 name hashes
+/// 
+/// Chunking keeps each account under the 10 KiB CPI allocation limit and
+/// lets a registration load only the chunk its name hashes into.
+const RESERVED_CHUNKS: usize = 16;
+const RESERVED_NAMES_PER_CHUNK: usize = 256;
+
+/// Name lengths (1 through 4 characters) with their own premium multiple
+const PREMIUM_TIERS: usize = 4;
+
+/// Most entries accepted by one `register_batch` call
+const MAX_BATCH_ENTRIES: usize = 16;
+
+/// Validate one batch entry and create its subdomain PDA
+/// 
+/// Every check runs before the account is created, so a rejected entry
+/// leaves no state behind and the batch can skip it.
+fn create_batch_subdomain<'info>(
+    parent: Pubkey,
+    parent_name: &str,
+    target: &AccountInfo<'info>,
+    payer: &Signer<'info>,
+    system_program: &Program<'info, System>,
+    entry: &BatchEntry,
+    now: i64,
+) -> Result<()> {
+    validate_label(&entry.label)?;
+    require!(
+        entry.label.len() + 1 + parent_name.len() <= MAX_DOMAIN_LEN,
+        DnsError::DomainTooLong
+    );
+    validate_record(entry.record_type, &entry.record)?;
+    validate_ttl(entry.ttl)?;
+
+    let label_hash = domain_seed(&entry.label);
+    let (address, bump) = Pubkey::find_program_address(
+        &[b"subdomain", parent.as_ref(), &label_hash],
+        &crate::ID,
+    );
+    require!(
+        target.key() == address && target.is_writable,
+        DnsError::BatchAccountMismatch
+    );
+    require!(
+        target.lamports() == 0 && target.data_is_empty(),
+        DnsError::SubdomainTaken
+    );
+
+    let space = 8 + SubdomainRecord::INIT_SPACE;
+    system_program::create_account(
+        CpiContext::new_with_signer(
+            system_program.to_account_info(),
+            system_program::CreateAccount {
+                from: payer.to_account_info(),
+                to: target.clone(),
+            },
+            &[&[b"subdomain", parent.as_ref(), &label_hash, &[bump]]],
+        ),
+        Rent::get()?.minimum_balance(space),
+        space as u64,
+        &crate::ID,
+    )?;
+    let subdomain = SubdomainRecord {
+        parent,
+        label: entry.label.clone(),
+        record: canonical_record(entry.record_type, entry.record.clone()),
+        record_type: entry.record_type,
+        ttl: entry.ttl,
+        authority: entry.owner,
+        created_at: now,
+        updated_at: now,
+        bump,
+    };
+    subdomain.try_serialize(&mut &mut target.try_borrow_mut_data()?[..])?;
+
+    emit!(SubdomainRegistered {
+        parent,
+        subdomain: address,
+        label: subdomain.label,
+        authority: entry.owner,
+    });
+    Ok(())
+}
+
+/// Past records kept per domain by `RecordHistory`
+const RECORD_HISTORY_LEN: usize = 8;
+
+/// Most guardians a `GuardianSet` can list
+const MAX_GUARDIANS: usize = 10;
+
+/// Challenge period between `start_recovery` and `complete_recovery`
+/// (7 days), during which the owner can veto
+const RECOVERY_PERIOD: i64 = 7 * 24 * 60 * 60;
+
+/// Shortest heartbeat period `set_beneficiary` accepts (30 days)
+const MIN_HEARTBEAT_PERIOD: i64 = 30 * 24 * 60 * 60;
+
+/// Longest heartbeat period `set_beneficiary` accepts (5 years)
+const MAX_HEARTBEAT_PERIOD: i64 = 5 * 365 * 24 * 60 * 60;
+
+/// Most members a `DomainGovernance` can list
+const MAX_GOVERNANCE_MEMBERS: usize = 10;
+
+/// Check a domain's governance, if it has any, against the signers passed
+/// 
+/// `governance` is the domain's governance PDA; when it was never created
+/// (or lapsed with a transfer) the action only needs the usual signer.
+fn require_governance(
+    domain: &DomainRecord,
+    governance: &UncheckedAccount,
+    signers: &[AccountInfo],
+) -> Result<()> {
+    if governance.data_is_empty() {
+        return Ok(());
+    }
+    let governance = Account::<DomainGovernance>::try_from(governance.as_ref())?;
+    if governance.applies_to(domain) {
+        require!(
+            governance.approvals(signers) >= governance.threshold,
+            DnsError::GovernanceThreshold
+        );
+    }
+    Ok(())
+}
+
+/// Shortest unlock delay `lock_domain` accepts (1 day)
+const MIN_UNLOCK_DELAY: i64 = 24 * 60 * 60;
+
+/// Longest unlock delay `lock_domain` accepts (365 days)
+const MAX_UNLOCK_DELAY: i64 = 365 * 24 * 60 * 60;
+
+/// Fail if the domain's lock PDA holds a lock that is still in force
+fn require_unlocked(domain: &DomainRecord, lock: &UncheckedAccount) -> Result<()> {
+    if lock.data_is_empty() {
+        return Ok(());
+    }
+    let lock = Account::<DomainLock>::try_from(lock.as_ref())?;
+    require!(
+        !(lock.applies_to(domain) && lock.is_locked(Clock::get()?.unix_timestamp)),
+        DnsError::DomainLocked
+    );
+    Ok(())
+}
+
+/// Shortest lease `lease_domain` accepts (1 day)
+const MIN_LEASE_DURATION: i64 = 24 * 60 * 60;
+
+/// Longest lease `lease_domain` accepts (365 days)
+const MAX_LEASE_DURATION: i64 = 365 * 24 * 60 * 60;
+
+/// Tenant of the domain's lease PDA if a lease is running at `now`
+fn active_lease(domain: &DomainRecord, lease: &UncheckedAccount, now: i64) -> Result<Option<Pubkey>> {
+    if lease.data_is_empty() {
+        return Ok(None);
+    }
+    let lease = Account::<DomainLease>::try_from(lease.as_ref())?;
+    Ok(lease.is_active(domain, now).then_some(lease.tenant))
+}
+
+/// Last label of a domain name, the TLD it is registered under
+fn tld_of(domain_name: &str) -> &str {
+    domain_name.rsplit('.').next().unwrap_or(domain_name)
+}
+
+/// Fail if the name's TLD is owned and restricted and neither its owner
+/// nor its registrar signed
+fn require_tld_approval(tld: &UncheckedAccount, approver: Option<&Signer>) -> Result<()> {
+    if tld.data_is_empty() {
+        return Ok(());
+    }
+    let tld = Account::<TldRecord>::try_from(tld.as_ref())?;
+    require!(
+        tld.allows(approver.map(|signer| signer.key())),
+        DnsError::TldApprovalRequired
+    );
+    Ok(())
+}
+
+/// Basis points in a whole
+const BPS_DENOMINATOR: u64 = 10_000;
+
+/// The TLD owner's cut of `fee`, checking `recipient` is the TLD treasury
+/// (or token account owned by it) whenever there is a cut to pay
+fn tld_cut(tld: &UncheckedAccount, recipient: Option<Pubkey>, fee: u64) -> Result<u64> {
+    if tld.data_is_empty() {
+        return Ok(0);
+    }
+    let tld = Account::<TldRecord>::try_from(tld.as_ref())?;
+    let cut = tld.share_of(fee);
+    if cut > 0 {
+        require!(recipient == Some(tld.treasury), DnsError::WrongTldTreasury);
+    }
+    Ok(cut)
+}
+
+/// Resize a program-owned account to `new_len`, settling rent with `payer`
+/// 
+/// Growth is paid by `payer`; when the account shrinks, lamports above
+/// the new rent-exempt minimum go back to `payer`. Anchor serializes the
+/// account into the new size on exit.
+fn fit_account<'info>(
+    account: &AccountInfo<'info>,
+    new_len: usize,
+    payer: &Signer<'info>,
+    system_program: &Program<'info, System>,
+) -> Result<()> {
+    let rent = Rent::get()?.minimum_balance(new_len);
+    let balance = account.lamports();
+    if rent > balance {
+        system_program::transfer(
+            CpiContext::new(
+                system_program.to_account_info(),
+                system_program::Transfer {
+                    from: payer.to_account_info(),
+                    to: account.clone(),
+                },
+            ),
+            rent - balance,
+        )?;
+    } else if balance > rent {
+        **account.try_borrow_mut_lamports()? -= balance - rent;
+        **payer.to_account_info().try_borrow_mut_lamports()? += balance - rent;
+    }
+    account.resize(new_len)?;
+    Ok(())
+}
+
+/// Shared body of `update_record` and `execute_update`
+/// 
+/// Validates and stores the new record, keeps the history ring buffer
+/// up to date and emits `DomainUpdated`; callers check who may write.
+fn write_record(
+    domain_account: &mut Account<DomainRecord>,
+    history: &mut Account<RecordHistory>,
+    history_bump: u8,
+    updated_by: Pubkey,
+    record: String,
+    record_type: RecordType,
+    ttl: u32,
+) -> Result<()> {
+    validate_record(record_type, &record)?;
+    validate_ttl(ttl)?;
+    let record = canonical_record(record_type, record);
+
+    require!(
+        record_type != RecordType::Cname || domain_account.record_set_count == 0,
+        DnsError::CnameConflict
+    );
+    domain_account.migrate_layout(Clock::get()?.unix_timestamp);
+
+    // Back-fill the value in effect if history missed it (first use,
+    // or a registration since the last update)
+    if history
+        .newest_first()
+        .next()
+        .map_or(true, |latest| latest.updated_at < domain_account.updated_at)
+    {
+        history.domain = domain_account.key();
+        history.bump = history_bump;
+        history.push(HistoryEntry {
+            record_type: domain_account.record_type,
+            record: domain_account.record.clone(),
+            ttl: domain_account.ttl,
+            updated_at: domain_account.updated_at,
+            updated_by: domain_account.authority,
+        });
+    }
+
+    domain_account.record = record;
+    domain_account.record_type = record_type;
+    domain_account.ttl = ttl;
+    domain_account.updated_at = Clock::get()?.unix_timestamp;
+    history.push(HistoryEntry {
+        record_type,
+        record: domain_account.record.clone(),
+        ttl,
+        updated_at: domain_account.updated_at,
+        updated_by,
+    });
+
+    emit!(DomainUpdated {
+        domain: domain_account.key(),
+        authority: domain_account.authority,
+        record: domain_account.record.clone(),
+        record_type,
+        ttl,
+        slot: Clock::get()?.slot,
+    });
+
+    msg!("✏️ NEURA DNS - Record Updated");
+    msg!("Domain: {}", domain_account.domain_name);
+    msg!("Record: {} {} (TTL {})", record_type.mnemonic(), domain_account.record, ttl);
+    Ok(())
+}
+
+/// Shortest delay `set_update_delay` accepts (1 hour)
+const MIN_UPDATE_DELAY: i64 = 60 * 60;
+
+/// Longest delay `set_update_delay` accepts (30 days)
+const MAX_UPDATE_DELAY: i64 = 30 * 24 * 60 * 60;
+
+/// Delay of the domain's update timelock, if one is in force
+fn update_delay(domain: &DomainRecord, timelock: &UncheckedAccount, now: i64) -> Result<Option<i64>> {
+    if timelock.data_is_empty() {
+        return Ok(None);
+    }
+    let timelock = Account::<UpdateTimelock>::try_from(timelock.as_ref())?;
+    Ok(Some(timelock.delay).filter(|_| timelock.applies_to(domain) && timelock.is_in_force(now)))
+}
+
+/// Owner-side checks for writing a domain's records directly: governance
+/// approval (members sign as `signers`), no lock and no update timelock
+/// in force
+fn require_record_write(
+    domain: &DomainRecord,
+    governance: &UncheckedAccount,
+    lock: &UncheckedAccount,
+    timelock: &UncheckedAccount,
+    signers: &[AccountInfo],
+) -> Result<()> {
+    require_governance(domain, governance, signers)?;
+    require_unlocked(domain, lock)?;
+    require!(
+        update_delay(domain, timelock, Clock::get()?.unix_timestamp)?.is_none(),
+        DnsError::UpdateTimelocked
+    );
+    Ok(())
+}
+
+/// Shared body of `register_request`, `reveal_and_register` and
+/// `settle_auction`
+/// 
+/// `prepaid` is the amount already paid into the treasury (a winning
+/// bid); otherwise the registration fee is collected here, and names
+/// reserved for auction are refused.
+fn register_domain(
+    ctx: Context<RegisterDomain>,
+    domain_name: String,
+    record: String,
+    record_type: RecordType,
+    ttl: u32,
+    prepaid: Option<u64>,
+) -> Result<()> {
+    verbose_msg!("🌐 NEURA DNS - Domain Registration Request");
+    verbose_msg!("Domain: {}", domain_name);
+    verbose_msg!("Record: {} {} (TTL {})", record_type.mnemonic(), record, ttl);
+    verbose_msg!("Authority: {}", ctx.accounts.authority.key());
+    ctx.accounts.config.require_not_paused()?;
+
+    // Validate domain and record format
+    validate_domain_and_record(&domain_name, record_type, &record)?;
+    validate_ttl(ttl)?;
+    require!(
+        !ctx.accounts.reserved_names.contains(&domain_seed(&domain_name)),
+        DnsError::NameReserved
+    );
+    let fee = match prepaid {
+        Some(amount) => amount,
+        None => {
+            // An auction's TLD approval was checked when it started
+            require_tld_approval(&ctx.accounts.tld, ctx.accounts.tld_approver.as_ref())?;
+            require!(
+                !ctx.accounts.config.requires_auction(&domain_name),
+                DnsError::NameRequiresAuction
+            );
+            let premium = release_premium(&ctx.accounts.release, Clock::get()?.unix_timestamp)?;
+            collect_fee(
+                &ctx.accounts.system_program,
+                &ctx.accounts.authority,
+                &ctx.accounts.treasury,
+                premium,
+            )?;
+            let fee = match (
+                &ctx.accounts.token_program,
+                &ctx.accounts.fee_mint,
+                &ctx.accounts.payer_token_account,
+                &ctx.accounts.treasury_token_account,
+            ) {
+                (Some(token_program), Some(mint), Some(from), Some(to)) => {
+                    let amount = ctx
+                        .accounts
+                        .config
+                        .premium_price(&domain_name, ctx.accounts.config.token_registration_fee);
+                    let tld_to = ctx.accounts.tld_treasury_token_account.as_ref();
+                    let cut = tld_cut(&ctx.accounts.tld, tld_to.map(|to| to.owner), amount)?;
+                    collect_token_fee(
+                        &ctx.accounts.config,
+                        token_program,
+                        mint,
+                        from,
+                        to,
+                        ctx.accounts.config.treasury,
+                        &ctx.accounts.authority,
+                        amount - cut,
+                    )?;
+                    if let Some(tld_to) = tld_to {
+                        collect_token_fee(
+                            &ctx.accounts.config,
+                            token_program,
+                            mint,
+                            from,
+                            tld_to,
+                            tld_to.owner,
+                            &ctx.accounts.authority,
+                            cut,
+                        )?;
+                    }
+                    0
+                }
+                _ => {
+                    let fee = ctx
+                        .accounts
+                        .config
+                        .premium_price(&domain_name, ctx.accounts.config.registration_fee);
+                    let tld_treasury = ctx.accounts.tld_treasury.as_ref();
+                    let cut = tld_cut(&ctx.accounts.tld, tld_treasury.map(|t| t.key()), fee)?;
+                    collect_fee(
+                        &ctx.accounts.system_program,
+                        &ctx.accounts.authority,
+                        &ctx.accounts.treasury,
+                        fee - cut,
+                    )?;
+                    if let Some(tld_treasury) = tld_treasury {
+                        collect_fee(
+                            &ctx.accounts.system_program,
+                            &ctx.accounts.authority,
+                            tld_treasury,
+                            cut,
+                        )?;
+                    }
+                    fee
+                }
+            };
+            fee + premium
+        }
+    };
+    let record = canonical_record(record_type, record);
+
+    // Store domain record on-chain
+    let domain_account = &mut ctx.accounts.domain_account;
+    domain_account.domain_name = domain_name;
+    domain_account.record = record;
+    domain_account.record_type = record_type;
+    domain_account.record_set_count = 0;
+    domain_account.open_subdomains = false;
+    domain_account.subdomain_count = 0;
+    domain_account.ttl = ttl;
+    domain_account.authority = ctx.accounts.authority.key();
+    domain_account.created_at = Clock::get()?.unix_timestamp;
+    domain_account.updated_at = domain_account.created_at;
+    domain_account.owned_since = domain_account.created_at;
+    domain_account.expires_at = domain_account.created_at + REGISTRATION_PERIOD;
+    domain_account.layout_version = DOMAIN_LAYOUT_CURRENT;
+    domain_account.index_page = append_to_index(
+        &mut ctx.accounts.namespace_index,
+        &mut ctx.accounts.index_page,
+        domain_account.key(),
+    )?;
+    ctx.accounts
+        .availability_filter
+        .load_mut()?
+        .insert(&domain_seed(&domain_account.domain_name));
+
+    emit!(DomainRegistered {
+        domain: domain_account.key(),
+        domain_name: domain_account.domain_name.clone(),
+        record: domain_account.record.clone(),
+        record_type,
+        ttl,
+        authority: domain_account.authority,
+        index_page: domain_account.index_page,
+        slot: Clock::get()?.slot,
+    });
+    let epoch = Clock::get()?.epoch;
+    ctx.accounts
+        .protocol_stats
+        .record_registration(epoch, &domain_account.authority);
+    ctx.accounts.protocol_stats.record_fee(epoch, fee);
+
+    verbose_msg!("✅ Registration successful - stored on-chain");
+    Ok(())
+}
+
+/// Slots a registration commitment must age before it can be revealed
+const COMMITMENT_MIN_AGE_SLOTS: u64 = 10;
+
+/// Slots after which an unrevealed commitment expires (~1 day)
+const COMMITMENT_MAX_AGE_SLOTS: u64 = 216_000;
+
+/// Commitment hiding a name until it is revealed
+/// 
+/// Binding the payer stops anyone else reusing a commitment seen on chain.
+fn registration_commitment(domain_name: &str, salt: &[u8; 32], payer: &Pubkey) -> [u8; 32] {
+    hashv(&[domain_name.as_bytes(), salt, payer.as_ref()]).to_bytes()
+}
+
+/// Shortest auction `set_auction_policy` accepts (1 hour)
+const MIN_AUCTION_DURATION: i64 = 60 * 60;
+
+/// Longest auction `set_auction_policy` accepts (30 days)
+const MAX_AUCTION_DURATION: i64 = 30 * 24 * 60 * 60;
+
+/// Bids this close to the end push it back to this far from now (10 min)
+const AUCTION_EXTENSION: i64 = 10 * 60;
+
+/// How long after `ends_at` the winner has to settle before anyone may
+/// `refund_bid` (30 days)
+const AUCTION_SETTLE_WINDOW: i64 = 30 * 24 * 60 * 60;
+
+/// Release premium halves this often (1 day)
+const RELEASE_HALF_LIFE: i64 = 24 * 60 * 60;
+
+/// Release premium is gone this long after a reclaim (21 days)
+const RELEASE_PERIOD: i64 = 21 * RELEASE_HALF_LIFE;
+
+/// Lamports owed on top of the fee for re-registering a released name
+fn release_premium(release: &UncheckedAccount, now: i64) -> Result<u64> {
+    if release.data_is_empty() {
+        return Ok(0);
+    }
+    Ok(Account::<ReleaseRecord>::try_from(release.as_ref())?.premium_at(now))
+}
+
+/// Whether `signer` holds the NFT of wrapped `domain` in `token`
+fn holds_domain_token(domain: &DomainRecord, token: &TokenAccount, signer: &Pubkey) -> bool {
+    domain.wrapped_mint == Some(token.mint) && token.owner == *signer && token.amount == 1
+}
+
+/// Account context for domain registration
+/// 
+/// # Accounts
+/// * `domain_account` - PDA account to store domain data (init, mutable)
+/// * `authority` - Wallet signing the transaction (signer, mutable for rent)
+/// * `system_program` - Solana system program for account creation
+/// * `namespace_index` - Index head PDA [b"index"] (mutable)
+/// * `index_page` - Current tail page [b"index_page", page_count - 1] (mutable)
+/// * `availability_filter` - Bloom filter PDA [b"bloom"] (mutable)
+/// * `protocol_stats` - Stats PDA [b"stats"] (mutable)
+/// * `reserved_names` - Reserved-name chunk for this name
+///   [b"reserved", reserved_chunk(domain_name)]
+/// * `config` - Program config PDA [b"config"] holding the fee schedule
+/// * `treasury` - Fee recipient named in the config (mutable)
+/// * `release` - Release PDA [b"release", sha256(domain_name)], set when
+///   the name was last reclaimed (may be empty)
+/// * `tld` - TLD PDA [b"tld", sha256(tld)] for the name's last label
+///   (may be empty)
+/// * `token_program`, `fee_mint`, `payer_token_account`,
+///   `treasury_token_account` - Optional; pass all four to pay the fee in
+///   the configured SPL mint instead of lamports
+/// * `commitment` - Registration commitment (`reveal_and_register` only)
+/// * `auction` - Ended auction for the name (`settle_auction` only)
+/// * `tld_approver` - TLD owner or registrar, for a restricted TLD (optional)
+/// * `tld_treasury`, `tld_treasury_token_account` - The TLD's fee
+///   recipient (or its token account, when paying in the fee mint);
+///   required when the TLD takes a revenue share
+/// 
+/// # PDA Derivation
+/// - Seeds: [b"domain", sha256(domain_name)]
+/// - Deterministic address based on domain name
+/// - Hashing keeps the seed at 32 bytes for any name length
+/// - Collision-free, one domain per PDA
+/// 
+/// # Storage
+/// - Space: DomainRecord::space_for(name length, record length)
+/// - Payer: authority (transaction signer)
+/// - Rent: Exempt (automatically calculated)
+#[derive(Accounts)]
+#[instruction(domain_name: String, record: String)]
+pub struct RegisterDomain<'info> {
+    #[account(
+        init,
+        payer = authority,
+        space = DomainRecord::space_for(domain_name.len(), record.len()),
+        seeds = [b"domain", &domain_seed(&domain_name)],
+        bump
+    )]
+    pub domain_account: Account<'info, DomainRecord>,
+    
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    
+    pub system_program: Program<'info, System>,
+
+    #[account(mut, seeds = [b"index"], bump = namespace_index.bump)]
+    pub namespace_index: Account<'info, NamespaceIndex>,
+
+    #[account(
+        mut,
+        seeds = [b"index_page", &(namespace_index.page_count - 1).to_le_bytes()],
+        bump = index_page.bump
+    )]
+    pub index_page: Account<'info, IndexPage>,
+
+    #[account(mut, seeds = [b"bloom"], bump)]
+    pub availability_filter: AccountLoader<'info, AvailabilityFilter>,
+
+    #[account(mut, seeds = [b"stats"], bump = protocol_stats.bump)]
+    pub protocol_stats: Account<'info, ProtocolStats>,
+
+    #[account(
+        seeds = [b"reserved", &[reserved_chunk(&domain_name)]],
+        bump = reserved_names.bump
+    )]
+    pub reserved_names: Account<'info, ReservedNames>,
+
+    #[account(seeds = [b"config"], bump = config.bump, has_one = treasury)]
+    pub config: Account<'info, ProgramConfig>,
+
+    /// CHECK: Fee recipient, checked against config.treasury
+    #[account(mut)]
+    pub treasury: UncheckedAccount<'info>,
+
+    /// CHECK: Release PDA, empty unless the name was reclaimed; read by `release_premium`
+    #[account(seeds = [b"release", &domain_seed(&domain_name)], bump)]
+    pub release: UncheckedAccount<'info>,
+
+    /// CHECK: TLD PDA, empty unless the TLD is owned; read by `require_tld_approval`
+    #[account(seeds = [b"tld", &domain_seed(tld_of(&domain_name))], bump)]
+    pub tld: UncheckedAccount<'info>,
+
+    pub token_program: Option<Interface<'info, TokenInterface>>,
+
+    pub fee_mint: Option<Box<InterfaceAccount<'info, Mint>>>,
+
+    #[account(mut)]
+    pub payer_token_account: Option<Box<InterfaceAccount<'info, TokenAccount>>>,
+
+    #[account(mut)]
+    pub treasury_token_account: Option<Box<InterfaceAccount<'info, TokenAccount>>>,
+
+    #[account(mut)]
+    pub commitment: Option<Account<'info, RegistrationCommitment>>,
+
+    #[account(mut, seeds = [b"auction", &domain_seed(&domain_name)], bump = auction.bump)]
+    pub auction: Option<Account<'info, Auction>>,
+
+    pub tld_approver: Option<Signer<'info>>,
+
+    /// CHECK: TLD fee recipient, checked against the TLD's treasury by `tld_cut`
+    #[account(mut)]
+    pub tld_treasury: Option<UncheckedAccount<'info>>,
+
+    #[account(mut)]
+    pub tld_treasury_token_account: Option<Box<InterfaceAccount<'info, TokenAccount>>>,
+}
+
+/// Account context for starting an auction
+/// 
+/// # Accounts
+/// * `auction` - Auction PDA (init, holds the escrowed bid)
+/// * `domain_account` - Domain PDA for the name (must not exist yet)
+/// * `config` - Program config PDA holding the auction policy
+/// * `bidder` - Opening bidder (signer, pays the bid and rent)
+/// * `tld` - TLD PDA [b"tld", sha256(tld)] (may be empty)
+/// * `tld_approver` - TLD owner or registrar, for a restricted TLD (optional)
+/// 
+/// # PDA Derivation
+/// - Seeds: [b"auction", sha256(domain_name)]
+#[derive(Accounts)]
+#[instruction(domain_name: String)]
+pub struct StartAuction<'info> {
+    #[account(
+        init,
+        payer = bidder,
+        space = 8 + Auction::INIT_SPACE,
+        seeds = [b"auction", &domain_seed(&domain_name)],
+        bump
+    )]
+    pub auction: Account<'info, Auction>,
+
+    /// CHECK: Only checked to be empty, i.e. the name is unregistered
+    #[account(seeds = [b"domain", &domain_seed(&domain_name)], bump)]
+    pub domain_account: UncheckedAccount<'info>,
+
+    #[account(seeds = [b"config"], bump = config.bump)]
+    pub config: Account<'info, ProgramConfig>,
+
+    #[account(mut)]
+    pub bidder: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+
+    /// CHECK: TLD PDA, empty unless the TLD is owned; read by `require_tld_approval`
+    #[account(seeds = [b"tld", &domain_seed(tld_of(&domain_name))], bump)]
+    pub tld: UncheckedAccount<'info>,
+
+    pub tld_approver: Option<Signer<'info>>,
+}
+
+/// Account context for refunding an unsettled auction
+/// 
+/// # Accounts
+/// * `auction` - Auction PDA (closed, bid and rent to the highest bidder)
+/// * `highest_bidder` - Winning bidder (mutable, refunded; need not sign)
+/// * `domain_account` - Domain PDA for the name (registered or empty)
+/// * `config` - Program config PDA holding the auction policy
+/// * `signer` - Anyone (signer)
+#[derive(Accounts)]
+pub struct RefundBid<'info> {
+    #[account(
+        mut,
+        seeds = [b"auction", &domain_seed(&auction.domain_name)],
+        bump = auction.bump,
+        close = highest_bidder
+    )]
+    pub auction: Account<'info, Auction>,
+
+    /// CHECK: Refund recipient, checked against auction.highest_bidder
+    #[account(mut, address = auction.highest_bidder @ DnsError::Unauthorized)]
+    pub highest_bidder: UncheckedAccount<'info>,
+
+    /// CHECK: Only checked for being empty, i.e. the name is unregistered
+    #[account(seeds = [b"domain", &domain_seed(&auction.domain_name)], bump)]
+    pub domain_account: UncheckedAccount<'info>,
+
+    #[account(seeds = [b"config"], bump = config.bump)]
+    pub config: Account<'info, ProgramConfig>,
+
+    pub signer: Signer<'info>,
+}
+
+/// Account context for bidding in an auction
+/// 
+/// # Accounts
+/// * `auction` - Auction PDA (mutable, escrow)
+/// * `previous_bidder` - Current leader (mutable, refunded)
+/// * `bidder` - New bidder (signer, pays the bid)
+#[derive(Accounts)]
+pub struct PlaceBid<'info> {
+    #[account(mut)]
+    pub auction: Account<'info, Auction>,
+
+    /// CHECK: Refund recipient, checked against auction.highest_bidder
+    #[account(mut, address = auction.highest_bidder @ DnsError::Unauthorized)]
+    pub previous_bidder: UncheckedAccount<'info>,
+
+    #[account(mut)]
+    pub bidder: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// Account context for committing to a registration
+/// 
+/// # Accounts
+/// * `commitment` - Commitment PDA (init)
+/// * `committer` - Wallet that will reveal (signer, pays rent)
+/// 
+/// # PDA Derivation
+/// - Seeds: [b"commit", commitment]
+#[derive(Accounts)]
+#[instruction(commitment: [u8; 32])]
+pub struct CommitRegistration<'info> {
+    #[account(
+        init,
+        payer = committer,
+        space = 8 + RegistrationCommitment::INIT_SPACE,
+        seeds = [b"commit", commitment.as_ref()],
+        bump
+    )]
+    pub commitment: Account<'info, RegistrationCommitment>,
+
+    #[account(mut)]
+    pub committer: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// Account context for closing an unused commitment
+/// 
+/// # Accounts
+/// * `commitment` - Commitment PDA (closed)
+/// * `committer` - Wallet that committed (signer, receives rent)
+#[derive(Accounts)]
+pub struct CloseCommitment<'info> {
+    #[account(
+        mut,
+        has_one = committer @ DnsError::Unauthorized,
+        close = committer
+    )]
+    pub commitment: Account<'info, RegistrationCommitment>,
+
+    #[account(mut)]
+    pub committer: Signer<'info>,
+}
+
+/// Account context for domain resolution
+/// 
+/// # Accounts
+/// * `domain_account` - PDA account containing domain data (read-only)
+/// * `authority` - Wallet querying the domain (signer)
+/// * `dynamic_record` - Optional updater-fed record PDA [b"dynamic", domain_account]
+/// 
+/// # PDA Derivation
+/// - Same seeds as registration: [b"domain", sha256(domain_name)]
+/// - Account must exist (created during registration)
+/// 
+/// # Notes
+/// - Read-only operation
+/// - No state modification
+/// - No rent fees
+#[derive(Accounts)]
+#[instruction(domain_name: String)]
+pub struct ResolveDomain<'info> {
+    #[account(
+        seeds = [b"domain", &domain_seed(&domain_name)],
+        bump
+    )]
+    pub domain_account: Account<'info, DomainRecord>,
+    
+    pub authority: Signer<'info>,
+
+    #[account(
+        seeds = [b"dynamic", domain_account.key().as_ref()],
+        bump
+    )]
+    pub dynamic_record: Option<Account<'info, DynamicRecord>>,
+}
+
+/// Account context for wrapping a domain as an NFT
+/// 
+/// # Accounts
+/// * `domain_account` - Domain being wrapped (mutable, owned by authority)
+/// * `authority` - Current domain owner (signer, pays for all new accounts)
+/// * `wrap_escrow` - PDA that holds domain authority while wrapped
+/// * `mint` - Fresh Token-2022 mint keypair with a metadata pointer (init, signer)
+/// * `holder_token_account` - Authority's ATA for the mint (init)
+/// * `token_program` - Token-2022 program
+/// * `governance` - Governance PDA (empty unless `set_governance` was called)
+/// * `lock` - Lock PDA (empty unless `lock_domain` was called)
+/// * `config` - Singleton config PDA (feature toggles, pause flag)
+/// 
+/// # PDA Derivation
+/// - Escrow seeds: [b"wrap_escrow", domain_account.key()]
+#[derive(Accounts)]
+pub struct WrapDomain<'info> {
+    #[account(mut, has_one = authority @ DnsError::Unauthorized)]
+    pub domain_account: Account<'info, DomainRecord>,
+
+    /// CHECK: Governance PDA, empty unless set; read by `require_governance`
+    #[account(seeds = [b"governance", domain_account.key().as_ref()], bump)]
+    pub governance: UncheckedAccount<'info>,
+
+    /// CHECK: Lock PDA, empty unless locked; read by `require_unlocked`
+    #[account(seeds = [b"lock", domain_account.key().as_ref()], bump)]
+    pub lock: UncheckedAccount<'info>,
+
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    /// CHECK: Data-less PDA used only as a signer for mint and metadata authority
+    #[account(
+        seeds = [b"wrap_escrow", domain_account.key().as_ref()],
+        bump
+    )]
+    pub wrap_escrow: UncheckedAccount<'info>,
+
+    #[account(
+        init,
+        payer = authority,
+        mint::decimals = 0,
+        mint::authority = wrap_escrow,
+        mint::freeze_authority = wrap_escrow,
+        mint::token_program = token_program,
+        extensions::metadata_pointer::authority = wrap_escrow,
+        extensions::metadata_pointer::metadata_address = mint
+    )]
+    pub mint: Box<InterfaceAccount<'info, Mint>>,
+
+    #[account(
+        init,
+        payer = authority,
+        associated_token::mint = mint,
+        associated_token::authority = authority,
+        associated_token::token_program = token_program
+    )]
+    pub holder_token_account: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    pub token_program: Program<'info, Token2022>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub system_program: Program<'info, System>,
+
+    #[account(seeds = [b"config"], bump = config.bump)]
+    pub config: Box<Account<'info, ProgramConfig>>,
+}
+
+/// Account context for unwrapping a domain NFT
+/// 
+/// # Accounts
+/// * `domain_account` - Wrapped domain (mutable)
+/// * `holder` - Current owner of the domain token (signer)
+/// * `mint` - The domain's wrapped mint (must match `wrapped_mint`)
+/// * `holder_token_account` - Holder's token account with the NFT
+/// * `config` - Singleton config PDA (pause flag)
+/// 
+/// # Notes
+/// - Works for any holder, not only the wallet that wrapped the domain
+#[derive(Accounts)]
+pub struct UnwrapDomain<'info> {
+    #[account(
+        mut,
+        constraint = domain_account.wrapped_mint == Some(mint.key()) @ DnsError::NotWrapped
+    )]
+    pub domain_account: Account<'info, DomainRecord>,
+
+    pub holder: Signer<'info>,
+
+    #[account(mut, mint::token_program = token_program)]
+    pub mint: Box<InterfaceAccount<'info, Mint>>,
+
+    #[account(
+        mut,
+        token::mint = mint,
+        token::authority = holder,
+        token::token_program = token_program,
+        constraint = holder_token_account.amount == 1 @ DnsError::NotTokenHolder
+    )]
+    pub holder_token_account: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+
+    #[account(seeds = [b"config"], bump = config.bump)]
+    pub config: Box<Account<'info, ProgramConfig>>,
+}
+
+/// Account context for listing a domain for sale
+/// 
+/// # Accounts
+/// * `domain_account` - Domain to sell (owned by authority)
+/// * `listing` - Listing PDA (init)
+/// * `authority` - Domain owner (signer, pays rent)
+/// * `governance` - Governance PDA (empty unless `set_governance` was called)
+/// * `lock` - Lock PDA (empty unless `lock_domain` was called)
+/// * `config` - Singleton config PDA (feature toggles)
+/// 
+/// # PDA Derivation
+/// - Seeds: [b"listing", domain_account.key()]
+#[derive(Accounts)]
+pub struct ListForSale<'info> {
+    #[account(has_one = authority @ DnsError::Unauthorized)]
+    pub domain_account: Account<'info, DomainRecord>,
+
+    #[account(
+        init,
+        payer = authority,
+        space = 8 + Listing::INIT_SPACE,
+        seeds = [b"listing", domain_account.key().as_ref()],
+        bump
+    )]
+    pub listing: Account<'info, Listing>,
+
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+
+    /// CHECK: Governance PDA, empty unless set; read by `require_governance`
+    #[account(seeds = [b"governance", domain_account.key().as_ref()], bump)]
+    pub governance: UncheckedAccount<'info>,
+
+    /// CHECK: Lock PDA, empty unless locked; read by `require_unlocked`
+    #[account(seeds = [b"lock", domain_account.key().as_ref()], bump)]
+    pub lock: UncheckedAccount<'info>,
+
+    #[account(seeds = [b"config"], bump = config.bump)]
+    pub config: Box<Account<'info, ProgramConfig>>,
+}
+
+/// Account context for buying a listed domain
+/// 
+/// # Accounts
+/// * `domain_account` - Listed domain (mutable)
+/// * `listing` - Listing PDA (closed)
+/// * `seller` - Lister (mutable, receives price and rent)
+/// * `buyer` - New owner (signer, pays the price)
+/// * `lock` - Lock PDA (empty unless `lock_domain` was called)
+/// * `config` - Singleton config PDA (feature toggles, pause flag)
+#[derive(Accounts)]
+pub struct BuyDomain<'info> {
+    #[account(mut)]
+    pub domain_account: Account<'info, DomainRecord>,
+
+    #[account(
+        mut,
+        seeds = [b"listing", domain_account.key().as_ref()],
+        bump = listing.bump,
+        has_one = seller @ DnsError::Unauthorized,
+        close = seller
+    )]
+    pub listing: Account<'info, Listing>,
+
+    /// CHECK: Payment recipient, checked against listing.seller
+    #[account(mut)]
+    pub seller: UncheckedAccount<'info>,
+
+    #[account(mut)]
+    pub buyer: Signer<'info>,
+
+    /// CHECK: Lock PDA, empty unless locked; read by `require_unlocked`
+    #[account(seeds = [b"lock", domain_account.key().as_ref()], bump)]
+    pub lock: UncheckedAccount<'info>,
+
+    pub system_program: Program<'info, System>,
+
+    #[account(seeds = [b"config"], bump = config.bump)]
+    pub config: Box<Account<'info, ProgramConfig>>,
+}
+
+/// Account context for cancelling a listing
+/// 
+/// # Accounts
+/// * `listing` - Listing PDA (closed)
+/// * `seller` - Lister (signer, receives rent)
+#[derive(Accounts)]
+pub struct CancelListing<'info> {
+    #[account(
+        mut,
+        has_one = seller @ DnsError::Unauthorized,
+        close = seller
+    )]
+    pub listing: Account<'info, Listing>,
+
+    #[account(mut)]
+    pub seller: Signer<'info>,
+}
+
+/// Account context for opening a sale escrow
+/// 
+/// # Accounts
+/// * `domain_account` - Domain to sell (owned by authority, mutable)
+/// * `escrow` - Escrow PDA (init), becomes the domain authority
+/// * `authority` - Seller (signer, pays rent)
+/// * `governance` - Governance PDA (empty unless `set_governance` was called)
+/// * `lock` - Lock PDA (empty unless `lock_domain` was called)
+/// * `config` - Singleton config PDA (feature toggles, pause flag)
+/// 
+/// # PDA Derivation
+/// - Seeds: [b"escrow", domain_account.key()]
+#[derive(Accounts)]
+pub struct OpenEscrow<'info> {
+    #[account(mut, has_one = authority @ DnsError::Unauthorized)]
+    pub domain_account: Account<'info, DomainRecord>,
+
+    #[account(
+        init,
+        payer = authority,
+        space = 8 + SaleEscrow::INIT_SPACE,
+        seeds = [b"escrow", domain_account.key().as_ref()],
+        bump
+    )]
+    pub escrow: Account<'info, SaleEscrow>,
+
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+
+    /// CHECK: Governance PDA, empty unless set; read by `require_governance`
+    #[account(seeds = [b"governance", domain_account.key().as_ref()], bump)]
+    pub governance: UncheckedAccount<'info>,
+
+    /// CHECK: Lock PDA, empty unless locked; read by `require_unlocked`
+    #[account(seeds = [b"lock", domain_account.key().as_ref()], bump)]
+    pub lock: UncheckedAccount<'info>,
+
+    #[account(seeds = [b"config"], bump = config.bump)]
+    pub config: Box<Account<'info, ProgramConfig>>,
+}
+
+/// Account context for completing a sale escrow
+/// 
+/// # Accounts
+/// * `domain_account` - Escrowed domain (mutable)
+/// * `escrow` - Escrow PDA (closed)
+/// * `seller` - Seller (mutable, receives price and rent)
+/// * `buyer` - Named buyer (signer, pays the price)
+/// * `config` - Singleton config PDA (pause flag)
+#[derive(Accounts)]
+pub struct CompleteEscrow<'info> {
+    #[account(mut, address = escrow.domain)]
+    pub domain_account: Account<'info, DomainRecord>,
+
+    #[account(
+        mut,
+        seeds = [b"escrow", domain_account.key().as_ref()],
+        bump = escrow.bump,
+        has_one = seller @ DnsError::Unauthorized,
+        has_one = buyer @ DnsError::Unauthorized,
+        close = seller
+    )]
+    pub escrow: Account<'info, SaleEscrow>,
+
+    /// CHECK: Payment recipient, checked against escrow.seller
+    #[account(mut)]
+    pub seller: UncheckedAccount<'info>,
+
+    #[account(mut)]
+    pub buyer: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+
+    #[account(seeds = [b"config"], bump = config.bump)]
+    pub config: Box<Account<'info, ProgramConfig>>,
+}
+
+/// Account context for cancelling a sale escrow
+/// 
+/// # Accounts
+/// * `domain_account` - Escrowed domain (mutable)
+/// * `escrow` - Escrow PDA (closed)
+/// * `seller` - Seller (mutable, receives rent)
+/// * `signer` - Seller or named buyer
+/// * `config` - Singleton config PDA (pause flag)
+#[derive(Accounts)]
+pub struct CancelEscrow<'info> {
+    #[account(mut, address = escrow.domain)]
+    pub domain_account: Account<'info, DomainRecord>,
+
+    #[account(
+        mut,
+        seeds = [b"escrow", domain_account.key().as_ref()],
+        bump = escrow.bump,
+        has_one = seller @ DnsError::Unauthorized,
+        close = seller
+    )]
+    pub escrow: Account<'info, SaleEscrow>,
+
+    /// CHECK: Rent recipient, checked against escrow.seller
+    #[account(mut)]
+    pub seller: UncheckedAccount<'info>,
+
+    pub signer: Signer<'info>,
+
+    #[account(seeds = [b"config"], bump = config.bump)]
+    pub config: Box<Account<'info, ProgramConfig>>,
+}
+
+/// Account context for publishing a DID document
+/// 
+/// # Accounts
+/// * `domain_account` - Domain the DID belongs to (owned by authority)
+/// * `did_record` - PDA holding DID data (created on first use)
+/// * `authority` - Domain owner (signer, pays rent)
+/// * `governance` - Governance PDA (empty unless `set_governance` was called)
+/// * `lock` - Lock PDA (empty unless `lock_domain` was called)
+/// * `timelock` - Update timelock PDA (empty unless `set_update_delay`
+///   was called)
+/// * `config` - Singleton config PDA (pause flag)
+/// 
+/// # PDA Derivation
+/// - Seeds: [b"did", domain_account.key()]
+#[derive(Accounts)]
+pub struct SetDidDocument<'info> {
+    #[account(has_one = authority @ DnsError::Unauthorized)]
+    pub domain_account: Account<'info, DomainRecord>,
+
+    #[account(
+        init_if_needed,
+        payer = authority,
+        space = 8 + DidRecord::INIT_SPACE,
+        seeds = [b"did", domain_account.key().as_ref()],
+        bump
+    )]
+    pub did_record: Account<'info, DidRecord>,
+
+    /// CHECK: Governance PDA, empty unless set; read by `require_governance`
+    #[account(seeds = [b"governance", domain_account.key().as_ref()], bump)]
+    pub governance: UncheckedAccount<'info>,
+
+    /// CHECK: Lock PDA, empty unless locked; read by `require_unlocked`
+    #[account(seeds = [b"lock", domain_account.key().as_ref()], bump)]
+    pub lock: UncheckedAccount<'info>,
+
+    /// CHECK: Timelock PDA, empty unless set; read by `update_delay`
+    #[account(seeds = [b"timelock", domain_account.key().as_ref()], bump)]
+    pub timelock: UncheckedAccount<'info>,
+
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+
+    #[account(seeds = [b"config"], bump = config.bump)]
+    pub config: Box<Account<'info, ProgramConfig>>,
+}
+
+/// Account context for DID resolution
+/// 
+/// # Accounts
+/// * `domain_account` - Domain the DID belongs to (read-only)
+/// * `did_record` - PDA holding DID data (read-only)
+#[derive(Accounts)]
+pub struct ResolveDid<'info> {
+    pub domain_account: Account<'info, DomainRecord>,
+
+    #[account(
+        seeds = [b"did", domain_account.key().as_ref()],
+        bump = did_record.bump
+    )]
+    pub did_record: Account<'info, DidRecord>,
+}
+
+/// Account context for removing a DID document
+/// 
+/// # Accounts
+/// * `domain_account` - Domain the DID belongs to (owned by authority)
+/// * `did_record` - PDA holding DID data (closed, rent to authority)
+/// * `authority` - Domain owner (signer)
+/// * `governance` - Governance PDA (empty unless `set_governance` was called)
+/// * `lock` - Lock PDA (empty unless `lock_domain` was called)
+/// * `timelock` - Update timelock PDA (empty unless `set_update_delay`
+///   was called)
+/// * `config` - Singleton config PDA (pause flag)
+#[derive(Accounts)]
+pub struct RemoveDidDocument<'info> {
+    #[account(has_one = authority @ DnsError::Unauthorized)]
+    pub domain_account: Account<'info, DomainRecord>,
+
+    #[account(
+        mut,
+        close = authority,
+        seeds = [b"did", domain_account.key().as_ref()],
+        bump = did_record.bump
+    )]
+    pub did_record: Account<'info, DidRecord>,
+
+    /// CHECK: Governance PDA, empty unless set; read by `require_governance`
+    #[account(seeds = [b"governance", domain_account.key().as_ref()], bump)]
+    pub governance: UncheckedAccount<'info>,
+
+    /// CHECK: Lock PDA, empty unless locked; read by `require_unlocked`
+    #[account(seeds = [b"lock", domain_account.key().as_ref()], bump)]
+    pub lock: UncheckedAccount<'info>,
+
+    /// CHECK: Timelock PDA, empty unless set; read by `update_delay`
+    #[account(seeds = [b"timelock", domain_account.key().as_ref()], bump)]
+    pub timelock: UncheckedAccount<'info>,
+
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    #[account(seeds = [b"config"], bump = config.bump)]
+    pub config: Box<Account<'info, ProgramConfig>>,
+}
+
+/// Account context for creating the program config
+/// 
+/// # Accounts
+/// * `config` - Singleton config PDA (init)
+/// * `admin` - Program upgrade authority (signer, pays rent)
+/// * `program` - This program, used to locate its program data account
+/// * `program_data` - Upgradeable loader data holding the upgrade authority
+/// 
+/// # PDA Derivation
+/// - Seeds: [b"config"]
+#[derive(Accounts)]
+pub struct InitializeConfig<'info> {
+    #[account(
+        init,
+        payer = admin,
+        space = 8 + ProgramConfig::INIT_SPACE,
+        seeds = [b"config"],
+        bump
+    )]
+    pub config: Account<'info, ProgramConfig>,
+
+    #[account(mut)]
+    pub admin: Signer<'info>,
+
+    #[account(constraint = program.programdata_address()? == Some(program_data.key()))]
+    pub program: Program<'info, program::NeuraDns>,
+
+    #[account(
+        constraint = program_data.upgrade_authority_address == Some(admin.key()) @ DnsError::NotAdmin
+    )]
+    pub program_data: Account<'info, ProgramData>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// Account context for admin config changes
+/// 
+/// # Accounts
+/// * `config` - Singleton config PDA (mutable)
+/// * `admin` - Current config admin (signer)
+#[derive(Accounts)]
+pub struct UpdateConfig<'info> {
+    #[account(
+        mut,
+        seeds = [b"config"],
+        bump = config.bump,
+        has_one = admin @ DnsError::NotAdmin
+    )]
+    pub config: Account<'info, ProgramConfig>,
+
+    pub admin: Signer<'info>,
+}
+
+/// Account context for accepting the config admin role
+/// 
+/// # Accounts
+/// * `config` - Singleton config PDA (mutable)
+/// * `new_admin` - Nominated admin (signer)
+#[derive(Accounts)]
+pub struct AcceptAdmin<'info> {
+    #[account(
+        mut,
+        seeds = [b"config"],
+        bump = config.bump,
+        constraint = config.pending_admin == Some(new_admin.key()) @ DnsError::NotPendingAdmin
+    )]
+    pub config: Account<'info, ProgramConfig>,
+
+    pub new_admin: Signer<'info>,
+}
+
+/// Account context for creating a TLD
+/// 
+/// # Accounts
+/// * `config` - Singleton config PDA
+/// * `tld` - TLD PDA (init)
+/// * `admin` - Config admin (signer, pays rent)
+/// 
+/// # PDA Derivation
+/// - Seeds: [b"tld", sha256(tld)], hashed like domain seeds so any
+///   label length fits
+#[derive(Accounts)]
+#[instruction(tld: String)]
+pub struct RegisterTld<'info> {
+    #[account(seeds = [b"config"], bump = config.bump, has_one = admin @ DnsError::NotAdmin)]
+    pub config: Account<'info, ProgramConfig>,
+
+    #[account(
+        init,
+        payer = admin,
+        space = 8 + TldRecord::INIT_SPACE,
+        seeds = [b"tld", &domain_seed(&tld)],
+        bump
+    )]
+    pub tld: Account<'info, TldRecord>,
+
+    #[account(mut)]
+    pub admin: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// Account context for TLD owner changes
+/// 
+/// # Accounts
+/// * `tld` - TLD PDA (mutable)
+/// * `owner` - Current TLD owner (signer)
+#[derive(Accounts)]
+pub struct UpdateTld<'info> {
+    #[account(mut, has_one = owner @ DnsError::Unauthorized)]
+    pub tld: Account<'info, TldRecord>,
+
+    pub owner: Signer<'info>,
+}
+
+/// Account context for admin changes to a TLD's terms
+/// 
+/// # Accounts
+/// * `config` - Singleton config PDA
+/// * `tld` - TLD PDA (mutable)
+/// * `admin` - Config admin (signer)
+#[derive(Accounts)]
+pub struct UpdateTldTerms<'info> {
+    #[account(seeds = [b"config"], bump = config.bump, has_one = admin @ DnsError::NotAdmin)]
+    pub config: Account<'info, ProgramConfig>,
+
+    #[account(mut)]
+    pub tld: Account<'info, TldRecord>,
+
+    pub admin: Signer<'info>,
+}
+
+/// Account context for resizing an older config
+/// 
+/// # Accounts
+/// * `config` - Singleton config PDA, read raw (mutable)
+/// * `admin` - Config admin, checked against the raw data (signer, pays rent)
+#[derive(Accounts)]
+pub struct ResizeConfig<'info> {
+    /// CHECK: Discriminator and admin are checked in the handler
+    #[account(mut, seeds = [b"config"], bump, owner = crate::ID)]
+    pub config: UncheckedAccount<'info>,
+
+    #[account(mut)]
+    pub admin: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// Account context for requesting external verification
+/// 
+/// # Accounts
+/// * `domain_account` - Domain to verify (owned by authority)
+/// * `verification_request` - PDA holding the pending challenge (init)
+/// * `authority` - Domain owner (signer, pays rent)
+/// 
+/// # PDA Derivation
+/// - Seeds: [b"verification", domain_account.key()]
+/// - One open request per domain
+#[derive(Accounts)]
+pub struct RequestExternalVerification<'info> {
+    #[account(has_one = authority @ DnsError::Unauthorized)]
+    pub domain_account: Account<'info, DomainRecord>,
+
+    #[account(
+        init,
+        payer = authority,
+        space = 8 + VerificationRequest::INIT_SPACE,
+        seeds = [b"verification", domain_account.key().as_ref()],
+        bump
+    )]
+    pub verification_request: Account<'info, VerificationRequest>,
+
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// Account context for confirming external verification
+/// 
+/// # Accounts
+/// * `config` - Program config naming the verifier
+/// * `verifier` - Verification oracle (signer)
+/// * `domain_account` - Domain being verified (mutable)
+/// * `verification_request` - Pending challenge (closed, rent to requester)
+/// * `requester` - Wallet that opened the request (receives rent)
+#[derive(Accounts)]
+pub struct ConfirmExternalVerification<'info> {
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump,
+        has_one = verifier @ DnsError::NotVerifier
+    )]
+    pub config: Account<'info, ProgramConfig>,
+
+    pub verifier: Signer<'info>,
+
+    #[account(
+        mut,
+        constraint = domain_account.authority == verification_request.authority @ DnsError::StaleVerification
+    )]
+    pub domain_account: Account<'info, DomainRecord>,
+
+    #[account(
+        mut,
+        close = requester,
+        seeds = [b"verification", domain_account.key().as_ref()],
+        bump = verification_request.bump,
+        constraint = verification_request.authority == requester.key() @ DnsError::StaleVerification
+    )]
+    pub verification_request: Account<'info, VerificationRequest>,
+
+    /// CHECK: Receives the request rent; must match the stored requester
+    #[account(mut)]
+    pub requester: UncheckedAccount<'info>,
+}
+
+/// Account context for revoking external verification
+/// 
+/// # Accounts
+/// * `config` - Program config naming the verifier
+/// * `verifier` - Verification oracle (signer)
+/// * `domain_account` - Domain losing its badge (mutable)
+#[derive(Accounts)]
+pub struct RevokeExternalVerification<'info> {
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump,
+        has_one = verifier @ DnsError::NotVerifier
+    )]
+    pub config: Account<'info, ProgramConfig>,
+
+    pub verifier: Signer<'info>,
+
+    #[account(mut)]
+    pub domain_account: Account<'info, DomainRecord>,
+}
+
+/// Account context for publishing an OpenPGP key
+/// 
+/// # Accounts
+/// * `domain_account` - Domain the email address belongs to (owned by authority)
+/// * `key_record` - PDA holding the key (created on first use)
+/// * `authority` - Domain owner (signer, pays rent)
+/// * `governance` - Governance PDA (empty unless `set_governance` was called)
+/// * `lock` - Lock PDA (empty unless `lock_domain` was called)
+/// * `timelock` - Update timelock PDA (empty unless `set_update_delay`
+///   was called)
+/// * `config` - Singleton config PDA (pause flag)
+/// 
+/// # PDA Derivation
+/// - Seeds: [b"openpgpkey", domain_account.key(), sha256(localpart)[..28]]
+#[derive(Accounts)]
+#[instruction(localpart: String)]
+pub struct SetOpenPgpKey<'info> {
+    #[account(has_one = authority @ DnsError::Unauthorized)]
+    pub domain_account: Account<'info, DomainRecord>,
+
+    #[account(
+        init_if_needed,
+        payer = authority,
+        space = 8 + OpenPgpKeyRecord::INIT_SPACE,
+        seeds = [b"openpgpkey", domain_account.key().as_ref(), &openpgpkey_hash(&localpart)],
+        bump
+    )]
+    pub key_record: Account<'info, OpenPgpKeyRecord>,
+
+    /// CHECK: Governance PDA, empty unless set; read by `require_governance`
+    #[account(seeds = [b"governance", domain_account.key().as_ref()], bump)]
+    pub governance: UncheckedAccount<'info>,
+
+    /// CHECK: Lock PDA, empty unless locked; read by `require_unlocked`
+    #[account(seeds = [b"lock", domain_account.key().as_ref()], bump)]
+    pub lock: UncheckedAccount<'info>,
+
+    /// CHECK: Timelock PDA, empty unless set; read by `update_delay`
+    #[account(seeds = [b"timelock", domain_account.key().as_ref()], bump)]
+    pub timelock: UncheckedAccount<'info>,
+
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+
+    #[account(seeds = [b"config"], bump = config.bump)]
+    pub config: Box<Account<'info, ProgramConfig>>,
+}
+
+/// Account context for OpenPGP key lookup
+/// 
+/// # Accounts
+/// * `domain_account` - Domain the email address belongs to (read-only)
+/// * `key_record` - PDA holding the key (read-only)
+#[derive(Accounts)]
+#[instruction(localpart: String)]
+pub struct ResolveOpenPgpKey<'info> {
+    pub domain_account: Account<'info, DomainRecord>,
+
+    #[account(
+        seeds = [b"openpgpkey", domain_account.key().as_ref(), &openpgpkey_hash(&localpart)],
+        bump = key_record.bump
+    )]
+    pub key_record: Account<'info, OpenPgpKeyRecord>,
+}
+
+/// Account context for removing an OpenPGP key
+/// 
+/// # Accounts
+/// * `domain_account` - Domain the email address belongs to (owned by authority)
+/// * `key_record` - PDA holding the key (closed, rent to authority)
+/// * `authority` - Domain owner (signer)
+/// * `governance` - Governance PDA (empty unless `set_governance` was called)
+/// * `lock` - Lock PDA (empty unless `lock_domain` was called)
+/// * `timelock` - Update timelock PDA (empty unless `set_update_delay`
+///   was called)
+/// * `config` - Singleton config PDA (pause flag)
+#[derive(Accounts)]
+#[instruction(localpart: String)]
+pub struct RemoveOpenPgpKey<'info> {
+    #[account(has_one = authority @ DnsError::Unauthorized)]
+    pub domain_account: Account<'info, DomainRecord>,
+
+    #[account(
+        mut,
+        close = authority,
+        seeds = [b"openpgpkey", domain_account.key().as_ref(), &openpgpkey_hash(&localpart)],
+        bump = key_record.bump
+    )]
+    pub key_record: Account<'info, OpenPgpKeyRecord>,
+
+    /// CHECK: Governance PDA, empty unless set; read by `require_governance`
+    #[account(seeds = [b"governance", domain_account.key().as_ref()], bump)]
+    pub governance: UncheckedAccount<'info>,
+
+    /// CHECK: Lock PDA, empty unless locked; read by `require_unlocked`
+    #[account(seeds = [b"lock", domain_account.key().as_ref()], bump)]
+    pub lock: UncheckedAccount<'info>,
+
+    /// CHECK: Timelock PDA, empty unless set; read by `update_delay`
+    #[account(seeds = [b"timelock", domain_account.key().as_ref()], bump)]
+    pub timelock: UncheckedAccount<'info>,
+
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    #[account(seeds = [b"config"], bump = config.bump)]
+    pub config: Box<Account<'info, ProgramConfig>>,
+}
+
+/// Account context for adding an SSHFP fingerprint
+/// 
+/// # Accounts
+/// * `domain_account` - Domain whose host keys are published (owned by authority)
+/// * `sshfp_records` - PDA holding the domain's fingerprints (created on first use)
+/// * `authority` - Domain owner (signer, pays rent)
+/// * `governance` - Governance PDA (empty unless `set_governance` was called)
+/// * `lock` - Lock PDA (empty unless `lock_domain` was called)
+/// * `timelock` - Update timelock PDA (empty unless `set_update_delay`
+///   was called)
+/// * `config` - Singleton config PDA (pause flag)
+/// 
+/// # PDA Derivation
+/// - Seeds: [b"sshfp", domain_account.key()]
+#[derive(Accounts)]
+pub struct SetSshfp<'info> {
+    #[account(has_one = authority @ DnsError::Unauthorized)]
+    pub domain_account: Account<'info, DomainRecord>,
+
+    #[account(
+        init_if_needed,
+        payer = authority,
+        space = 8 + SshfpRecords::INIT_SPACE,
+        seeds = [b"sshfp", domain_account.key().as_ref()],
+        bump
+    )]
+    pub sshfp_records: Account<'info, SshfpRecords>,
+
+    /// CHECK: Governance PDA, empty unless set; read by `require_governance`
+    #[account(seeds = [b"governance", domain_account.key().as_ref()], bump)]
+    pub governance: UncheckedAccount<'info>,
+
+    /// CHECK: Lock PDA, empty unless locked; read by `require_unlocked`
+    #[account(seeds = [b"lock", domain_account.key().as_ref()], bump)]
+    pub lock: UncheckedAccount<'info>,
+
+    /// CHECK: Timelock PDA, empty unless set; read by `update_delay`
+    #[account(seeds = [b"timelock", domain_account.key().as_ref()], bump)]
+    pub timelock: UncheckedAccount<'info>,
+
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+
+    #[account(seeds = [b"config"], bump = config.bump)]
+    pub config: Box<Account<'info, ProgramConfig>>,
+}
+
+/// Account context for removing an SSHFP fingerprint
+/// 
+/// # Accounts
+/// * `domain_account` - Domain whose host keys are published (owned by authority)
+/// * `sshfp_records` - PDA holding the domain's fingerprints (mutable)
+/// * `authority` - Domain owner (signer)
+/// * `governance` - Governance PDA (empty unless `set_governance` was called)
+/// * `lock` - Lock PDA (empty unless `lock_domain` was called)
+/// * `timelock` - Update timelock PDA (empty unless `set_update_delay`
+///   was called)
+/// * `config` - Singleton config PDA (pause flag)
+#[derive(Accounts)]
+pub struct RemoveSshfp<'info> {
+    #[account(has_one = authority @ DnsError::Unauthorized)]
+    pub domain_account: Account<'info, DomainRecord>,
+
+    #[account(
+        mut,
+        seeds = [b"sshfp", domain_account.key().as_ref()],
+        bump = sshfp_records.bump
+    )]
+    pub sshfp_records: Account<'info, SshfpRecords>,
+
+    /// CHECK: Governance PDA, empty unless set; read by `require_governance`
+    #[account(seeds = [b"governance", domain_account.key().as_ref()], bump)]
+    pub governance: UncheckedAccount<'info>,
+
+    /// CHECK: Lock PDA, empty unless locked; read by `require_unlocked`
+    #[account(seeds = [b"lock", domain_account.key().as_ref()], bump)]
+    pub lock: UncheckedAccount<'info>,
+
+    /// CHECK: Timelock PDA, empty unless set; read by `update_delay`
+    #[account(seeds = [b"timelock", domain_account.key().as_ref()], bump)]
+    pub timelock: UncheckedAccount<'info>,
+
+    pub authority: Signer<'info>,
+
+    #[account(seeds = [b"config"], bump = config.bump)]
+    pub config: Box<Account<'info, ProgramConfig>>,
+}
+
+/// Account context for SSHFP lookup
+/// 
+/// # Accounts
+/// * `domain_account` - Domain whose host keys are published (read-only)
+/// * `sshfp_records` - PDA holding the domain's fingerprints (read-only)
+#[derive(Accounts)]
+pub struct ResolveSshfp<'info> {
+    pub domain_account: Account<'info, DomainRecord>,
+
+    #[account(
+        seeds = [b"sshfp", domain_account.key().as_ref()],
+        bump = sshfp_records.bump
+    )]
+    pub sshfp_records: Account<'info, SshfpRecords>,
+}
+
+/// Account context for adding a DS or DNSKEY record
+/// 
+/// # Accounts
+/// * `domain_account` - Domain whose zone is signed (owned by authority)
+/// * `dnssec_records` - PDA holding the domain's DNSSEC records (created on first use)
+/// * `authority` - Domain owner (signer, pays rent)
+/// * `governance` - Governance PDA (empty unless `set_governance` was called)
+/// * `lock` - Lock PDA (empty unless `lock_domain` was called)
+/// * `timelock` - Update timelock PDA (empty unless `set_update_delay`
+///   was called)
+/// * `config` - Singleton config PDA (pause flag)
+/// 
+/// # PDA Derivation
+/// - Seeds: [b"dnssec", domain_account.key()]
+#[derive(Accounts)]
+pub struct SetDnssec<'info> {
+    #[account(has_one = authority @ DnsError::Unauthorized)]
+    pub domain_account: Account<'info, DomainRecord>,
+
+    #[account(
+        init_if_needed,
+        payer = authority,
+        space = 8 + DnssecRecords::INIT_SPACE,
+        seeds = [b"dnssec", domain_account.key().as_ref()],
+        bump
+    )]
+    pub dnssec_records: Account<'info, DnssecRecords>,
+
+    /// CHECK: Governance PDA, empty unless set; read by `require_governance`
+    #[account(seeds = [b"governance", domain_account.key().as_ref()], bump)]
+    pub governance: UncheckedAccount<'info>,
+
+    /// CHECK: Lock PDA, empty unless locked; read by `require_unlocked`
+    #[account(seeds = [b"lock", domain_account.key().as_ref()], bump)]
+    pub lock: UncheckedAccount<'info>,
+
+    /// CHECK: Timelock PDA, empty unless set; read by `update_delay`
+    #[account(seeds = [b"timelock", domain_account.key().as_ref()], bump)]
+    pub timelock: UncheckedAccount<'info>,
+
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+
+    #[account(seeds = [b"config"], bump = config.bump)]
+    pub config: Box<Account<'info, ProgramConfig>>,
+}
+
+/// Account context for removing a DS or DNSKEY record
+/// 
+/// # Accounts
+/// * `domain_account` - Domain whose zone is signed (owned by authority)
+/// * `dnssec_records` - PDA holding the domain's DNSSEC records (mutable)
+/// * `authority` - Domain owner (signer)
+/// * `governance` - Governance PDA (empty unless `set_governance` was called)
+/// * `lock` - Lock PDA (empty unless `lock_domain` was called)
+/// * `timelock` - Update timelock PDA (empty unless `set_update_delay`
+///   was called)
+/// * `config` - Singleton config PDA (pause flag)
+#[derive(Accounts)]
+pub struct RemoveDnssec<'info> {
+    #[account(has_one = authority @ DnsError::Unauthorized)]
+    pub domain_account: Account<'info, DomainRecord>,
+
+    #[account(
+        mut,
+        seeds = [b"dnssec", domain_account.key().as_ref()],
+        bump = dnssec_records.bump
+    )]
+    pub dnssec_records: Account<'info, DnssecRecords>,
+
+    /// CHECK: Governance PDA, empty unless set; read by `require_governance`
+    #[account(seeds = [b"governance", domain_account.key().as_ref()], bump)]
+    pub governance: UncheckedAccount<'info>,
+
+    /// CHECK: Lock PDA, empty unless locked; read by `require_unlocked`
+    #[account(seeds = [b"lock", domain_account.key().as_ref()], bump)]
+    pub lock: UncheckedAccount<'info>,
+
+    /// CHECK: Timelock PDA, empty unless set; read by `update_delay`
+    #[account(seeds = [b"timelock", domain_account.key().as_ref()], bump)]
+    pub timelock: UncheckedAccount<'info>,
+
+    pub authority: Signer<'info>,
+
+    #[account(seeds = [b"config"], bump = config.bump)]
+    pub config: Box<Account<'info, ProgramConfig>>,
+}
+
+/// Account context for DNSSEC lookup
+/// 
+/// # Accounts
+/// * `domain_account` - Domain whose zone is signed (read-only)
+/// * `dnssec_records` - PDA holding the domain's DNSSEC records (read-only)
+#[derive(Accounts)]
+pub struct ResolveDnssec<'info> {
+    pub domain_account: Account<'info, DomainRecord>,
+
+    #[account(
+        seeds = [b"dnssec", domain_account.key().as_ref()],
+        bump = dnssec_records.bump
+    )]
+    pub dnssec_records: Account<'info, DnssecRecords>,
+}
+
+/// Account context for adding an attestation
+/// 
+/// # Accounts
+/// * `domain_account` - Domain being attested (read-only)
+/// * `attestation` - PDA holding the claim (init)
+/// * `attester` - Third party making the claim (signer, pays rent)
+/// 
+/// # PDA Derivation
+/// - Seeds: [b"attestation", domain_account.key(), attester.key()]
+/// - One live claim per attester per domain
+#[derive(Accounts)]
+pub struct AddAttestation<'info> {
+    pub domain_account: Account<'info, DomainRecord>,
+
+    #[account(
+        init,
+        payer = attester,
+        space = 8 + Attestation::INIT_SPACE,
+        seeds = [b"attestation", domain_account.key().as_ref(), attester.key().as_ref()],
+        bump
+    )]
+    pub attestation: Account<'info, Attestation>,
+
+    #[account(mut)]
+    pub attester: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// Account context for accepting an attestation
+/// 
+/// # Accounts
+/// * `domain_account` - Attested domain (owned by authority)
+/// * `attestation` - Pending claim for this domain (mutable)
+/// * `authority` - Domain owner (signer)
+#[derive(Accounts)]
+pub struct AcceptAttestation<'info> {
+    #[account(has_one = authority @ DnsError::Unauthorized)]
+    pub domain_account: Account<'info, DomainRecord>,
+
+    #[account(
+        mut,
+        seeds = [b"attestation", domain_account.key().as_ref(), attestation.attester.as_ref()],
+        bump = attestation.bump
+    )]
+    pub attestation: Account<'info, Attestation>,
+
+    pub authority: Signer<'info>,
+}
+
+/// Account context for the owner removing an attestation
+/// 
+/// # Accounts
+/// * `domain_account` - Attested domain (owned by authority)
+/// * `attestation` - Claim to remove (closed, rent to attester)
+/// * `attester` - Original attester (receives rent)
+/// * `authority` - Domain owner (signer)
+#[derive(Accounts)]
+pub struct RemoveAttestation<'info> {
+    #[account(has_one = authority @ DnsError::Unauthorized)]
+    pub domain_account: Account<'info, DomainRecord>,
+
+    #[account(
+        mut,
+        close = attester,
+        seeds = [b"attestation", domain_account.key().as_ref(), attester.key().as_ref()],
+        bump = attestation.bump
+    )]
+    pub attestation: Account<'info, Attestation>,
+
+    /// CHECK: Rent destination; bound to the attestation by its seeds
+    #[account(mut)]
+    pub attester: UncheckedAccount<'info>,
+
+    pub authority: Signer<'info>,
+}
+
+/// Account context for the attester withdrawing an attestation
+/// 
+/// # Accounts
+/// * `domain_account` - Attested domain (read-only)
+/// * `attestation` - Claim to withdraw (closed, rent to attester)
+/// * `attester` - Original attester (signer)
+#[derive(Accounts)]
+pub struct RevokeAttestation<'info> {
+    pub domain_account: Account<'info, DomainRecord>,
+
+    #[account(
+        mut,
+        close = attester,
+        seeds = [b"attestation", domain_account.key().as_ref(), attester.key().as_ref()],
+        bump = attestation.bump
+    )]
+    pub attestation: Account<'info, Attestation>,
+
+    #[account(mut)]
+    pub attester: Signer<'info>,
+}
+
+/// Account context for configuring a dynamic record
+/// 
+/// # Accounts
+/// * `domain_account` - Domain getting a dynamic record (owned by authority)
+/// * `dynamic_record` - PDA holding the live value (created on first use)
+/// * `authority` - Domain owner (signer, pays rent)
+/// * `governance` - Governance PDA (empty unless `set_governance` was called)
+/// * `lock` - Lock PDA (empty unless `lock_domain` was called)
+/// * `timelock` - Update timelock PDA (empty unless `set_update_delay`
+///   was called)
+/// * `config` - Singleton config PDA (pause flag)
+/// 
+/// # PDA Derivation
+/// - Seeds: [b"dynamic", domain_account.key()]
+#[derive(Accounts)]
+pub struct ConfigureDynamicRecord<'info> {
+    #[account(has_one = authority @ DnsError::Unauthorized)]
+    pub domain_account: Account<'info, DomainRecord>,
+
+    #[account(
+        init_if_needed,
+        payer = authority,
+        space = 8 + DynamicRecord::INIT_SPACE,
+        seeds = [b"dynamic", domain_account.key().as_ref()],
+        bump
+    )]
+    pub dynamic_record: Account<'info, DynamicRecord>,
+
+    /// CHECK: Governance PDA, empty unless set; read by `require_governance`
+    #[account(seeds = [b"governance", domain_account.key().as_ref()], bump)]
+    pub governance: UncheckedAccount<'info>,
+
+    /// CHECK: Lock PDA, empty unless locked; read by `require_unlocked`
+    #[account(seeds = [b"lock", domain_account.key().as_ref()], bump)]
+    pub lock: UncheckedAccount<'info>,
+
+    /// CHECK: Timelock PDA, empty unless set; read by `update_delay`
+    #[account(seeds = [b"timelock", domain_account.key().as_ref()], bump)]
+    pub timelock: UncheckedAccount<'info>,
+
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+
+    #[account(seeds = [b"config"], bump = config.bump)]
+    pub config: Box<Account<'info, ProgramConfig>>,
+}
+
+/// Account context for pushing a dynamic record value
+/// 
+/// # Accounts
+/// * `dynamic_record` - PDA holding the live value (mutable)
+/// * `updater` - Configured updater key (signer)
+/// * `domain_account` - Domain the record belongs to
+/// * `lock` - Lock PDA (empty unless `lock_domain` was called)
+/// * `config` - Singleton config PDA (pause flag)
+#[derive(Accounts)]
+pub struct PushDynamicRecord<'info> {
+    #[account(
+        mut,
+        has_one = updater @ DnsError::NotUpdater
+    )]
+    pub dynamic_record: Account<'info, DynamicRecord>,
+
+    pub updater: Signer<'info>,
+
+    #[account(address = dynamic_record.domain)]
+    pub domain_account: Account<'info, DomainRecord>,
+
+    /// CHECK: Lock PDA, empty unless locked; read by `require_unlocked`
+    #[account(seeds = [b"lock", domain_account.key().as_ref()], bump)]
+    pub lock: UncheckedAccount<'info>,
+
+    #[account(seeds = [b"config"], bump = config.bump)]
+    pub config: Box<Account<'info, ProgramConfig>>,
+}
+
+/// Account context for removing a dynamic record
+/// 
+/// # Accounts
+/// * `domain_account` - Domain with a dynamic record (owned by authority)
+/// * `dynamic_record` - PDA holding the live value (closed, rent to authority)
+/// * `authority` - Domain owner (signer)
+/// * `governance` - Governance PDA (empty unless `set_governance` was called)
+/// * `lock` - Lock PDA (empty unless `lock_domain` was called)
+/// * `timelock` - Update timelock PDA (empty unless `set_update_delay`
+///   was called)
+/// * `config` - Singleton config PDA (pause flag)
+#[derive(Accounts)]
+pub struct RemoveDynamicRecord<'info> {
+    #[account(has_one = authority @ DnsError::Unauthorized)]
+    pub domain_account: Account<'info, DomainRecord>,
+
+    #[account(
+        mut,
+        close = authority,
+        seeds = [b"dynamic", domain_account.key().as_ref()],
+        bump = dynamic_record.bump
+    )]
+    pub dynamic_record: Account<'info, DynamicRecord>,
+
+    /// CHECK: Governance PDA, empty unless set; read by `require_governance`
+    #[account(seeds = [b"governance", domain_account.key().as_ref()], bump)]
+    pub governance: UncheckedAccount<'info>,
+
+    /// CHECK: Lock PDA, empty unless locked; read by `require_unlocked`
+    #[account(seeds = [b"lock", domain_account.key().as_ref()], bump)]
+    pub lock: UncheckedAccount<'info>,
+
+    /// CHECK: Timelock PDA, empty unless set; read by `update_delay`
+    #[account(seeds = [b"timelock", domain_account.key().as_ref()], bump)]
+    pub timelock: UncheckedAccount<'info>,
+
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    #[account(seeds = [b"config"], bump = config.bump)]
+    pub config: Box<Account<'info, ProgramConfig>>,
+}
+
+/// Account context for migrating a legacy domain account
+/// 
+/// # Accounts
+/// * `legacy_account` - Old PDA keyed by the raw name (closed, rent to authority)
+/// * `domain_account` - New PDA keyed by the hashed name (init)
+/// * `authority` - Domain owner (signer, pays for the new account)
+/// * `system_program` - Solana system program for account creation
+/// * `namespace_index` - Index head PDA (mutable)
+/// * `index_page` - Current tail index page (mutable)
+/// 
+/// # PDA Derivation
+/// - Legacy seeds: [b"domain", domain_name.as_bytes()]
+/// - New seeds: [b"domain", sha256(domain_name)]
+#[derive(Accounts)]
+#[instruction(domain_name: String)]
+pub struct MigrateDomainAccount<'info> {
+    #[account(
+        mut,
+        close = authority,
+        has_one = authority @ DnsError::Unauthorized,
+        seeds = [b"domain", domain_name.as_bytes()],
+        bump
+    )]
+    pub legacy_account: Account<'info, DomainRecord>,
+
+    #[account(
+        init,
+        payer = authority,
+        space = DomainRecord::space_for(domain_name.len(), legacy_account.record.len()),
+        seeds = [b"domain", &domain_seed(&domain_name)],
+        bump
+    )]
+    pub domain_account: Account<'info, DomainRecord>,
+
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+
+    #[account(mut, seeds = [b"index"], bump = namespace_index.bump)]
+    pub namespace_index: Account<'info, NamespaceIndex>,
+
+    #[account(
+        mut,
+        seeds = [b"index_page", &(namespace_index.page_count - 1).to_le_bytes()],
+        bump = index_page.bump
+    )]
+    pub index_page: Account<'info, IndexPage>,
+}
+
+/// Account context for creating the namespace index
+/// 
+/// # Accounts
+/// * `namespace_index` - Index head PDA (init)
+/// * `first_page` - Page 0 (init)
+/// * `payer` - Any wallet paying rent (signer)
+/// 
+/// # PDA Derivation
+/// - Head seeds: [b"index"]
+/// - Page seeds: [b"index_page", page_number (u32 LE)]
+#[derive(Accounts)]
+pub struct InitializeIndex<'info> {
+    #[account(
+        init,
+        payer = payer,
+        space = 8 + NamespaceIndex::INIT_SPACE,
+        seeds = [b"index"],
+        bump
+    )]
+    pub namespace_index: Account<'info, NamespaceIndex>,
+
+    #[account(
+        init,
+        payer = payer,
+        space = 8 + IndexPage::INIT_SPACE,
+        seeds = [b"index_page", &0u32.to_le_bytes()],
+        bump
+    )]
+    pub first_page: Account<'info, IndexPage>,
+
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// Account context for appending an index page
+/// 
+/// # Accounts
+/// * `namespace_index` - Index head PDA (mutable)
+/// * `tail_page` - Current full tail page (mutable, gets the `next` link)
+/// * `new_page` - Next page (init)
+/// * `payer` - Any wallet paying rent (signer)
+#[derive(Accounts)]
+pub struct AppendIndexPage<'info> {
+    #[account(mut, seeds = [b"index"], bump = namespace_index.bump)]
+    pub namespace_index: Account<'info, NamespaceIndex>,
+
+    #[account(
+        mut,
+        seeds = [b"index_page", &(namespace_index.page_count - 1).to_le_bytes()],
+        bump = tail_page.bump
+    )]
+    pub tail_page: Account<'info, IndexPage>,
+
+    #[account(
+        init,
+        payer = payer,
+        space = 8 + IndexPage::INIT_SPACE,
+        seeds = [b"index_page", &namespace_index.page_count.to_le_bytes()],
+        bump
+    )]
+    pub new_page: Account<'info, IndexPage>,
+
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// Account context for creating the availability filter
+/// 
+/// # Accounts
+/// * `config` - Program config naming the admin
+/// * `availability_filter` - Zero-copy Bloom filter PDA (init)
+/// * `admin` - Config admin (signer, pays rent)
+/// 
+/// # PDA Derivation
+/// - Seeds: [b"bloom"]
+#[derive(Accounts)]
+pub struct InitializeAvailabilityFilter<'info> {
+    #[account(seeds = [b"config"], bump = config.bump, has_one = admin @ DnsError::NotAdmin)]
+    pub config: Account<'info, ProgramConfig>,
+
+    #[account(
+        init,
+        payer = admin,
+        space = 8 + std::mem::size_of::<AvailabilityFilter>(),
+        seeds = [b"bloom"],
+        bump
+    )]
+    pub availability_filter: AccountLoader<'info, AvailabilityFilter>,
+
+    #[account(mut)]
+    pub admin: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// Account context for admin filter maintenance
+/// 
+/// # Accounts
+/// * `config` - Program config naming the admin
+/// * `availability_filter` - Zero-copy Bloom filter PDA (mutable)
+/// * `admin` - Config admin (signer)
+#[derive(Accounts)]
+pub struct ManageAvailabilityFilter<'info> {
+    #[account(seeds = [b"config"], bump = config.bump, has_one = admin @ DnsError::NotAdmin)]
+    pub config: Account<'info, ProgramConfig>,
+
+    #[account(mut, seeds = [b"bloom"], bump)]
+    pub availability_filter: AccountLoader<'info, AvailabilityFilter>,
+
+    pub admin: Signer<'info>,
+}
+
+/// Account context for upgrading a domain account layout
+/// 
+/// # Accounts
+/// * `domain_account` - Domain to upgrade (reallocated to current size)
+/// * `payer` - Pays any additional rent (signer, need not be the owner)
+#[derive(Accounts)]
+pub struct UpgradeDomainAccount<'info> {
+    #[account(
+        mut,
+        realloc = DomainRecord::space_for(
+            domain_account.domain_name.len(),
+            domain_account.record.len()
+        ),
+        realloc::payer = payer,
+        realloc::zero = true
+    )]
+    pub domain_account: Account<'info, DomainRecord>,
+
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// Account context for creating the protocol stats account
+/// 
+/// # Accounts
+/// * `protocol_stats` - Stats PDA (init)
+/// * `payer` - Any wallet paying rent (signer)
+/// 
+/// # PDA Derivation
+/// - Seeds: [b"stats"]
+#[derive(Accounts)]
+pub struct InitializeStats<'info> {
+    #[account(
+        init,
+        payer = payer,
+        space = 8 + ProtocolStats::INIT_SPACE,
+        seeds = [b"stats"],
+        bump
+    )]
+    pub protocol_stats: Account<'info, ProtocolStats>,
+
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// Account context for updating a domain's record
+/// 
+/// # Accounts
+/// * `domain_account` - Domain to update (resized)
+/// * `record_history` - History ring buffer PDA (created on first update)
+/// * `operator_grant` - Operator PDA, when signing as the operator (optional)
+/// * `record_acl` - Record type ACL PDA, when signing with per-type
+///   rights (optional)
+/// * `holder_token_account` - Signer's token account holding a wrapped
+///   domain's NFT, when signing as the holder (optional)
+/// * `signer` - Domain owner, its operator, a key granted the record
+///   types, the NFT holder or the tenant (pays or receives rent difference)
+/// * `governance` - Governance PDA (empty unless `set_governance` was called)
+/// * `lock` - Lock PDA (empty unless `lock_domain` was called)
+/// * `lease` - Lease PDA (empty unless `lease_domain` was called)
+/// * `timelock` - Update timelock PDA (empty unless `set_update_delay`
+///   was called)
+/// * `config` - Singleton config PDA (pause flag)
+/// 
+/// # PDA Derivation
+/// - History seeds: [b"history", domain_account.key()]
+/// - Operator seeds: [b"operator", domain_account.key()]
+/// - ACL seeds: [b"acl", domain_account.key()]
+#[derive(Accounts)]
+#[instruction(record: String)]
+pub struct UpdateRecord<'info> {
+    #[account(
+        mut,
+        realloc = DomainRecord::space_for(domain_account.domain_name.len(), record.len()),
+        realloc::payer = signer,
+        realloc::zero = true
+    )]
+    pub domain_account: Account<'info, DomainRecord>,
+
+    #[account(
+        init_if_needed,
+        payer = signer,
+        space = 8 + RecordHistory::INIT_SPACE,
+        seeds = [b"history", domain_account.key().as_ref()],
+        bump
+    )]
+    pub record_history: Account<'info, RecordHistory>,
+
+    #[account(
+        seeds = [b"operator", domain_account.key().as_ref()],
+        bump = operator_grant.bump
+    )]
+    pub operator_grant: Option<Account<'info, OperatorGrant>>,
+
+    #[account(
+        seeds = [b"acl", domain_account.key().as_ref()],
+        bump = record_acl.bump
+    )]
+    pub record_acl: Option<Account<'info, RecordAcl>>,
+
+    pub holder_token_account: Option<Box<InterfaceAccount<'info, TokenAccount>>>,
+
+    /// CHECK: Governance PDA, empty unless set; read by `require_governance`
+    #[account(seeds = [b"governance", domain_account.key().as_ref()], bump)]
+    pub governance: UncheckedAccount<'info>,
+
+    /// CHECK: Lock PDA, empty unless locked; read by `require_unlocked`
+    #[account(seeds = [b"lock", domain_account.key().as_ref()], bump)]
+    pub lock: UncheckedAccount<'info>,
+
+    /// CHECK: Lease PDA, empty unless leased; read by `active_lease`
+    #[account(seeds = [b"lease", domain_account.key().as_ref()], bump)]
+    pub lease: UncheckedAccount<'info>,
+
+    /// CHECK: Timelock PDA, empty unless set; read by `update_delay`
+    #[account(seeds = [b"timelock", domain_account.key().as_ref()], bump)]
+    pub timelock: UncheckedAccount<'info>,
+
+    #[account(mut)]
+    pub signer: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+
+    #[account(seeds = [b"config"], bump = config.bump)]
+    pub config: Box<Account<'info, ProgramConfig>>,
+}
+
+/// Account context for reading record history
+/// 
+/// # Accounts
+/// * `domain` - Domain address the history belongs to (may be closed)
+/// * `record_history` - History PDA (read-only)
+#[derive(Accounts)]
+pub struct GetHistory<'info> {
+    /// CHECK: Only used as a PDA seed; the domain may since have been unregistered
+    pub domain: UncheckedAccount<'info>,
+
+    #[account(
+        seeds = [b"history", domain.key().as_ref()],
+        bump = record_history.bump
+    )]
+    pub record_history: Account<'info, RecordHistory>,
+}
+
+/// Account context for setting a domain's operator
+/// 
+/// # Accounts
+/// * `domain_account` - Domain (owned by authority)
+/// * `operator_grant` - Operator PDA (created or overwritten)
+/// * `authority` - Domain owner (signer, pays rent)
+/// 
+/// # PDA Derivation
+/// - Seeds: [b"operator", domain_account.key()]
+#[derive(Accounts)]
+pub struct SetOperator<'info> {
+    #[account(has_one = authority @ DnsError::Unauthorized)]
+    pub domain_account: Account<'info, DomainRecord>,
+
+    #[account(
+        init_if_needed,
+        payer = authority,
+        space = 8 + OperatorGrant::INIT_SPACE,
+        seeds = [b"operator", domain_account.key().as_ref()],
+        bump
+    )]
+    pub operator_grant: Account<'info, OperatorGrant>,
+
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// Account context for revoking a domain's operator
+/// 
+/// # Accounts
+/// * `domain_account` - Domain (owned by authority)
+/// * `operator_grant` - Operator PDA (closed)
+/// * `authority` - Domain owner (signer, receives rent)
+#[derive(Accounts)]
+pub struct RevokeOperator<'info> {
+    #[account(has_one = authority @ DnsError::Unauthorized)]
+    pub domain_account: Account<'info, DomainRecord>,
+
+    #[account(
+        mut,
+        seeds = [b"operator", domain_account.key().as_ref()],
+        bump = operator_grant.bump,
+        close = authority
+    )]
+    pub operator_grant: Account<'info, OperatorGrant>,
+
+    #[account(mut)]
+    pub authority: Signer<'info>,
+}
+
+/// Account context for granting a key per-type rights
+/// 
+/// # Accounts
+/// * `domain_account` - Domain (owned by authority)
+/// * `record_acl` - Record type ACL PDA (created on first grant)
+/// * `authority` - Domain owner (signer, pays rent)
+/// 
+/// # PDA Derivation
+/// - Seeds: [b"acl", domain_account.key()]
+#[derive(Accounts)]
+pub struct GrantRecordTypes<'info> {
+    #[account(has_one = authority @ DnsError::Unauthorized)]
+    pub domain_account: Account<'info, DomainRecord>,
+
+    #[account(
+        init_if_needed,
+        payer = authority,
+        space = 8 + RecordAcl::INIT_SPACE,
+        seeds = [b"acl", domain_account.key().as_ref()],
+        bump
+    )]
+    pub record_acl: Account<'info, RecordAcl>,
+
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// Account context for revoking a key's per-type rights
+/// 
+/// # Accounts
+/// * `domain_account` - Domain (owned by authority)
+/// * `record_acl` - Record type ACL PDA (mutable)
+/// * `authority` - Domain owner (signer)
+#[derive(Accounts)]
+pub struct RevokeRecordTypes<'info> {
+    #[account(has_one = authority @ DnsError::Unauthorized)]
+    pub domain_account: Account<'info, DomainRecord>,
+
+    #[account(
+        mut,
+        seeds = [b"acl", domain_account.key().as_ref()],
+        bump = record_acl.bump
+    )]
+    pub record_acl: Account<'info, RecordAcl>,
+
+    pub authority: Signer<'info>,
+}
+
+/// Account context for naming guardians
+/// 
+/// # Accounts
+/// * `domain_account` - Domain (owned by authority)
+/// * `guardian_set` - Guardian PDA (created or overwritten)
+/// * `governance` - Governance PDA (empty unless `set_governance` was called)
+/// * `lock` - Lock PDA (empty unless `lock_domain` was called)
+/// * `authority` - Domain owner (signer, pays rent)
+/// * `remaining_accounts` - Signing governance members, when governed
+/// 
+/// # PDA Derivation
+/// - Seeds: [b"guardians", domain_account.key()]
+#[derive(Accounts)]
+pub struct SetGuardians<'info> {
+    #[account(has_one = authority @ DnsError::Unauthorized)]
+    pub domain_account: Account<'info, DomainRecord>,
+
+    #[account(
+        init_if_needed,
+        payer = authority,
+        space = 8 + GuardianSet::INIT_SPACE,
+        seeds = [b"guardians", domain_account.key().as_ref()],
+        bump
+    )]
+    pub guardian_set: Account<'info, GuardianSet>,
+
+    /// CHECK: Governance PDA, empty unless set; read by `require_governance`
+    #[account(seeds = [b"governance", domain_account.key().as_ref()], bump)]
+    pub governance: UncheckedAccount<'info>,
+
+    /// CHECK: Lock PDA, empty unless locked; read by `require_unlocked`
+    #[account(seeds = [b"lock", domain_account.key().as_ref()], bump)]
+    pub lock: UncheckedAccount<'info>,
+
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// Account context for removing guardians
+/// 
+/// # Accounts
+/// * `domain_account` - Domain (owned by authority)
+/// * `guardian_set` - Guardian PDA (closed)
+/// * `authority` - Domain owner (signer, receives rent)
+#[derive(Accounts)]
+pub struct RemoveGuardians<'info> {
+    #[account(has_one = authority @ DnsError::Unauthorized)]
+    pub domain_account: Account<'info, DomainRecord>,
+
+    #[account(
+        mut,
+        seeds = [b"guardians", domain_account.key().as_ref()],
+        bump = guardian_set.bump,
+        close = authority
+    )]
+    pub guardian_set: Account<'info, GuardianSet>,
+
+    #[account(mut)]
+    pub authority: Signer<'info>,
+}
+
+/// Account context for starting a recovery
+/// 
+/// # Accounts
+/// * `domain_account` - Domain being recovered (read-only)
+/// * `guardian_set` - Guardian PDA (read-only)
+/// * `recovery` - Recovery request PDA (created, or reused once stale)
+/// * `initiator` - Anyone, usually a guardian (signer, pays rent)
+/// * `remaining_accounts` - Signing guardians
+/// 
+/// # PDA Derivation
+/// - Seeds: [b"recovery", domain_account.key()]
+#[derive(Accounts)]
+pub struct StartRecovery<'info> {
+    pub domain_account: Account<'info, DomainRecord>,
+
+    #[account(
+        seeds = [b"guardians", domain_account.key().as_ref()],
+        bump = guardian_set.bump
+    )]
+    pub guardian_set: Account<'info, GuardianSet>,
+
+    #[account(
+        init_if_needed,
+        payer = initiator,
+        space = 8 + RecoveryRequest::INIT_SPACE,
+        seeds = [b"recovery", domain_account.key().as_ref()],
+        bump
+    )]
+    pub recovery: Account<'info, RecoveryRequest>,
+
+    #[account(mut)]
+    pub initiator: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// Account context for vetoing a recovery
+/// 
+/// # Accounts
+/// * `domain_account` - Domain (owned by authority)
+/// * `recovery` - Recovery request PDA (closed)
+/// * `initiator` - Whoever started the recovery (receives rent)
+/// * `authority` - Domain owner (signer)
+#[derive(Accounts)]
+pub struct VetoRecovery<'info> {
+    #[account(has_one = authority @ DnsError::Unauthorized)]
+    pub domain_account: Account<'info, DomainRecord>,
+
+    #[account(
+        mut,
+        seeds = [b"recovery", domain_account.key().as_ref()],
+        bump = recovery.bump,
+        close = initiator
+    )]
+    pub recovery: Account<'info, RecoveryRequest>,
+
+    /// CHECK: Rent recipient, checked against the request
+    #[account(mut, address = recovery.initiated_by @ DnsError::Unauthorized)]
+    pub initiator: UncheckedAccount<'info>,
+
+    pub authority: Signer<'info>,
+}
+
+/// Account context for completing a recovery
+/// 
+/// # Accounts
+/// * `domain_account` - Domain being recovered (mutable)
+/// * `recovery` - Recovery request PDA (closed)
+/// * `initiator` - Whoever started the recovery (receives rent)
+/// * `config` - Singleton config PDA (pause flag)
+#[derive(Accounts)]
+pub struct CompleteRecovery<'info> {
+    #[account(mut)]
+    pub domain_account: Account<'info, DomainRecord>,
+
+    #[account(
+        mut,
+        seeds = [b"recovery", domain_account.key().as_ref()],
+        bump = recovery.bump,
+        close = initiator
+    )]
+    pub recovery: Account<'info, RecoveryRequest>,
+
+    /// CHECK: Rent recipient, checked against the request
+    #[account(mut, address = recovery.initiated_by @ DnsError::Unauthorized)]
+    pub initiator: UncheckedAccount<'info>,
+
+    #[account(seeds = [b"config"], bump = config.bump)]
+    pub config: Box<Account<'info, ProgramConfig>>,
+}
+
+/// Account context for naming a beneficiary
+/// 
+/// # Accounts
+/// * `domain_account` - Domain (owned by authority)
+/// * `inheritance` - Inheritance PDA (created or overwritten)
+/// * `governance` - Governance PDA (empty unless `set_governance` was called)
+/// * `lock` - Lock PDA (empty unless `lock_domain` was called)
+/// * `authority` - Domain owner (signer, pays rent)
+/// * `remaining_accounts` - Signing governance members, when governed
+/// 
+/// # PDA Derivation
+/// - Seeds: [b"inheritance", domain_account.key()]
+#[derive(Accounts)]
+pub struct SetBeneficiary<'info> {
+    #[account(has_one = authority @ DnsError::Unauthorized)]
+    pub domain_account: Account<'info, DomainRecord>,
+
+    #[account(
+        init_if_needed,
+        payer = authority,
+        space = 8 + Inheritance::INIT_SPACE,
+        seeds = [b"inheritance", domain_account.key().as_ref()],
+        bump
+    )]
+    pub inheritance: Account<'info, Inheritance>,
+
+    /// CHECK: Governance PDA, empty unless set; read by `require_governance`
+    #[account(seeds = [b"governance", domain_account.key().as_ref()], bump)]
+    pub governance: UncheckedAccount<'info>,
+
+    /// CHECK: Lock PDA, empty unless locked; read by `require_unlocked`
+    #[account(seeds = [b"lock", domain_account.key().as_ref()], bump)]
+    pub lock: UncheckedAccount<'info>,
+
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// Account context for a heartbeat
+/// 
+/// # Accounts
+/// * `domain_account` - Domain (owned by authority)
+/// * `inheritance` - Inheritance PDA (set by this owner, mutable)
+/// * `authority` - Domain owner (signer)
+#[derive(Accounts)]
+pub struct Heartbeat<'info> {
+    #[account(has_one = authority @ DnsError::Unauthorized)]
+    pub domain_account: Account<'info, DomainRecord>,
+
+    #[account(
+        mut,
+        seeds = [b"inheritance", domain_account.key().as_ref()],
+        bump = inheritance.bump,
+        constraint = inheritance.applies_to(&domain_account) @ DnsError::Unauthorized
+    )]
+    pub inheritance: Account<'info, Inheritance>,
+
+    pub authority: Signer<'info>,
+}
+
+/// Account context for removing a beneficiary
+/// 
+/// # Accounts
+/// * `domain_account` - Domain (owned by authority)
+/// * `inheritance` - Inheritance PDA (closed)
+/// * `authority` - Domain owner (signer, receives rent)
+#[derive(Accounts)]
+pub struct RemoveBeneficiary<'info> {
+    #[account(has_one = authority @ DnsError::Unauthorized)]
+    pub domain_account: Account<'info, DomainRecord>,
+
+    #[account(
+        mut,
+        seeds = [b"inheritance", domain_account.key().as_ref()],
+        bump = inheritance.bump,
+        close = authority
+    )]
+    pub inheritance: Account<'info, Inheritance>,
+
+    #[account(mut)]
+    pub authority: Signer<'info>,
+}
+
+/// Account context for claiming an inheritance
+/// 
+/// # Accounts
+/// * `domain_account` - Domain being inherited (mutable)
+/// * `inheritance` - Inheritance PDA naming the beneficiary (closed)
+/// * `beneficiary` - Named beneficiary (signer, receives rent)
+/// * `config` - Singleton config PDA (pause flag)
+#[derive(Accounts)]
+pub struct ClaimInheritance<'info> {
+    #[account(mut)]
+    pub domain_account: Account<'info, DomainRecord>,
+
+    #[account(
+        mut,
+        seeds = [b"inheritance", domain_account.key().as_ref()],
+        bump = inheritance.bump,
+        has_one = beneficiary @ DnsError::Unauthorized,
+        close = beneficiary
+    )]
+    pub inheritance: Account<'info, Inheritance>,
+
+    #[account(mut)]
+    pub beneficiary: Signer<'info>,
+
+    #[account(seeds = [b"config"], bump = config.bump)]
+    pub config: Box<Account<'info, ProgramConfig>>,
+}
+
+/// Account context for setting a domain's governance
+/// 
+/// # Accounts
+/// * `domain_account` - Domain (owned by authority)
+/// * `governance` - Governance PDA (created or overwritten)
+/// * `authority` - Domain owner (signer, pays rent)
+/// * `remaining_accounts` - Signing members, when governance already exists
+/// 
+/// # PDA Derivation
+/// - Seeds: [b"governance", domain_account.key()]
+#[derive(Accounts)]
+pub struct SetGovernance<'info> {
+    #[account(has_one = authority @ DnsError::Unauthorized)]
+    pub domain_account: Account<'info, DomainRecord>,
+
+    #[account(
+        init_if_needed,
+        payer = authority,
+        space = 8 + DomainGovernance::INIT_SPACE,
+        seeds = [b"governance", domain_account.key().as_ref()],
+        bump
+    )]
+    pub governance: Account<'info, DomainGovernance>,
+
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// Account context for removing a domain's governance
+/// 
+/// # Accounts
+/// * `domain_account` - Domain (owned by authority)
+/// * `governance` - Governance PDA (closed)
+/// * `authority` - Domain owner (signer, receives rent)
+/// * `remaining_accounts` - Signing members
+#[derive(Accounts)]
+pub struct RemoveGovernance<'info> {
+    #[account(has_one = authority @ DnsError::Unauthorized)]
+    pub domain_account: Account<'info, DomainRecord>,
+
+    #[account(
+        mut,
+        seeds = [b"governance", domain_account.key().as_ref()],
+        bump = governance.bump,
+        close = authority
+    )]
+    pub governance: Account<'info, DomainGovernance>,
+
+    #[account(mut)]
+    pub authority: Signer<'info>,
+}
+
+/// Account context for locking a domain
+/// 
+/// # Accounts
+/// * `domain_account` - Domain to lock (owned by authority, mutable)
+/// * `lock` - Lock PDA (init; one lock at a time)
+/// * `authority` - Domain owner (signer, pays rent)
+/// 
+/// # PDA Derivation
+/// - Seeds: [b"lock", domain_account.key()]
+#[derive(Accounts)]
+pub struct LockDomain<'info> {
+    #[account(mut, has_one = authority @ DnsError::Unauthorized)]
+    pub domain_account: Account<'info, DomainRecord>,
+
+    #[account(
+        init,
+        payer = authority,
+        space = 8 + DomainLock::INIT_SPACE,
+        seeds = [b"lock", domain_account.key().as_ref()],
+        bump
+    )]
+    pub lock: Account<'info, DomainLock>,
+
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// Account context for requesting or cancelling an unlock
+/// 
+/// # Accounts
+/// * `domain_account` - Locked domain (owned by authority)
+/// * `lock` - Lock PDA (mutable)
+/// * `authority` - Domain owner (signer)
+#[derive(Accounts)]
+pub struct ModifyLock<'info> {
+    #[account(has_one = authority @ DnsError::Unauthorized)]
+    pub domain_account: Account<'info, DomainRecord>,
+
+    #[account(
+        mut,
+        seeds = [b"lock", domain_account.key().as_ref()],
+        bump = lock.bump
+    )]
+    pub lock: Account<'info, DomainLock>,
+
+    pub authority: Signer<'info>,
+}
+
+/// Account context for removing an elapsed lock
+/// 
+/// # Accounts
+/// * `domain_account` - Locked domain (owned by authority)
+/// * `lock` - Lock PDA (closed)
+/// * `authority` - Domain owner (signer, receives rent)
+#[derive(Accounts)]
+pub struct UnlockDomain<'info> {
+    #[account(has_one = authority @ DnsError::Unauthorized)]
+    pub domain_account: Account<'info, DomainRecord>,
+
+    #[account(
+        mut,
+        seeds = [b"lock", domain_account.key().as_ref()],
+        bump = lock.bump,
+        close = authority
+    )]
+    pub lock: Account<'info, DomainLock>,
+
+    #[account(mut)]
+    pub authority: Signer<'info>,
+}
+
+/// Account context for setting an update timelock
+/// 
+/// # Accounts
+/// * `domain_account` - Domain (owned by authority)
+/// * `timelock` - Update timelock PDA (created or updated)
+/// * `authority` - Domain owner (signer, pays rent)
+/// 
+/// # PDA Derivation
+/// - Seeds: [b"timelock", domain_account.key()]
+#[derive(Accounts)]
+pub struct SetUpdateDelay<'info> {
+    #[account(has_one = authority @ DnsError::Unauthorized)]
+    pub domain_account: Account<'info, DomainRecord>,
+
+    #[account(
+        init_if_needed,
+        payer = authority,
+        space = 8 + UpdateTimelock::INIT_SPACE,
+        seeds = [b"timelock", domain_account.key().as_ref()],
+        bump
+    )]
+    pub timelock: Account<'info, UpdateTimelock>,
+
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// Account context for requesting a timelock's removal
+/// 
+/// # Accounts
+/// * `domain_account` - Timelocked domain (owned by authority)
+/// * `timelock` - Update timelock PDA (mutable)
+/// * `authority` - Domain owner (signer)
+#[derive(Accounts)]
+pub struct ModifyTimelock<'info> {
+    #[account(has_one = authority @ DnsError::Unauthorized)]
+    pub domain_account: Account<'info, DomainRecord>,
+
+    #[account(
+        mut,
+        seeds = [b"timelock", domain_account.key().as_ref()],
+        bump = timelock.bump
+    )]
+    pub timelock: Account<'info, UpdateTimelock>,
+
+    pub authority: Signer<'info>,
+}
+
+/// Account context for removing a lifted timelock
+/// 
+/// # Accounts
+/// * `domain_account` - Timelocked domain (owned by authority)
+/// * `timelock` - Update timelock PDA (closed)
+/// * `authority` - Domain owner (signer, receives rent)
+#[derive(Accounts)]
+pub struct RemoveTimelock<'info> {
+    #[account(has_one = authority @ DnsError::Unauthorized)]
+    pub domain_account: Account<'info, DomainRecord>,
+
+    #[account(
+        mut,
+        seeds = [b"timelock", domain_account.key().as_ref()],
+        bump = timelock.bump,
+        close = authority
+    )]
+    pub timelock: Account<'info, UpdateTimelock>,
+
+    #[account(mut)]
+    pub authority: Signer<'info>,
+}
+
+/// Account context for scheduling a record change
+/// 
+/// # Accounts
+/// * `domain_account` - Timelocked domain (owned by authority)
+/// * `pending_update` - Scheduled change PDA (created or overwritten)
+/// * `timelock` - Update timelock PDA (read by `update_delay`)
+/// * `governance` - Governance PDA (empty unless `set_governance` was called)
+/// * `lock` - Lock PDA (empty unless `lock_domain` was called)
+/// * `authority` - Domain owner (signer, pays rent)
+/// 
+/// # PDA Derivation
+/// - Seeds: [b"pending_update", domain_account.key()]
+#[derive(Accounts)]
+pub struct ScheduleUpdate<'info> {
+    #[account(has_one = authority @ DnsError::Unauthorized)]
+    pub domain_account: Account<'info, DomainRecord>,
+
+    #[account(
+        init_if_needed,
+        payer = authority,
+        space = 8 + PendingUpdate::INIT_SPACE,
+        seeds = [b"pending_update", domain_account.key().as_ref()],
+        bump
+    )]
+    pub pending_update: Account<'info, PendingUpdate>,
+
+    /// CHECK: Timelock PDA, empty unless set; read by `update_delay`
+    #[account(seeds = [b"timelock", domain_account.key().as_ref()], bump)]
+    pub timelock: UncheckedAccount<'info>,
+
+    /// CHECK: Governance PDA, empty unless set; read by `require_governance`
+    #[account(seeds = [b"governance", domain_account.key().as_ref()], bump)]
+    pub governance: UncheckedAccount<'info>,
+
+    /// CHECK: Lock PDA, empty unless locked; read by `require_unlocked`
+    #[account(seeds = [b"lock", domain_account.key().as_ref()], bump)]
+    pub lock: UncheckedAccount<'info>,
+
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// Account context for cancelling a scheduled change
+/// 
+/// # Accounts
+/// * `domain_account` - Timelocked domain (owned by authority)
+/// * `pending_update` - Scheduled change PDA (closed)
+/// * `authority` - Domain owner (signer, receives rent)
+#[derive(Accounts)]
+pub struct CancelScheduledUpdate<'info> {
+    #[account(has_one = authority @ DnsError::Unauthorized)]
+    pub domain_account: Account<'info, DomainRecord>,
+
+    #[account(
+        mut,
+        seeds = [b"pending_update", domain_account.key().as_ref()],
+        bump = pending_update.bump,
+        close = authority
+    )]
+    pub pending_update: Account<'info, PendingUpdate>,
+
+    #[account(mut)]
+    pub authority: Signer<'info>,
+}
+
+/// Account context for applying a scheduled change
+/// 
+/// # Accounts
+/// * `domain_account` - Domain to update (resized)
+/// * `pending_update` - Scheduled change PDA (closed, rent to authority)
+/// * `record_history` - History ring buffer PDA (created on first update)
+/// * `lock` - Lock PDA (empty unless `lock_domain` was called)
+/// * `lease` - Lease PDA (empty unless `lease_domain` was called)
+/// * `authority` - Domain owner (receives the pending update's rent)
+/// * `payer` - Anyone (signer, pays or receives rent difference)
+/// * `config` - Singleton config PDA (pause flag)
+#[derive(Accounts)]
+pub struct ExecuteUpdate<'info> {
+    #[account(
+        mut,
+        realloc = DomainRecord::space_for(
+            domain_account.domain_name.len(),
+            pending_update.record.len()
+        ),
+        realloc::payer = payer,
+        realloc::zero = true
+    )]
+    pub domain_account: Account<'info, DomainRecord>,
+
+    #[account(
+        mut,
+        seeds = [b"pending_update", domain_account.key().as_ref()],
+        bump = pending_update.bump,
+        close = authority
+    )]
+    pub pending_update: Account<'info, PendingUpdate>,
+
+    #[account(
+        init_if_needed,
+        payer = payer,
+        space = 8 + RecordHistory::INIT_SPACE,
+        seeds = [b"history", domain_account.key().as_ref()],
+        bump
+    )]
+    pub record_history: Account<'info, RecordHistory>,
+
+    /// CHECK: Lock PDA, empty unless locked; read by `require_unlocked`
+    #[account(seeds = [b"lock", domain_account.key().as_ref()], bump)]
+    pub lock: UncheckedAccount<'info>,
+
+    /// CHECK: Lease PDA, empty unless leased; read by `active_lease`
+    #[account(seeds = [b"lease", domain_account.key().as_ref()], bump)]
+    pub lease: UncheckedAccount<'info>,
+
+    /// CHECK: Rent recipient, checked against the domain's authority
+    #[account(mut, address = domain_account.authority @ DnsError::Unauthorized)]
+    pub authority: UncheckedAccount<'info>,
+
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+
+    #[account(seeds = [b"config"], bump = config.bump)]
+    pub config: Box<Account<'info, ProgramConfig>>,
+}
+
+/// Account context for unregistering a domain
+/// 
+/// # Accounts
+/// * `domain_account` - Domain to close (owned by authority, rent refunded)
+/// * `authority` - Domain owner (signer, receives rent)
+/// * `namespace_index` - Index head PDA (mutable)
+/// * `index_page` - Index page listing the domain (mutable)
+/// * `protocol_stats` - Stats PDA (mutable)
+/// * `did_record`, `sshfp_records`, `dynamic_record`, `verification_request` -
+///   The domain's fixed child PDAs, which must already be closed
+/// * `governance` - Governance PDA (empty unless `set_governance` was called)
+/// * `lock` - Lock PDA (empty unless `lock_domain` was called)
+/// * `lease` - Lease PDA (empty unless `lease_domain` was called)
+/// * `timelock` - Update timelock PDA (empty unless `set_update_delay`
+///   was called)
+/// * `config` - Singleton config PDA (pause flag)
+/// 
+/// # Notes
+/// - Closing children first stops them attaching to whoever registers
+///   the name next, since child PDAs derive from the domain address
+#[derive(Accounts)]
+pub struct UnregisterDomain<'info> {
+    #[account(
+        mut,
+        seeds = [b"domain", &domain_seed(&domain_account.domain_name)],
+        bump,
+        has_one = authority @ DnsError::Unauthorized,
+        constraint = domain_account.record_set_count == 0 @ DnsError::ChildRecordsExist,
+        constraint = domain_account.subdomain_count == 0 @ DnsError::ChildRecordsExist,
+        close = authority
+    )]
+    pub domain_account: Account<'info, DomainRecord>,
+
+    /// CHECK: Governance PDA, empty unless set; read by `require_governance`
+    #[account(seeds = [b"governance", domain_account.key().as_ref()], bump)]
+    pub governance: UncheckedAccount<'info>,
+
+    /// CHECK: Lock PDA, empty unless locked; read by `require_unlocked`
+    #[account(seeds = [b"lock", domain_account.key().as_ref()], bump)]
+    pub lock: UncheckedAccount<'info>,
+
+    /// CHECK: Lease PDA, empty unless leased; read by `active_lease`
+    #[account(seeds = [b"lease", domain_account.key().as_ref()], bump)]
+    pub lease: UncheckedAccount<'info>,
+
+    /// CHECK: Timelock PDA, empty unless set; read by `update_delay`
+    #[account(seeds = [b"timelock", domain_account.key().as_ref()], bump)]
+    pub timelock: UncheckedAccount<'info>,
+
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    #[account(mut, seeds = [b"index"], bump = namespace_index.bump)]
+    pub namespace_index: Account<'info, NamespaceIndex>,
+
+    #[account(
+        mut,
+        seeds = [b"index_page", &domain_account.index_page.to_le_bytes()],
+        bump = index_page.bump
+    )]
+    pub index_page: Account<'info, IndexPage>,
+
+    #[account(mut, seeds = [b"stats"], bump = protocol_stats.bump)]
+    pub protocol_stats: Account<'info, ProtocolStats>,
+
+    /// CHECK: Only checked to be empty
+    #[account(
+        seeds = [b"did", domain_account.key().as_ref()],
+        bump,
+        constraint = did_record.data_is_empty() @ DnsError::ChildRecordsExist
+    )]
+    pub did_record: UncheckedAccount<'info>,
+
+    /// CHECK: Only checked to be empty
+    #[account(
+        seeds = [b"sshfp", domain_account.key().as_ref()],
+        bump,
+        constraint = sshfp_records.data_is_empty() @ DnsError::ChildRecordsExist
+    )]
+    pub sshfp_records: UncheckedAccount<'info>,
+
+    /// CHECK: Only checked to be empty
+    #[account(
+        seeds = [b"dynamic", domain_account.key().as_ref()],
+        bump,
+        constraint = dynamic_record.data_is_empty() @ DnsError::ChildRecordsExist
+    )]
+    pub dynamic_record: UncheckedAccount<'info>,
+
+    /// CHECK: Only checked to be empty
+    #[account(
+        seeds = [b"verification", domain_account.key().as_ref()],
+        bump,
+        constraint = verification_request.data_is_empty() @ DnsError::ChildRecordsExist
+    )]
+    pub verification_request: UncheckedAccount<'info>,
+
+    #[account(seeds = [b"config"], bump = config.bump)]
+    pub config: Box<Account<'info, ProgramConfig>>,
+}
+
+/// Account context for proposing a domain transfer
+/// 
+/// # Accounts
+/// * `domain_account` - Domain to transfer (owned by authority, resized)
+/// * `authority` - Current domain owner (signer, pays any extra rent)
+/// * `governance` - Governance PDA (empty unless `set_governance` was called)
+/// * `lock` - Lock PDA (empty unless `lock_domain` was called)
+/// * `config` - Singleton config PDA (pause flag)
+/// 
+/// # Notes
+/// - Accounts created before `pending_authority` existed only have room
+///   for `None`; the realloc makes room for the key
+#[derive(Accounts)]
+pub struct ProposeTransfer<'info> {
+    #[account(
+        mut,
+        has_one = authority @ DnsError::Unauthorized,
+        realloc = DomainRecord::space_for(
+            domain_account.domain_name.len(),
+            domain_account.record.len()
+        ),
+        realloc::payer = authority,
+        realloc::zero = true
+    )]
+    pub domain_account: Account<'info, DomainRecord>,
+
+    /// CHECK: Governance PDA, empty unless set; read by `require_governance`
+    #[account(seeds = [b"governance", domain_account.key().as_ref()], bump)]
+    pub governance: UncheckedAccount<'info>,
+
+    /// CHECK: Lock PDA, empty unless locked; read by `require_unlocked`
+    #[account(seeds = [b"lock", domain_account.key().as_ref()], bump)]
+    pub lock: UncheckedAccount<'info>,
+
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+
+    #[account(seeds = [b"config"], bump = config.bump)]
+    pub config: Account<'info, ProgramConfig>,
+}
+
+/// Account context for accepting a domain transfer
+/// 
+/// # Accounts
+/// * `domain_account` - Domain being transferred (mutable)
+/// * `new_authority` - Pending owner (signer)
+/// * `config` - Singleton config PDA (pause flag)
+#[derive(Accounts)]
+pub struct AcceptTransfer<'info> {
+    #[account(
+        mut,
+        constraint = domain_account.pending_authority == Some(new_authority.key())
+            @ DnsError::NotPendingAuthority
+    )]
+    pub domain_account: Account<'info, DomainRecord>,
+
+    pub new_authority: Signer<'info>,
+
+    #[account(seeds = [b"config"], bump = config.bump)]
+    pub config: Account<'info, ProgramConfig>,
+}
+
+/// Account context for cancelling a domain transfer
+/// 
+/// # Accounts
+/// * `domain_account` - Domain with a pending transfer (owned by authority)
+/// * `authority` - Current domain owner (signer)
+#[derive(Accounts)]
+pub struct CancelTransfer<'info> {
+    #[account(mut, has_one = authority @ DnsError::Unauthorized)]
+    pub domain_account: Account<'info, DomainRecord>,
+
+    pub authority: Signer<'info>,
+}
+
+/// Account context for adding a value to a record set
+/// 
+/// # Accounts
+/// * `domain_account` - Domain the set belongs to (owned by authority, mutable)
+/// * `record_set` - PDA holding the set (created on first use, then grown)
+/// * `authority` - Domain owner (signer, pays rent)
+/// * `governance` - Governance PDA (empty unless `set_governance` was called)
+/// * `lock` - Lock PDA (empty unless `lock_domain` was called)
+/// * `timelock` - Update timelock PDA (empty unless `set_update_delay`
+///   was called)
+/// * `config` - Singleton config PDA (pause flag)
+/// 
+/// # PDA Derivation
+/// - Seeds: [b"record", domain_account.key(), record_type as u8]
+#[derive(Accounts)]
+#[instruction(record_type: RecordType, value: String)]
+pub struct AddRecord<'info> {
+    #[account(mut, has_one = authority @ DnsError::Unauthorized)]
+    pub domain_account: Account<'info, DomainRecord>,
+
+    #[account(
+        init_if_needed,
+        payer = authority,
+        space = RecordSet::space_for(std::slice::from_ref(&value)),
+        seeds = [b"record", domain_account.key().as_ref(), &[record_type as u8]],
+        bump
+    )]
+    pub record_set: Account<'info, RecordSet>,
+
+    /// CHECK: Governance PDA, empty unless set; read by `require_governance`
+    #[account(seeds = [b"governance", domain_account.key().as_ref()], bump)]
+    pub governance: UncheckedAccount<'info>,
+
+    /// CHECK: Lock PDA, empty unless locked; read by `require_unlocked`
+    #[account(seeds = [b"lock", domain_account.key().as_ref()], bump)]
+    pub lock: UncheckedAccount<'info>,
+
+    /// CHECK: Timelock PDA, empty unless set; read by `update_delay`
+    #[account(seeds = [b"timelock", domain_account.key().as_ref()], bump)]
+    pub timelock: UncheckedAccount<'info>,
+
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+
+    #[account(seeds = [b"config"], bump = config.bump)]
+    pub config: Box<Account<'info, ProgramConfig>>,
+}
+
+/// Account context for removing or replacing record set values
+/// 
+/// # Accounts
+/// * `domain_account` - Domain the set belongs to (owned by authority, mutable)
+/// * `record_set` - PDA holding the set (resized, closed when emptied)
+/// * `authority` - Domain owner (signer, pays or receives rent difference)
+/// * `governance` - Governance PDA (empty unless `set_governance` was called)
+/// * `lock` - Lock PDA (empty unless `lock_domain` was called)
+/// * `timelock` - Update timelock PDA (empty unless `set_update_delay`
+///   was called)
+/// * `config` - Singleton config PDA (pause flag)
+#[derive(Accounts)]
+#[instruction(record_type: RecordType)]
+pub struct ModifyRecordSet<'info> {
+    #[account(mut, has_one = authority @ DnsError::Unauthorized)]
+    pub domain_account: Account<'info, DomainRecord>,
+
+    #[account(
+        mut,
+        seeds = [b"record", domain_account.key().as_ref(), &[record_type as u8]],
+        bump = record_set.bump
+    )]
+    pub record_set: Account<'info, RecordSet>,
+
+    /// CHECK: Governance PDA, empty unless set; read by `require_governance`
+    #[account(seeds = [b"governance", domain_account.key().as_ref()], bump)]
+    pub governance: UncheckedAccount<'info>,
+
+    /// CHECK: Lock PDA, empty unless locked; read by `require_unlocked`
+    #[account(seeds = [b"lock", domain_account.key().as_ref()], bump)]
+    pub lock: UncheckedAccount<'info>,
+
+    /// CHECK: Timelock PDA, empty unless set; read by `update_delay`
+    #[account(seeds = [b"timelock", domain_account.key().as_ref()], bump)]
+    pub timelock: UncheckedAccount<'info>,
+
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+
+    #[account(seeds = [b"config"], bump = config.bump)]
+    pub config: Box<Account<'info, ProgramConfig>>,
+}
+
+/// Account context for record set lookup
+/// 
+/// # Accounts
+/// * `domain_account` - Domain the set belongs to (read-only)
+/// * `record_set` - PDA holding the set (read-only)
+#[derive(Accounts)]
+#[instruction(record_type: RecordType)]
+pub struct ResolveRecordSet<'info> {
+    pub domain_account: Account<'info, DomainRecord>,
+
+    #[account(
+        seeds = [b"record", domain_account.key().as_ref(), &[record_type as u8]],
+        bump = record_set.bump
+    )]
+    pub record_set: Account<'info, RecordSet>,
+}
+
+/// Account context for renewing a domain
+/// 
+/// # Accounts
+/// * `domain_account` - Domain to renew (owned by authority, resized)
+/// * `authority` - Domain owner (signer, pays rent and fees)
+/// * `config` - Program config PDA holding the fee schedule
+/// * `treasury` - Fee recipient named in the config (mutable)
+/// * `protocol_stats` - Stats PDA (mutable)
+/// * `token_program`, `fee_mint`, `payer_token_account`,
+///   `treasury_token_account` - Optional; pass all four to pay the renewal
+///   fee in the configured SPL mint instead of lamports
+/// * `tld` - TLD PDA [b"tld", sha256(tld)] for the name's last label
+///   (may be empty)
+/// * `tld_treasury`, `tld_treasury_token_account` - The TLD's fee
+///   recipient (or its token account, when paying in the fee mint);
+///   required when the TLD takes a revenue share
+#[derive(Accounts)]
+pub struct RenewDomain<'info> {
+    #[account(
+        mut,
+        has_one = authority @ DnsError::Unauthorized,
+        realloc = DomainRecord::space_for(
+            domain_account.domain_name.len(),
+            domain_account.record.len()
+        ),
+        realloc::payer = authority,
+        realloc::zero = true
+    )]
+    pub domain_account: Account<'info, DomainRecord>,
+
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    #[account(seeds = [b"config"], bump = config.bump, has_one = treasury)]
+    pub config: Account<'info, ProgramConfig>,
+
+    /// CHECK: Fee recipient, checked against config.treasury
+    #[account(mut)]
+    pub treasury: UncheckedAccount<'info>,
+
+    #[account(mut, seeds = [b"stats"], bump = protocol_stats.bump)]
+    pub protocol_stats: Account<'info, ProtocolStats>,
+
+    pub system_program: Program<'info, System>,
+
+    pub token_program: Option<Interface<'info, TokenInterface>>,
+
+    pub fee_mint: Option<Box<InterfaceAccount<'info, Mint>>>,
+
+    #[account(mut)]
+    pub payer_token_account: Option<Box<InterfaceAccount<'info, TokenAccount>>>,
+
+    #[account(mut)]
+    pub treasury_token_account: Option<Box<InterfaceAccount<'info, TokenAccount>>>,
+
+    /// CHECK: TLD PDA, empty unless the TLD is owned; read by `tld_cut`
+    #[account(seeds = [b"tld", &domain_seed(tld_of(&domain_account.domain_name))], bump)]
+    pub tld: UncheckedAccount<'info>,
+
+    /// CHECK: TLD fee recipient, checked against the TLD's treasury by `tld_cut`
+    #[account(mut)]
+    pub tld_treasury: Option<UncheckedAccount<'info>>,
+
+    #[account(mut)]
+    pub tld_treasury_token_account: Option<Box<InterfaceAccount<'info, TokenAccount>>>,
+}
+
+/// Account context for reclaiming an expired domain
+/// 
+/// # Accounts
+/// * `domain_account` - Expired domain to close
+/// * `authority` - Lapsed owner (receives rent unless wrapped; need not sign)
+/// * `reclaimer` - Anyone (signer, pays release PDA rent, receives a
+///   wrapped domain's rent)
+/// * `namespace_index` - Index head PDA (mutable)
+/// * `index_page` - Index page listing the domain (mutable)
+/// * `protocol_stats` - Stats PDA (mutable)
+/// * `did_record`, `sshfp_records`, `dynamic_record`, `verification_request` -
+///   The domain's fixed child PDAs, which must already be closed
+/// * `config` - Program config PDA holding the release premium
+/// * `release` - Release PDA [b"release", sha256(domain_name)] (created or reset)
+#[derive(Accounts)]
+pub struct ReclaimExpired<'info> {
+    #[account(
+        mut,
+        seeds = [b"domain", &domain_seed(&domain_account.domain_name)],
+        bump,
+        has_one = authority @ DnsError::Unauthorized,
+        constraint = domain_account.record_set_count == 0 @ DnsError::ChildRecordsExist,
+        constraint = domain_account.subdomain_count == 0 @ DnsError::ChildRecordsExist
+    )]
+    pub domain_account: Account<'info, DomainRecord>,
+
+    /// CHECK: Rent recipient, checked against domain_account.authority
+    #[account(mut)]
+    pub authority: UncheckedAccount<'info>,
+
+    #[account(mut)]
+    pub reclaimer: Signer<'info>,
+
+    #[account(mut, seeds = [b"index"], bump = namespace_index.bump)]
+    pub namespace_index: Account<'info, NamespaceIndex>,
+
+    #[account(
+        mut,
+        seeds = [b"index_page", &domain_account.index_page.to_le_bytes()],
+        bump = index_page.bump
+    )]
+    pub index_page: Account<'info, IndexPage>,
+
+    #[account(mut, seeds = [b"stats"], bump = protocol_stats.bump)]
+    pub protocol_stats: Account<'info, ProtocolStats>,
+
+    /// CHECK: Only checked to be empty
+    #[account(
+        seeds = [b"did", domain_account.key().as_ref()],
+        bump,
+        constraint = did_record.data_is_empty() @ DnsError::ChildRecordsExist
+    )]
+    pub did_record: UncheckedAccount<'info>,
+
+    /// CHECK: Only checked to be empty
+    #[account(
+        seeds = [b"sshfp", domain_account.key().as_ref()],
+        bump,
+        constraint = sshfp_records.data_is_empty() @ DnsError::ChildRecordsExist
+    )]
+    pub sshfp_records: UncheckedAccount<'info>,
+
+    /// CHECK: Only checked to be empty
+    #[account(
+        seeds = [b"dynamic", domain_account.key().as_ref()],
+        bump,
+        constraint = dynamic_record.data_is_empty() @ DnsError::ChildRecordsExist
+    )]
+    pub dynamic_record: UncheckedAccount<'info>,
+
+    /// CHECK: Only checked to be empty
+    #[account(
+        seeds = [b"verification", domain_account.key().as_ref()],
+        bump,
+        constraint = verification_request.data_is_empty() @ DnsError::ChildRecordsExist
+    )]
+    pub verification_request: UncheckedAccount<'info>,
+
+    #[account(seeds = [b"config"], bump = config.bump)]
+    pub config: Account<'info, ProgramConfig>,
+
+    #[account(
+        init_if_needed,
+        payer = reclaimer,
+        space = 8 + ReleaseRecord::INIT_SPACE,
+        seeds = [b"release", &domain_seed(&domain_account.domain_name)],
+        bump
+    )]
+    pub release: Account<'info, ReleaseRecord>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// Account context for changing a domain's subdomain policy
+/// 
+/// # Accounts
+/// * `domain_account` - Parent domain (owned by authority, resized)
+/// * `authority` - Domain owner (signer, pays any extra rent)
+#[derive(Accounts)]
+pub struct SetSubdomainPolicy<'info> {
+    #[account(
+        mut,
+        has_one = authority @ DnsError::Unauthorized,
+        realloc = DomainRecord::space_for(
+            domain_account.domain_name.len(),
+            domain_account.record.len()
+        ),
+        realloc::payer = authority,
+        realloc::zero = true
+    )]
+    pub domain_account: Account<'info, DomainRecord>,
+
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// Account context for subdomain registration
+/// 
+/// # Accounts
+/// * `parent_account` - Parent domain (mutable, counts subdomains)
+/// * `subdomain` - Subdomain PDA (init)
+/// * `payer` - Parent authority, or anyone if the parent is open (signer)
+/// * `config` - Singleton config PDA (feature toggles, pause flag)
+/// 
+/// # PDA Derivation
+/// - Seeds: [b"subdomain", parent_account.key(), sha256(label)]
+/// - One subdomain per label under each parent
+#[derive(Accounts)]
+#[instruction(label: String)]
+pub struct RegisterSubdomain<'info> {
+    #[account(mut)]
+    pub parent_account: Account<'info, DomainRecord>,
+
+    #[account(
+        init,
+        payer = payer,
+        space = 8 + SubdomainRecord::INIT_SPACE,
+        seeds = [b"subdomain", parent_account.key().as_ref(), &domain_seed(&label)],
+        bump
+    )]
+    pub subdomain: Account<'info, SubdomainRecord>,
+
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+
+    #[account(seeds = [b"config"], bump = config.bump)]
+    pub config: Box<Account<'info, ProgramConfig>>,
+}
+
+/// Account context for batch subdomain registration
+/// 
+/// # Accounts
+/// * `parent_account` - Parent domain (mutable, counts subdomains)
+/// * `payer` - Parent authority, or anyone if the parent is open (signer)
+/// * `config` - Singleton config PDA (feature toggles, pause flag)
+/// * `remaining_accounts` - One writable subdomain PDA per entry, in order
+#[derive(Accounts)]
+pub struct RegisterBatch<'info> {
+    #[account(mut)]
+    pub parent_account: Account<'info, DomainRecord>,
+
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+
+    #[account(seeds = [b"config"], bump = config.bump)]
+    pub config: Box<Account<'info, ProgramConfig>>,
+}
+
+/// Account context for subdomain lookup
+/// 
+/// # Accounts
+/// * `parent_account` - Parent domain (read-only)
+/// * `subdomain` - Subdomain PDA (read-only)
+#[derive(Accounts)]
+#[instruction(label: String)]
+pub struct ResolveSubdomain<'info> {
+    pub parent_account: Account<'info, DomainRecord>,
+
+    #[account(
+        seeds = [b"subdomain", parent_account.key().as_ref(), &domain_seed(&label)],
+        bump = subdomain.bump
+    )]
+    pub subdomain: Account<'info, SubdomainRecord>,
+}
+
+/// Account context for removing a subdomain
+/// 
+/// # Accounts
+/// * `parent_account` - Parent domain (mutable, counts subdomains)
+/// * `subdomain` - Subdomain PDA (closed)
+/// * `authority` - Subdomain owner (receives rent; need not sign)
+/// * `signer` - Subdomain owner, parent owner, or anyone once the parent
+///   is reclaimable
+#[derive(Accounts)]
+#[instruction(label: String)]
+pub struct RemoveSubdomain<'info> {
+    #[account(mut)]
+    pub parent_account: Account<'info, DomainRecord>,
+
+    #[account(
+        mut,
+        seeds = [b"subdomain", parent_account.key().as_ref(), &domain_seed(&label)],
+        bump = subdomain.bump,
+        has_one = authority @ DnsError::Unauthorized,
+        close = authority
+    )]
+    pub subdomain: Account<'info, SubdomainRecord>,
+
+    /// CHECK: Rent recipient, checked against subdomain.authority
+    #[account(mut)]
+    pub authority: UncheckedAccount<'info>,
+
+    pub signer: Signer<'info>,
+}
+
+/// Account context for creating a reserved-name chunk
+/// 
+/// # Accounts
+/// * `config` - Program config naming the admin
+/// * `reserved_names` - Chunk PDA (init)
+/// * `admin` - Config admin (signer, pays rent)
+/// 
+/// # PDA Derivation
+/// - Seeds: [b"reserved", chunk]
+#[derive(Accounts)]
+#[instruction(chunk: u8)]
+pub struct InitializeReservedNames<'info> {
+    #[account(seeds = [b"config"], bump = config.bump, has_one = admin @ DnsError::NotAdmin)]
+    pub config: Account<'info, ProgramConfig>,
+
+    #[account(
+        init,
+        payer = admin,
+        space = 8 + ReservedNames::INIT_SPACE,
+        seeds = [b"reserved", &[chunk]],
+        bump
+    )]
+    pub reserved_names: Account<'info, ReservedNames>,
+
+    #[account(mut)]
+    pub admin: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// Account context for changing a reserved-name chunk
+/// 
+/// # Accounts
+/// * `config` - Program config naming the admin
+/// * `reserved_names` - Chunk PDA (mutable)
+/// * `admin` - Config admin (signer)
+#[derive(Accounts)]
+#[instruction(chunk: u8)]
+pub struct ManageReservedNames<'info> {
+    #[account(seeds = [b"config"], bump = config.bump, has_one = admin @ DnsError::NotAdmin)]
+    pub config: Account<'info, ProgramConfig>,
+
+    #[account(mut, seeds = [b"reserved", &[chunk]], bump = reserved_names.bump)]
+    pub reserved_names: Account<'info, ReservedNames>,
+
+    pub admin: Signer<'info>,
+}
+
+/// Account context for leasing a domain
+/// 
+/// # Accounts
+/// * `domain_account` - Domain to lease (owned by authority)
+/// * `lease` - Lease PDA (created, or reused once the last lease ended)
+/// * `authority` - Domain owner (signer, pays rent, receives lease rent)
+/// * `tenant` - Tenant (signer, pays lease rent)
+/// * `governance` - Governance PDA (empty unless `set_governance` was called)
+/// * `lock` - Lock PDA (empty unless `lock_domain` was called)
+/// * `config` - Singleton config PDA (feature toggles, pause flag)
+/// 
+/// # PDA Derivation
+/// - Seeds: [b"lease", domain_account.key()]
+#[derive(Accounts)]
+pub struct LeaseDomain<'info> {
+    #[account(has_one = authority @ DnsError::Unauthorized)]
+    pub domain_account: Account<'info, DomainRecord>,
+
+    #[account(
+        init_if_needed,
+        payer = authority,
+        space = 8 + DomainLease::INIT_SPACE,
+        seeds = [b"lease", domain_account.key().as_ref()],
+        bump
+    )]
+    pub lease: Account<'info, DomainLease>,
+
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    #[account(mut)]
+    pub tenant: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+
+    /// CHECK: Governance PDA, empty unless set; read by `require_governance`
+    #[account(seeds = [b"governance", domain_account.key().as_ref()], bump)]
+    pub governance: UncheckedAccount<'info>,
+
+    /// CHECK: Lock PDA, empty unless locked; read by `require_unlocked`
+    #[account(seeds = [b"lock", domain_account.key().as_ref()], bump)]
+    pub lock: UncheckedAccount<'info>,
+
+    #[account(seeds = [b"config"], bump = config.bump)]
+    pub config: Box<Account<'info, ProgramConfig>>,
+}
+
+/// Account context for ending a lease
+/// 
+/// # Accounts
+/// * `domain_account` - Leased domain
+/// * `lease` - Lease PDA (closed)
+/// * `lessor` - Owner that created the lease (mutable, receives rent)
+/// * `signer` - Tenant, or anyone once the lease is over
+#[derive(Accounts)]
+pub struct EndLease<'info> {
+    pub domain_account: Account<'info, DomainRecord>,
+
+    #[account(
+        mut,
+        seeds = [b"lease", domain_account.key().as_ref()],
+        bump = lease.bump,
+        has_one = lessor @ DnsError::Unauthorized,
+        close = lessor
+    )]
+    pub lease: Account<'info, DomainLease>,
+
+    /// CHECK: Rent recipient, checked against lease.lessor
+    #[account(mut)]
+    pub lessor: UncheckedAccount<'info>,
+
+    pub signer: Signer<'info>,
+}
+
+/// Account context for setting a primary domain
+/// 
+/// # Accounts
+/// * `domain_account` - Domain to show for the wallet (owned by authority)
+/// * `reverse` - Reverse PDA for the wallet (created or overwritten)
+/// * `authority` - Wallet (signer, pays rent)
+/// 
+/// # PDA Derivation
+/// - Seeds: [b"reverse", authority.key()]
+#[derive(Accounts)]
+pub struct SetPrimaryDomain<'info> {
+    #[account(has_one = authority @ DnsError::Unauthorized)]
+    pub domain_account: Account<'info, DomainRecord>,
+
+    #[account(
+        init_if_needed,
+        payer = authority,
+        space = 8 + PrimaryDomain::INIT_SPACE,
+        seeds = [b"reverse", authority.key().as_ref()],
+        bump
+    )]
+    pub reverse: Account<'info, PrimaryDomain>,
+
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// Account context for clearing a primary domain
+/// 
+/// # Accounts
+/// * `reverse` - Reverse PDA for the wallet (closed)
+/// * `owner` - Wallet (signer, receives rent)
+#[derive(Accounts)]
+pub struct ClearPrimary<'info> {
+    #[account(
+        mut,
+        seeds = [b"reverse", owner.key().as_ref()],
+        bump = reverse.bump,
+        close = owner
+    )]
+    pub reverse: Account<'info, PrimaryDomain>,
+
+    #[account(mut)]
+    pub owner: Signer<'info>,
+}
+
+/// Account context for reverse resolution
+/// 
+/// # Accounts
+/// * `reverse` - Reverse PDA for the wallet (read-only)
+/// * `domain_account` - Domain the reverse PDA names (may be closed)
+#[derive(Accounts)]
+pub struct ResolvePrimary<'info> {
+    pub reverse: Account<'info, PrimaryDomain>,
+
+    /// CHECK: Checked against reverse.domain; may have been unregistered
+    #[account(address = reverse.domain)]
+    pub domain_account: UncheckedAccount<'info>,
+}
+
+/// Account context for claiming a reverse (PTR) entry
+/// 
+/// # Accounts
+/// * `domain_account` - Domain with an A record (owned by authority)
+/// * `ip_pointers` - Reverse PDA for the record's IP (created if needed)
+/// * `authority` - Domain owner (signer, pays rent)
+/// 
+/// # PDA Derivation
+/// - Seeds: [b"ptr", ipv4 octets]
+#[derive(Accounts)]
+pub struct ClaimIpPointer<'info> {
+    #[account(
+        has_one = authority @ DnsError::Unauthorized,
+        constraint = domain_account.record_type == RecordType::A @ DnsError::NotARecord
+    )]
+    pub domain_account: Account<'info, DomainRecord>,
+
+    #[account(
+        init_if_needed,
+        payer = authority,
+        space = 8 + IpPointers::INIT_SPACE,
+        seeds = [b"ptr", &parse_ipv4(&domain_account.record).unwrap_or_default()],
+        bump
+    )]
+    pub ip_pointers: Account<'info, IpPointers>,
+
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// Account context for removing a reverse (PTR) entry
+/// 
+/// # Accounts
+/// * `ip_pointers` - Reverse PDA for the IP (mutable)
+/// * `domain_account` - Listed domain (may be closed)
+/// * `signer` - Domain authority, or anyone for a stale entry
+#[derive(Accounts)]
+#[instruction(ip: String)]
+pub struct RemoveIpPointer<'info> {
+    #[account(
+        mut,
+        seeds = [b"ptr", &parse_ipv4(&ip).unwrap_or_default()],
+        bump = ip_pointers.bump
+    )]
+    pub ip_pointers: Account<'info, IpPointers>,
+
+    /// CHECK: Read by `points_at`; may have been unregistered
+    pub domain_account: UncheckedAccount<'info>,
+
+    pub signer: Signer<'info>,
+}
+
+/// Account context for reverse IP lookups
+/// 
+/// # Accounts
+/// * `ip_pointers` - Reverse PDA for the IP (read-only)
+#[derive(Accounts)]
+#[instruction(ip: String)]
+pub struct ResolveIp<'info> {
+    #[account(
+        seeds = [b"ptr", &parse_ipv4(&ip).unwrap_or_default()],
+        bump = ip_pointers.bump
+    )]
+    pub ip_pointers: Account<'info, IpPointers>,
+}
+
+/// Account context for resolving a domain's wallet address
+/// 
+/// # Accounts
+/// * `domain_account` - Domain to resolve (read-only)
+/// * `record_set` - The domain's SOL record set, when the primary record
+///   is another type (optional)
+#[derive(Accounts)]
+pub struct ResolveWallet<'info> {
+    pub domain_account: Account<'info, DomainRecord>,
+
+    #[account(
+        seeds = [b"record", domain_account.key().as_ref(), &[RecordType::Sol as u8]],
+        bump = record_set.bump
+    )]
+    pub record_set: Option<Account<'info, RecordSet>>,
+}
+
+/// Account context for MX lookup
+/// 
+/// # Accounts
+/// * `domain_account` - Domain to resolve (read-only)
+/// * `record_set` - The domain's MX record set (optional)
+#[derive(Accounts)]
+pub struct ResolveMx<'info> {
+    pub domain_account: Account<'info, DomainRecord>,
+
+    #[account(
+        seeds = [b"record", domain_account.key().as_ref(), &[RecordType::Mx as u8]],
+        bump = record_set.bump
+    )]
+    pub record_set: Option<Account<'info, RecordSet>>,
+}
+
+/// Account context for delegation lookup
+/// 
+/// # Accounts
+/// * `domain_account` - Domain to resolve (read-only)
+/// * `record_set` - The domain's NS record set (optional)
+#[derive(Accounts)]
+pub struct ResolveDelegation<'info> {
+    pub domain_account: Account<'info, DomainRecord>,
+
+    #[account(
+        seeds = [b"record", domain_account.key().as_ref(), &[RecordType::Ns as u8]],
+        bump = record_set.bump
+    )]
+    pub record_set: Option<Account<'info, RecordSet>>,
+}
+
+/// Account context for setting a cross-chain address
+/// 
+/// # Accounts
+/// * `domain_account` - Domain (owned by authority)
+/// * `address_record` - Address PDA for the coin type (created or overwritten)
+/// * `authority` - Domain owner (signer, pays rent)
+/// * `governance` - Governance PDA (empty unless `set_governance` was called)
+/// * `lock` - Lock PDA (empty unless `lock_domain` was called)
+/// * `timelock` - Update timelock PDA (empty unless `set_update_delay`
+///   was called)
+/// * `config` - Singleton config PDA (pause flag)
+/// 
+/// # PDA Derivation
+/// - Seeds: [b"address", domain_account.key(), coin_type (u32 LE)]
+#[derive(Accounts)]
+#[instruction(coin_type: u32)]
+pub struct SetAddress<'info> {
+    #[account(has_one = authority @ DnsError::Unauthorized)]
+    pub domain_account: Account<'info, DomainRecord>,
+
+    #[account(
+        init_if_needed,
+        payer = authority,
+        space = 8 + AddressRecord::INIT_SPACE,
+        seeds = [b"address", domain_account.key().as_ref(), &coin_type.to_le_bytes()],
+        bump
+    )]
+    pub address_record: Account<'info, AddressRecord>,
+
+    /// CHECK: Governance PDA, empty unless set; read by `require_governance`
+    #[account(seeds = [b"governance", domain_account.key().as_ref()], bump)]
+    pub governance: UncheckedAccount<'info>,
+
+    /// CHECK: Lock PDA, empty unless locked; read by `require_unlocked`
+    #[account(seeds = [b"lock", domain_account.key().as_ref()], bump)]
+    pub lock: UncheckedAccount<'info>,
+
+    /// CHECK: Timelock PDA, empty unless set; read by `update_delay`
+    #[account(seeds = [b"timelock", domain_account.key().as_ref()], bump)]
+    pub timelock: UncheckedAccount<'info>,
+
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+
+    #[account(seeds = [b"config"], bump = config.bump)]
+    pub config: Box<Account<'info, ProgramConfig>>,
+}
+
+/// Account context for removing a cross-chain address
+/// 
+/// # Accounts
+/// * `domain_account` - Domain (owned by authority)
+/// * `address_record` - Address PDA (closed, rent to authority)
+/// * `authority` - Domain owner (signer)
+/// * `governance` - Governance PDA (empty unless `set_governance` was called)
+/// * `lock` - Lock PDA (empty unless `lock_domain` was called)
+/// * `timelock` - Update timelock PDA (empty unless `set_update_delay`
+///   was called)
+/// * `config` - Singleton config PDA (pause flag)
+#[derive(Accounts)]
+#[instruction(coin_type: u32)]
+pub struct RemoveAddress<'info> {
+    #[account(has_one = authority @ DnsError::Unauthorized)]
+    pub domain_account: Account<'info, DomainRecord>,
+
+    #[account(
+        mut,
+        seeds = [b"address", domain_account.key().as_ref(), &coin_type.to_le_bytes()],
+        bump = address_record.bump,
+        close = authority
+    )]
+    pub address_record: Account<'info, AddressRecord>,
+
+    /// CHECK: Governance PDA, empty unless set; read by `require_governance`
+    #[account(seeds = [b"governance", domain_account.key().as_ref()], bump)]
+    pub governance: UncheckedAccount<'info>,
+
+    /// CHECK: Lock PDA, empty unless locked; read by `require_unlocked`
+    #[account(seeds = [b"lock", domain_account.key().as_ref()], bump)]
+    pub lock: UncheckedAccount<'info>,
+
+    /// CHECK: Timelock PDA, empty unless set; read by `update_delay`
+    #[account(seeds = [b"timelock", domain_account.key().as_ref()], bump)]
+    pub timelock: UncheckedAccount<'info>,
+
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    #[account(seeds = [b"config"], bump = config.bump)]
+    pub config: Box<Account<'info, ProgramConfig>>,
+}
+
+/// Account context for cross-chain address lookup
+/// 
+/// # Accounts
+/// * `domain_account` - Domain to resolve (read-only)
+/// * `address_record` - Address PDA for the coin type (read-only)
+#[derive(Accounts)]
+#[instruction(coin_type: u32)]
+pub struct AddressOf<'info> {
+    pub domain_account: Account<'info, DomainRecord>,
+
+    #[account(
+        seeds = [b"address", domain_account.key().as_ref(), &coin_type.to_le_bytes()],
+        bump = address_record.bump
+    )]
+    pub address_record: Account<'info, AddressRecord>,
+}
+
+/// Account context for setting a profile text record
+/// 
+/// # Accounts
+/// * `domain_account` - Domain (owned by authority)
+/// * `text_record` - Text PDA for the key (created or overwritten)
+/// * `authority` - Domain owner (signer, pays rent)
+/// * `governance` - Governance PDA (empty unless `set_governance` was called)
+/// * `lock` - Lock PDA (empty unless `lock_domain` was called)
+/// * `timelock` - Update timelock PDA (empty unless `set_update_delay`
+///   was called)
+/// * `config` - Singleton config PDA (pause flag)
+/// 
+/// # PDA Derivation
+/// - Seeds: [b"text", domain_account.key(), key as u8]
+#[derive(Accounts)]
+#[instruction(key: TextKey)]
+pub struct SetText<'info> {
+    #[account(has_one = authority @ DnsError::Unauthorized)]
+    pub domain_account: Account<'info, DomainRecord>,
+
+    #[account(
+        init_if_needed,
+        payer = authority,
+        space = 8 + TextRecord::INIT_SPACE,
+        seeds = [b"text", domain_account.key().as_ref(), &[key as u8]],
+        bump
+    )]
+    pub text_record: Account<'info, TextRecord>,
+
+    /// CHECK: Governance PDA, empty unless set; read by `require_governance`
+    #[account(seeds = [b"governance", domain_account.key().as_ref()], bump)]
+    pub governance: UncheckedAccount<'info>,
+
+    /// CHECK: Lock PDA, empty unless locked; read by `require_unlocked`
+    #[account(seeds = [b"lock", domain_account.key().as_ref()], bump)]
+    pub lock: UncheckedAccount<'info>,
+
+    /// CHECK: Timelock PDA, empty unless set; read by `update_delay`
+    #[account(seeds = [b"timelock", domain_account.key().as_ref()], bump)]
+    pub timelock: UncheckedAccount<'info>,
+
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+
+    #[account(seeds = [b"config"], bump = config.bump)]
+    pub config: Box<Account<'info, ProgramConfig>>,
+}
+
+/// Account context for removing a profile text record
+/// 
+/// # Accounts
+/// * `domain_account` - Domain (owned by authority)
+/// * `text_record` - Text PDA (closed, rent to authority)
+/// * `authority` - Domain owner (signer)
+/// * `governance` - Governance PDA (empty unless `set_governance` was called)
+/// * `lock` - Lock PDA (empty unless `lock_domain` was called)
+/// * `timelock` - Update timelock PDA (empty unless `set_update_delay`
+///   was called)
+/// * `config` - Singleton config PDA (pause flag)
+#[derive(Accounts)]
+#[instruction(key: TextKey)]
+pub struct RemoveText<'info> {
+    #[account(has_one = authority @ DnsError::Unauthorized)]
+    pub domain_account: Account<'info, DomainRecord>,
+
+    #[account(
+        mut,
+        seeds = [b"text", domain_account.key().as_ref(), &[key as u8]],
+        bump = text_record.bump,
+        close = authority
+    )]
+    pub text_record: Account<'info, TextRecord>,
+
+    /// CHECK: Governance PDA, empty unless set; read by `require_governance`
+    #[account(seeds = [b"governance", domain_account.key().as_ref()], bump)]
+    pub governance: UncheckedAccount<'info>,
+
+    /// CHECK: Lock PDA, empty unless locked; read by `require_unlocked`
+    #[account(seeds = [b"lock", domain_account.key().as_ref()], bump)]
+    pub lock: UncheckedAccount<'info>,
+
+    /// CHECK: Timelock PDA, empty unless set; read by `update_delay`
+    #[account(seeds = [b"timelock", domain_account.key().as_ref()], bump)]
+    pub timelock: UncheckedAccount<'info>,
+
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    #[account(seeds = [b"config"], bump = config.bump)]
+    pub config: Box<Account<'info, ProgramConfig>>,
+}
+
+/// Account context for profile text lookup
+/// 
+/// # Accounts
+/// * `domain_account` - Domain to resolve (read-only)
+/// * `text_record` - Text PDA for the key (read-only)
+#[derive(Accounts)]
+#[instruction(key: TextKey)]
+pub struct TextOf<'info> {
+    pub domain_account: Account<'info, DomainRecord>,
+
+    #[account(
+        seeds = [b"text", domain_account.key().as_ref(), &[key as u8]],
+        bump = text_record.bump
+    )]
+    pub text_record: Account<'info, TextRecord>,
+}
+
+/// Account context for publishing SRV records
+/// 
+/// # Accounts
+/// * `domain_account` - Domain offering the service (owned by authority)
+/// * `srv_record` - SRV PDA for the service (created or overwritten)
+/// * `authority` - Domain owner (signer, pays rent)
+/// * `governance` - Governance PDA (empty unless `set_governance` was called)
+/// * `lock` - Lock PDA (empty unless `lock_domain` was called)
+/// * `timelock` - Update timelock PDA (empty unless `set_update_delay`
+///   was called)
+/// * `config` - Singleton config PDA (pause flag)
+/// 
+/// # PDA Derivation
+/// - Seeds: [b"srv", domain_account.key(), service, protocol]
+#[derive(Accounts)]
+#[instruction(service: String, protocol: String)]
+pub struct SetSrv<'info> {
+    #[account(has_one = authority @ DnsError::Unauthorized)]
+    pub domain_account: Account<'info, DomainRecord>,
+
+    #[account(
+        init_if_needed,
+        payer = authority,
+        space = 8 + SrvRecordSet::INIT_SPACE,
+        seeds = [
+            b"srv",
+            domain_account.key().as_ref(),
+            service.as_bytes(),
+            protocol.as_bytes()
+        ],
+        bump
+    )]
+    pub srv_record: Account<'info, SrvRecordSet>,
+
+    /// CHECK: Governance PDA, empty unless set; read by `require_governance`
+    #[account(seeds = [b"governance", domain_account.key().as_ref()], bump)]
+    pub governance: UncheckedAccount<'info>,
+
+    /// CHECK: Lock PDA, empty unless locked; read by `require_unlocked`
+    #[account(seeds = [b"lock", domain_account.key().as_ref()], bump)]
+    pub lock: UncheckedAccount<'info>,
+
+    /// CHECK: Timelock PDA, empty unless set; read by `update_delay`
+    #[account(seeds = [b"timelock", domain_account.key().as_ref()], bump)]
+    pub timelock: UncheckedAccount<'info>,
+
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+
+    #[account(seeds = [b"config"], bump = config.bump)]
+    pub config: Box<Account<'info, ProgramConfig>>,
+}
+
+/// Account context for removing SRV records
+/// 
+/// # Accounts
+/// * `domain_account` - Domain offering the service (owned by authority)
+/// * `srv_record` - SRV PDA (closed, rent to authority)
+/// * `authority` - Domain owner (signer)
+/// * `governance` - Governance PDA (empty unless `set_governance` was called)
+/// * `lock` - Lock PDA (empty unless `lock_domain` was called)
+/// * `timelock` - Update timelock PDA (empty unless `set_update_delay`
+///   was called)
+/// * `config` - Singleton config PDA (pause flag)
+#[derive(Accounts)]
+#[instruction(service: String, protocol: String)]
+pub struct RemoveSrv<'info> {
+    #[account(has_one = authority @ DnsError::Unauthorized)]
+    pub domain_account: Account<'info, DomainRecord>,
+
+    #[account(
+        mut,
+        seeds = [
+            b"srv",
+            domain_account.key().as_ref(),
+            service.as_bytes(),
+            protocol.as_bytes()
+        ],
+        bump = srv_record.bump,
+        close = authority
+    )]
+    pub srv_record: Account<'info, SrvRecordSet>,
+
+    /// CHECK: Governance PDA, empty unless set; read by `require_governance`
+    #[account(seeds = [b"governance", domain_account.key().as_ref()], bump)]
+    pub governance: UncheckedAccount<'info>,
+
+    /// CHECK: Lock PDA, empty unless locked; read by `require_unlocked`
+    #[account(seeds = [b"lock", domain_account.key().as_ref()], bump)]
+    pub lock: UncheckedAccount<'info>,
+
+    /// CHECK: Timelock PDA, empty unless set; read by `update_delay`
+    #[account(seeds = [b"timelock", domain_account.key().as_ref()], bump)]
+    pub timelock: UncheckedAccount<'info>,
+
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    #[account(seeds = [b"config"], bump = config.bump)]
+    pub config: Box<Account<'info, ProgramConfig>>,
+}
+
+/// Account context for SRV lookup
+/// 
+/// # Accounts
+/// * `domain_account` - Domain offering the service (read-only)
+/// * `srv_record` - SRV PDA for the service (read-only)
+#[derive(Accounts)]
+#[instruction(service: String, protocol: String)]
+pub struct ResolveSrv<'info> {
+    pub domain_account: Account<'info, DomainRecord>,
+
+    #[account(
+        seeds = [
+            b"srv",
+            domain_account.key().as_ref(),
+            service.as_bytes(),
+            protocol.as_bytes()
+        ],
+        bump = srv_record.bump
+    )]
+    pub srv_record: Account<'info, SrvRecordSet>,
+}
+
+/// Domain record data structure
+/// 
+/// # Fields
+/// * `domain_name` - The registered domain name (max 256 chars)
+/// * `record` - Record value, interpreted per `record_type` (max 255 chars)
+/// * `authority` - Public key of the registrar (wallet address)
+/// * `created_at` - Unix timestamp of registration
+/// * `wrapped_mint` - NFT mint while the domain is wrapped, otherwise None
+/// * `verified_external` - Owner proved control of the name in traditional DNS
+/// * `index_page` - Namespace index page listing this domain
+/// * `layout_version` - Storage layout version (0 = unversioned V1)
+/// * `updated_at` - Unix timestamp of the last record change
+/// * `pending_authority` - Proposed new owner awaiting `accept_transfer`
+/// * `record_type` - Type of `record` (A for accounts older than V4)
+/// * `record_set_count` - Open `RecordSet` PDAs for this domain
+/// * `ttl` - Seconds resolvers may cache `record` (3600 for older accounts)
+/// * `expires_at` - Unix timestamp the registration lapses (see `renew_domain`)
+/// * `open_subdomains` - Anyone may register subdomains, not just the authority
+/// * `subdomain_count` - Open `SubdomainRecord` PDAs under this domain
+/// * `owned_since` - Unix timestamp the current authority took over
+///   (registration or the last `hand_over`)
+/// 
+/// # Storage Size
+/// - domain_name: 4 bytes (length) + actual name bytes (max 256)
+/// - record: 4 bytes (length) + actual record bytes (max 255)
+/// - authority: 32 bytes (Pubkey)
+/// - created_at: 8 bytes (i64)
+/// - wrapped_mint: 1 byte (option tag) + 32 bytes (Pubkey)
+/// - verified_external: 1 byte (bool)
+/// - index_page: 4 bytes (u32)
+/// - layout_version: 1 byte (u8)
+/// - updated_at: 8 bytes (i64)
+/// - pending_authority: 1 byte (option tag) + 32 bytes (Pubkey)
+/// - record_type: 1 byte (enum)
+/// - record_set_count: 1 byte (u8)
+/// - ttl: 4 bytes (u32)
+/// - expires_at: 8 bytes (i64)
+/// - open_subdomains: 1 byte (bool)
+/// - subdomain_count: 4 bytes (u32)
+/// - owned_since: 8 bytes (i64)
+/// - Growth margin: 16 bytes
+/// - Total: 171 bytes + name + record + 8 byte discriminator
+///   (~197 bytes for an A record on a typical name vs ~690 at max lengths)
+/// 
+/// # Notes
+/// - The record can be replaced by the authority via `update_record`
+/// - Deterministically addressable via PDA
+/// - Rent-exempt storage
+#[account]
+#[derive(InitSpace)]
+pub struct DomainRecord {
+    #[max_len(256)]
+    pub domain_name: String,
+    #[max_len(255)]
+    pub record: String,
+    pub authority: Pubkey,
+    pub created_at: i64,
+    pub wrapped_mint: Option<Pubkey>,
+    pub verified_external: bool,
+    pub index_page: u32,
+    pub layout_version: u8,
+    pub updated_at: i64,
+    pub pending_authority: Option<Pubkey>,
+    pub record_type: RecordType,
+    pub record_set_count: u8,
+    pub ttl: u32,
+    pub expires_at: i64,
+    pub open_subdomains: bool,
+    pub subdomain_count: u32,
+    pub owned_since: i64,
+}
+
+impl DomainRecord {
+    /// Spare bytes allocated beyond the current contents
+    pub const GROWTH_MARGIN: usize = 16;
+
+    /// Fixed-size fields: authority, created_at, wrapped_mint,
+    /// verified_external, index_page, layout_version, updated_at,
+    /// pending_authority, record_type, record_set_count, ttl, expires_at,
+    /// open_subdomains, subdomain_count, owned_since
+    const FIXED_SPACE: usize =
+        32 + 8 + (1 + 32) + 1 + 4 + 1 + 8 + (1 + 32) + 1 + 1 + 4 + 8 + 1 + 4 + 8;
+
+    /// Account size (including discriminator) for the given string lengths
+    /// 
+    /// Sized from the actual name and record instead of their maximums, so
+    /// typical domains pay rent on well under half the max-length size.
+    pub fn space_for(domain_len: usize, record_len: usize) -> usize {
+        8 + (4 + domain_len) + (4 + record_len) + Self::FIXED_SPACE + Self::GROWTH_MARGIN
+    }
+
+    /// Convert fields from the stored layout version to the current one
+    /// 
+    /// The account must already be sized with `space_for`. Each layout
+    /// adds one step; unversioned V1 accounts need no field changes.
+    /// `now` starts the first registration term of pre-expiry accounts.
+    pub fn migrate_layout(&mut self, now: i64) {
+        if self.layout_version < DOMAIN_LAYOUT_V2 {
+            // Never updated so far: the last change was the registration
+            self.updated_at = self.created_at;
+        }
+        if self.layout_version < DOMAIN_LAYOUT_V3 {
+            self.pending_authority = None;
+        }
+        if self.layout_version < DOMAIN_LAYOUT_V4 {
+            // Every record before typed records was an IPv4 address
+            self.record_type = RecordType::A;
+        }
+        if self.layout_version < DOMAIN_LAYOUT_V5 {
+            self.record_set_count = 0;
+        }
+        if self.layout_version < DOMAIN_LAYOUT_V6 {
+            self.ttl = DEFAULT_TTL;
+        }
+        if self.layout_version < DOMAIN_LAYOUT_V7 {
+            self.expires_at = now + REGISTRATION_PERIOD;
+        }
+        if self.layout_version < DOMAIN_LAYOUT_V8 {
+            self.open_subdomains = false;
+            self.subdomain_count = 0;
+        }
+        if self.layout_version < DOMAIN_LAYOUT_V9 {
+            // Earlier handovers weren't recorded
+            self.owned_since = self.created_at;
+        }
+        self.layout_version = DOMAIN_LAYOUT_CURRENT;
+    }
+
+    /// Make `new_authority` the owner, dropping state tied to the old one
+    /// 
+    /// Every ownership change goes through here: it clears any pending
+    /// transfer and the external verification badge, since a new owner
+    /// has not proven control of the real DNS name, and restarts
+    /// `owned_since`, which voids listings the domain had before.
+    pub fn hand_over(&mut self, new_authority: Pubkey, now: i64) {
+        self.authority = new_authority;
+        self.owned_since = now;
+        self.pending_authority = None;
+        self.verified_external = false;
+    }
+
+    /// When the current authority took over, for voiding stale listings
+    /// 
+    /// Accounts not upgraded to V9 read `owned_since` as zero, so they
+    /// fall back to the registration time.
+    pub fn owned_since(&self) -> i64 {
+        self.owned_since.max(self.created_at)
+    }
+
+    /// Where the registration stands at `now`
+    /// 
+    /// Accounts from before layout V7 have no expiry yet and stay active
+    /// until upgraded.
+    pub fn phase(&self, now: i64) -> DomainPhase {
+        if self.layout_version < DOMAIN_LAYOUT_V7 || now < self.expires_at {
+            DomainPhase::Active
+        } else if now < self.expires_at + GRACE_PERIOD {
+            DomainPhase::Grace
+        } else if now < self.expires_at + GRACE_PERIOD + REDEMPTION_PERIOD {
+            DomainPhase::Redemption
+        } else {
+            DomainPhase::Expired
+        }
+    }
+}
+
+/// DID document data for a domain
+/// 
+/// # Fields
+/// * `domain` - Domain account this DID belongs to
+/// * `verification_keys` - Extra Ed25519 verification methods (max 4)
+/// * `document_uri` - Optional pointer to a full DID document
+/// * `updated_at` - Unix timestamp of the last change
+/// * `bump` - PDA bump seed
+/// 
+/// # Notes
+/// - The domain authority is always the DID controller
+#[account]
+#[derive(InitSpace)]
+pub struct DidRecord {
+    pub domain: Pubkey,
+    #[max_len(4)]
+    pub verification_keys: Vec<Pubkey>,
+    #[max_len(200)]
+    pub document_uri: String,
+    pub updated_at: i64,
+    pub bump: u8,
+}
+
+/// Optional features the admin can switch off
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug)]
+pub enum Feature {
+    Marketplace,
+    Escrow,
+    Leasing,
+    Wrapping,
+    Subdomains,
+}
+
+impl Feature {
+    /// Bit of this feature in `ProgramConfig::disabled_features`
+    fn bit(self) -> u16 {
+        1 << (self as u8)
+    }
+}
+
+/// Program-wide configuration singleton
+/// 
+/// # Fields
+/// * `admin` - Key allowed to change configuration
+/// * `verifier` - Oracle key that confirms external verification
+/// * `bump` - PDA bump seed
+/// * `treasury` - Account credited with registration and renewal fees
+/// * `registration_fee` - Lamports charged per registration
+/// * `renewal_fee` - Lamports charged per yearly renewal
+/// * `fee_mint` - SPL mint fees may also be paid in (e.g. USDC), if any
+/// * `token_registration_fee` - Fee mint base units per registration
+/// * `token_renewal_fee` - Fee mint base units per yearly renewal
+/// * `premium_multipliers` - Fee multiple for 1-4 character names
+/// * `require_commitment` - Only `reveal_and_register` may register names
+/// * `auction_max_len` - Names this short must be auctioned (0 = off)
+/// * `auction_min_bid` - Smallest opening bid in lamports
+/// * `auction_duration` - Seconds an auction's bidding stays open
+/// * `release_start_premium` - Lamports a reclaimed name costs extra at
+///   release, decaying to zero over 21 days
+/// * `disabled_features` - `Feature` bits the admin turned off (0 = all on)
+/// * `paused` - Emergency stop for registrations, updates and transfers
+/// * `pending_admin` - Nominee who can `accept_admin`, if any
+#[account]
+#[derive(InitSpace)]
+pub struct ProgramConfig {
+    pub admin: Pubkey,
+    pub verifier: Pubkey,
+    pub bump: u8,
+    pub treasury: Pubkey,
+    pub registration_fee: u64,
+    pub renewal_fee: u64,
+    pub fee_mint: Option<Pubkey>,
+    pub token_registration_fee: u64,
+    pub token_renewal_fee: u64,
+    pub premium_multipliers: [u32; PREMIUM_TIERS],
+    pub require_commitment: bool,
+    pub auction_max_len: u8,
+    pub auction_min_bid: u64,
+    pub auction_duration: i64,
+    pub release_start_premium: u64,
+    pub disabled_features: u16,
+    pub paused: bool,
+    pub pending_admin: Option<Pubkey>,
+}
+
+impl ProgramConfig {
+    /// Fail with `ProgramPaused` while the admin has paused the program
+    pub fn require_not_paused(&self) -> Result<()> {
+        require!(!self.paused, DnsError::ProgramPaused);
+        Ok(())
+    }
+
+    /// Fail with `FeatureDisabled` if the admin turned `feature` off
+    pub fn require_feature(&self, feature: Feature) -> Result<()> {
+        require!(
+            self.disabled_features & feature.bit() == 0,
+            DnsError::FeatureDisabled
+        );
+        Ok(())
+    }
+
+    /// Fee for a name after its length premium
+    /// 
+    /// Short leftmost labels pay `premium_multipliers[len - 1]` times the
+    /// base fee (0 counts as 1x); names of 5+ characters pay the base fee.
+    pub fn premium_price(&self, domain_name: &str, base_fee: u64) -> u64 {
+        let label_len = domain_name.split('.').next().map_or(0, str::len);
+        match label_len {
+            1..=PREMIUM_TIERS => {
+                let multiplier = self.premium_multipliers[label_len - 1].max(1);
+                base_fee.saturating_mul(u64::from(multiplier))
+            }
+            _ => base_fee,
+        }
+    }
+
+    /// Whether a name may only be registered by winning an auction
+    pub fn requires_auction(&self, domain_name: &str) -> bool {
+        let label_len = domain_name.split('.').next().map_or(0, str::len);
+        label_len <= self.auction_max_len as usize
+    }
+}
+
+/// How an external verification challenge is proven
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, InitSpace)]
+pub enum VerificationMethod {
+    /// TXT record at `_neura-challenge.<domain>` in traditional DNS
+    DnsTxt,
+    /// DKIM-signed email from the domain carrying the challenge
+    DkimEmail,
+}
+
+/// Pending external verification challenge
+/// 
+/// # Fields
+/// * `domain` - Domain account being verified
+/// * `authority` - Domain owner who opened the request
+/// * `method` - How the challenge will be proven
+/// * `challenge` - Random-looking bytes the owner must publish (hex)
+/// * `requested_at` - Unix timestamp of the request
+/// * `bump` - PDA bump seed
+#[account]
+#[derive(InitSpace)]
+pub struct VerificationRequest {
+    pub domain: Pubkey,
+    pub authority: Pubkey,
+    pub method: VerificationMethod,
+    pub challenge: [u8; 16],
+    pub requested_at: i64,
+    pub bump: u8,
+}
+
+/// OpenPGP public key published for one email address
+/// 
+/// # Fields
+/// * `domain` - Domain account the address belongs to
+/// * `localpart_hash` - SHA2-256 of the local part, truncated to 28 bytes
+/// * `key` - Binary transferable public key (max 900 bytes)
+/// * `updated_at` - Unix timestamp of the last change
+/// * `bump` - PDA bump seed
+/// 
+/// # Notes
+/// - A record last updated before the domain's `created_at` belongs to
+///   a previous registration of the name and is not served
+#[account]
+#[derive(InitSpace)]
+pub struct OpenPgpKeyRecord {
+    pub domain: Pubkey,
+    pub localpart_hash: [u8; 28],
+    #[max_len(900)]
+    pub key: Vec<u8>,
+    pub updated_at: i64,
+    pub bump: u8,
+}
+
+/// One SSHFP fingerprint
+/// 
+/// # Fields
+/// * `algorithm` - SSH key algorithm number
+/// * `fingerprint_type` - Digest algorithm number
+/// * `fingerprint` - Raw digest bytes (20 or 32)
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, InitSpace)]
+pub struct SshfpEntry {
+    pub algorithm: u8,
+    pub fingerprint_type: u8,
+    #[max_len(32)]
+    pub fingerprint: Vec<u8>,
+}
+
+/// SSHFP fingerprints published for a domain
+/// 
+/// # Fields
+/// * `domain` - Domain account the host keys belong to
+/// * `entries` - Up to 8 fingerprints, unique per (algorithm, type)
+/// * `bump` - PDA bump seed
+#[account]
+#[derive(InitSpace)]
+pub struct SshfpRecords {
+    pub domain: Pubkey,
+    #[max_len(8)]
+    pub entries: Vec<SshfpEntry>,
+    pub bump: u8,
+}
+
+/// One DS record
+/// 
+/// # Fields
+/// * `key_tag` - Key tag of the DNSKEY the digest covers
+/// * `algorithm` - DNSKEY algorithm number
+/// * `digest_type` - Digest algorithm number
+/// * `digest` - Raw digest bytes (32 or 48)
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, InitSpace)]
+pub struct DsEntry {
+    pub key_tag: u16,
+    pub algorithm: u8,
+    pub digest_type: u8,
+    #[max_len(48)]
+    pub digest: Vec<u8>,
+}
+
+/// One DNSKEY record
+/// 
+/// # Fields
+/// * `flags` - 256 (ZSK) or 257 (KSK)
+/// * `protocol` - Always 3
+/// * `algorithm` - DNSKEY algorithm number
+/// * `key_tag` - Computed key tag (RFC 4034 Appendix B)
+/// * `public_key` - Key in DNSKEY wire format
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, InitSpace)]
+pub struct DnskeyEntry {
+    pub flags: u16,
+    pub protocol: u8,
+    pub algorithm: u8,
+    pub key_tag: u16,
+    #[max_len(261)]
+    pub public_key: Vec<u8>,
+}
+
+/// DNSSEC records published for a domain
+/// 
+/// # Fields
+/// * `domain` - Domain account the zone belongs to
+/// * `ds` - Up to 4 DS records, unique per (key_tag, algorithm, digest_type)
+/// * `dnskeys` - Up to 4 DNSKEY records, unique per key
+/// * `updated_at` - Unix timestamp of the last change
+/// * `bump` - PDA bump seed
+#[account]
+#[derive(InitSpace)]
+pub struct DnssecRecords {
+    pub domain: Pubkey,
+    #[max_len(4)]
+    pub ds: Vec<DsEntry>,
+    #[max_len(4)]
+    pub dnskeys: Vec<DnskeyEntry>,
+    pub updated_at: i64,
+    pub bump: u8,
+}
+
+impl DnssecRecords {
+    /// Take the account over for the domain's current registration,
+    /// dropping records left from a previous one, and stamp the change
+    fn claim(&mut self, domain: &Account<DomainRecord>, bump: u8, now: i64) {
+        if self.updated_at < domain.created_at {
+            self.ds.clear();
+            self.dnskeys.clear();
+        }
+        self.domain = domain.key();
+        self.updated_at = now;
+        self.bump = bump;
+    }
+}
+
+/// Verifiable claim a third party attaches to a domain
+/// 
+/// # Fields
+/// * `domain` - Domain account the claim is about
+/// * `attester` - Key that signed the claim
+/// * `claim_type` - Short claim identifier (max 32 chars)
+/// * `claim_uri` - Location of the full credential (max 200 chars)
+/// * `claim_hash` - SHA-256 of the full credential document
+/// * `issued_at` - Unix timestamp the claim was attached
+/// * `expires_at` - Optional expiry of the claim
+/// * `accepted` - Domain owner has accepted the claim
+/// * `bump` - PDA bump seed
+/// 
+/// # Notes
+/// - Verifiers should check `claim_hash` against the fetched credential
+/// - Ignore claims with `issued_at` before the domain's `created_at`;
+///   they were made about a previous registration of the name
+#[account]
+#[derive(InitSpace)]
+pub struct Attestation {
+    pub domain: Pubkey,
+    pub attester: Pubkey,
+    #[max_len(32)]
+    pub claim_type: String,
+    #[max_len(200)]
+    pub claim_uri: String,
+    pub claim_hash: [u8; 32],
+    pub issued_at: i64,
+    pub expires_at: Option<i64>,
+    pub accepted: bool,
+    pub bump: u8,
+}
+
+/// Record whose value is pushed by a designated updater
+/// 
+/// # Fields
+/// * `domain` - Domain account this record overrides
+/// * `updater` - Key allowed to push values
+/// * `max_staleness` - Seconds a pushed value stays valid
+/// * `value` - Latest IPv4 address pushed
+/// * `updated_at` - Unix timestamp of the last push
+/// * `bump` - PDA bump seed
+/// 
+/// # Notes
+/// - Resolution falls back to the static record once the value is stale
+#[account]
+#[derive(InitSpace)]
+pub struct DynamicRecord {
+    pub domain: Pubkey,
+    pub updater: Pubkey,
+    pub max_staleness: u32,
+    #[max_len(15)]
+    pub value: String,
+    pub updated_at: i64,
+    pub bump: u8,
+}
+
+impl DynamicRecord {
+    /// Whether the last pushed value is still within its freshness bound
+    pub fn is_fresh(&self, now: i64) -> bool {
+        now.saturating_sub(self.updated_at) <= self.max_staleness as i64
+    }
+}
+
+/// Head of the namespace index
+/// 
+/// # Fields
+/// * `page_count` - Number of index pages; the tail is `page_count - 1`
+/// * `total_domains` - Domains listed across all pages
+/// * `bump` - PDA bump seed
+#[account]
+#[derive(InitSpace)]
+pub struct NamespaceIndex {
+    pub page_count: u32,
+    pub total_domains: u64,
+    pub bump: u8,
+}
+
+/// Fixed-capacity page of registered domain accounts
+/// 
+/// # Fields
+/// * `page` - Page number (also its PDA seed)
+/// * `bump` - PDA bump seed
+/// * `next` - Following page, once the tail fills up
+/// * `domains` - Domain account addresses in registration order (max 64)
+/// 
+/// # Notes
+/// - Explorers walk from page 0 via `next` (or derive pages by number)
+///   instead of scanning with getProgramAccounts
+#[account]
+#[derive(InitSpace)]
+pub struct IndexPage {
+    pub page: u32,
+    pub bump: u8,
+    pub next: Option<Pubkey>,
+    #[max_len(64)]
+    pub domains: Vec<Pubkey>,
+}
+
+/// Probabilistic availability index over registered name hashes
+/// 
+/// # Fields
+/// * `item_count` - Names inserted since the last rebuild
+/// * `generation` - Incremented on each rebuild
+/// * `rebuilding` - 1 while a rebuild is in progress (filter incomplete)
+/// * `bump` - PDA bump seed
+/// * `bits` - 65,536-bit Bloom filter
+/// 
+/// # Notes
+/// - Zero-copy: 8 KB of bits would overflow the stack under Borsh
+/// - Any probe bit unset means the name is definitely available
+/// - All bits set means "maybe taken"; confirm by reading the domain PDA
+#[account(zero_copy)]
+pub struct AvailabilityFilter {
+    pub item_count: u64,
+    pub generation: u32,
+    pub rebuilding: u8,
+    pub bump: u8,
+    pub padding: [u8; 2],
+    pub bits: [u8; BLOOM_FILTER_BYTES],
+}
+
+impl AvailabilityFilter {
+    /// Bit positions probed for a name hash
+    fn probes(name_hash: &[u8; 32]) -> [usize; BLOOM_HASHES] {
+        let mut probes = [0usize; BLOOM_HASHES];
+        for (i, probe) in probes.iter_mut().enumerate() {
+            *probe = u16::from_le_bytes([name_hash[2 * i], name_hash[2 * i + 1]]) as usize;
+        }
+        probes
+    }
+
+    /// Record a name hash as registered
+    pub fn insert(&mut self, name_hash: &[u8; 32]) {
+        for bit in Self::probes(name_hash) {
+            self.bits[bit / 8] |= 1 << (bit % 8);
+        }
+        self.item_count += 1;
+    }
+
+    /// Whether a name hash may be registered (false means definitely not)
+    pub fn may_contain(&self, name_hash: &[u8; 32]) -> bool {
+        Self::probes(name_hash)
+            .iter()
+            .all(|&bit| self.bits[bit / 8] & (1 << (bit % 8)) != 0)
+    }
+}
+
+/// Emitted when a domain account moves to a newer storage layout
+#[event]
+pub struct DomainAccountUpgraded {
+    pub domain: Pubkey,
+    pub from_version: u8,
+    pub to_version: u8,
+    pub space: u32,
+}
+
+/// Emitted when a domain is registered
+#[event]
+pub struct DomainRegistered {
+    pub domain: Pubkey,
+    pub domain_name: String,
+    pub record: String,
+    pub record_type: RecordType,
+    pub ttl: u32,
+    pub authority: Pubkey,
+    pub index_page: u32,
+    pub slot: u64,
+}
+
+/// Emitted when a domain's primary record is replaced
+#[event]
+pub struct DomainUpdated {
+    pub domain: Pubkey,
+    pub authority: Pubkey,
+    pub record: String,
+    pub record_type: RecordType,
+    pub ttl: u32,
+    pub slot: u64,
+}
+
+/// Activity counters for one epoch (or lifetime totals)
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Default, InitSpace)]
+pub struct EpochStats {
+    pub epoch: u64,
+    pub registrations: u32,
+    pub renewals: u32,
+    pub deletions: u32,
+    pub fee_volume: u64,
+}
+
+/// Rolled-up protocol activity for dashboards
+/// 
+/// # Fields
+/// * `lifetime` - Totals since initialization (`epoch` unused)
+/// * `history` - Per-epoch counters, slot `epoch % STATS_HISTORY_EPOCHS`
+/// * `registrant_sketch` - HyperLogLog registers over registrant keys
+/// * `bump` - PDA bump seed
+/// 
+/// # Notes
+/// - A history slot is reset the first time a new epoch writes to it,
+///   so epochs with no activity leave no entry
+/// - Unique registrants ≈ 0.709 * 64² / Σ 2^-register (standard HLL,
+///   with linear counting while many registers are still zero)
+#[account]
+#[derive(InitSpace)]
+pub struct ProtocolStats {
+    pub lifetime: EpochStats,
+    pub history: [EpochStats; STATS_HISTORY_EPOCHS],
+    pub registrant_sketch: [u8; REGISTRANT_SKETCH_REGISTERS],
+    pub bump: u8,
+}
+
+impl ProtocolStats {
+    /// Counters for `epoch`, resetting its ring slot if it held an older epoch
+    fn epoch_mut(&mut self, epoch: u64) -> &mut EpochStats {
+        let slot = &mut self.history[(epoch % STATS_HISTORY_EPOCHS as u64) as usize];
+        if slot.epoch != epoch {
+            *slot = EpochStats { epoch, ..EpochStats::default() };
+        }
+        slot
+    }
+
+    pub fn record_registration(&mut self, epoch: u64, registrant: &Pubkey) {
+        self.lifetime.registrations = self.lifetime.registrations.saturating_add(1);
+        let current = self.epoch_mut(epoch);
+        current.registrations = current.registrations.saturating_add(1);
+
+        let h = hash(registrant.as_ref()).to_bytes();
+        let register = h[0] as usize % REGISTRANT_SKETCH_REGISTERS;
+        let mut w = [0u8; 8];
+        w.copy_from_slice(&h[1..9]);
+        let rank = (u64::from_le_bytes(w).leading_zeros() + 1) as u8;
+        if rank > self.registrant_sketch[register] {
+            self.registrant_sketch[register] = rank;
+        }
+    }
+
+    pub fn record_renewal(&mut self, epoch: u64) {
+        self.lifetime.renewals = self.lifetime.renewals.saturating_add(1);
+        let current = self.epoch_mut(epoch);
+        current.renewals = current.renewals.saturating_add(1);
+    }
+
+    pub fn record_deletion(&mut self, epoch: u64) {
+        self.lifetime.deletions = self.lifetime.deletions.saturating_add(1);
+        let current = self.epoch_mut(epoch);
+        current.deletions = current.deletions.saturating_add(1);
+    }
+
+    pub fn record_fee(&mut self, epoch: u64, lamports: u64) {
+        self.lifetime.fee_volume = self.lifetime.fee_volume.saturating_add(lamports);
+        let current = self.epoch_mut(epoch);
+        current.fee_volume = current.fee_volume.saturating_add(lamports);
+    }
+}
+
+/// Emitted when a domain changes owner
+#[event]
+pub struct DomainTransferred {
+    pub domain: Pubkey,
+    pub from: Pubkey,
+    pub to: Pubkey,
+    pub timestamp: i64,
+    pub slot: u64,
+}
+
+/// Emitted when a domain's operator is set (Some) or revoked (None)
+#[event]
+pub struct OperatorChanged {
+    pub domain: Pubkey,
+    pub operator: Option<Pubkey>,
+    pub slot: u64,
+}
+
+/// Emitted when a key's record type rights are set, or revoked (empty
+/// `record_types`)
+#[event]
+pub struct RecordAclChanged {
+    pub domain: Pubkey,
+    pub key: Pubkey,
+    pub record_types: Vec<RecordType>,
+    pub slot: u64,
+}
+
+/// Emitted when an update timelock is set (`delay`), its removal is
+/// requested (`removed_at`), or it is removed (both None)
+#[event]
+pub struct UpdateTimelockChanged {
+    pub domain: Pubkey,
+    pub delay: Option<i64>,
+    pub removed_at: Option<i64>,
+    pub slot: u64,
+}
+
+/// Emitted when a record change is scheduled (`executes_at` set) or
+/// cancelled (None)
+#[event]
+pub struct ScheduledUpdateChanged {
+    pub domain: Pubkey,
+    pub record: String,
+    pub record_type: RecordType,
+    pub ttl: u32,
+    pub executes_at: Option<i64>,
+    pub slot: u64,
+}
+
+/// Emitted when a domain is locked or unlocked, or an unlock is requested
+/// (`unlocks_at` set) or cancelled
+#[event]
+pub struct DomainLockChanged {
+    pub domain: Pubkey,
+    pub locked: bool,
+    pub unlocks_at: Option<i64>,
+    pub slot: u64,
+}
+
+/// DNS record types a domain record can hold
+/// 
+/// # Notes
+/// - Serialized as a single byte; `A` is 0 so zeroed storage reads as A
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, InitSpace)]
+pub enum RecordType {
+    A,
+    Aaaa,
+    Cname,
+    Txt,
+    Mx,
+    Ns,
+    /// Solana wallet address for payments, base58
+    Sol,
+    /// Decentralized site content: "ipfs://<CID>", "ipns://<CID>" or
+    /// "ar://<transaction id>"
+    ContentHash,
+}
+
+impl RecordType {
+    /// Presentation-format type name (e.g. "AAAA")
+    pub fn mnemonic(&self) -> &'static str {
+        match self {
+            RecordType::A => "A",
+            RecordType::Aaaa => "AAAA",
+            RecordType::Cname => "CNAME",
+            RecordType::Txt => "TXT",
+            RecordType::Mx => "MX",
+            RecordType::Ns => "NS",
+            RecordType::Sol => "SOL",
+            RecordType::ContentHash => "CONTENTHASH",
+        }
+    }
+}
+
+/// Return data of `resolve_did`
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct ResolvedDid {
+    pub did: String,
+    pub controller: Pubkey,
+    pub verification_keys: Vec<Pubkey>,
+    pub document_uri: String,
+}
+
+/// Return data of `resolve_dnssec`
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct ResolvedDnssec {
+    pub ds: Vec<DsEntry>,
+    pub dnskeys: Vec<DnskeyEntry>,
+}
+
+/// One mail exchanger of a domain, as returned by `resolve_mx`
+/// 
+/// # Fields
+/// * `priority` - MX preference; lower values are tried first
+/// * `exchange` - Domain name of the mail server
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct MxExchange {
+    pub priority: u16,
+    pub exchange: String,
+}
+
+/// Return data of `resolve_srv`
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct ResolvedSrv {
+    pub targets: Vec<SrvTarget>,
+    pub ttl: u32,
+}
+
+/// Return data of `resolve_domain`
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct ResolvedRecord {
+    pub record_type: RecordType,
+    pub value: String,
+    pub ttl: u32,
+}
+
+/// Additional values of one record type for a domain
+/// 
+/// # Fields
+/// * `domain` - Domain account the set belongs to
+/// * `record_type` - Type of every value in the set
+/// * `values` - Up to 8 values, unique, validated for `record_type`
+/// * `updated_at` - Unix timestamp of the last change
+/// * `bump` - PDA bump seed
+/// 
+/// # Notes
+/// - Resolvers serve these together with the domain's primary record
+///   when its `record_type` matches
+#[account]
+#[derive(InitSpace)]
+pub struct RecordSet {
+    pub domain: Pubkey,
+    pub record_type: RecordType,
+    #[max_len(8, 255)]
+    pub values: Vec<String>,
+    pub updated_at: i64,
+    pub bump: u8,
+}
+
+impl RecordSet {
+    /// Fixed-size fields: domain, record_type, values length prefix,
+    /// updated_at, bump
+    const FIXED_SPACE: usize = 32 + 1 + 4 + 8 + 1;
+
+    /// Account size (including discriminator) for the given values
+    /// 
+    /// Sized from the values actually stored, so a set of two A records
+    /// pays rent on ~90 bytes instead of room for 8 maximum-length ones.
+    pub fn space_for(values: &[String]) -> usize {
+        8 + Self::FIXED_SPACE + values.iter().map(|value| 4 + value.len()).sum::<usize>()
+    }
+}
+
+/// Secondary key allowed to update a domain's record
+/// 
+/// # Fields
+/// * `domain` - Domain account the grant is for
+/// * `operator` - Key that may call `update_record`
+/// * `granted_by` - Authority that set the operator
+/// * `granted_at` - Unix timestamp of the grant
+/// * `bump` - PDA bump seed
+#[account]
+#[derive(InitSpace)]
+pub struct OperatorGrant {
+    pub domain: Pubkey,
+    pub operator: Pubkey,
+    pub granted_by: Pubkey,
+    pub granted_at: i64,
+    pub bump: u8,
+}
+
+impl OperatorGrant {
+    /// Whether `signer` may update `domain` under this grant
+    /// 
+    /// Only grants made by the current owner since the current
+    /// registration count, so a transfer or re-registration revokes them.
+    pub fn allows(&self, domain: &DomainRecord, signer: &Pubkey) -> bool {
+        self.operator == *signer
+            && self.granted_by == domain.authority
+            && self.granted_at >= domain.created_at
+    }
+}
+
+/// Most keys one domain's record type ACL holds
+const MAX_ACL_ENTRIES: usize = 8;
+
+/// Record types one key may write
+/// 
+/// # Fields
+/// * `key` - Key holding the rights
+/// * `record_types` - Bit `1 << record_type` set for each allowed type
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, InitSpace)]
+pub struct AclEntry {
+    pub key: Pubkey,
+    pub record_types: u16,
+}
+
+/// Per-record-type rights over a domain's record
+/// 
+/// # Fields
+/// * `domain` - Domain account the ACL is for
+/// * `entries` - Up to 8 keys and their record types
+/// * `granted_by` - Authority that last changed the ACL
+/// * `granted_at` - Unix timestamp of the last change
+/// * `bump` - PDA bump seed
+#[account]
+#[derive(InitSpace)]
+pub struct RecordAcl {
+    pub domain: Pubkey,
+    #[max_len(8)]
+    pub entries: Vec<AclEntry>,
+    pub granted_by: Pubkey,
+    pub granted_at: i64,
+    pub bump: u8,
+}
+
+impl RecordAcl {
+    /// ACL bit for a record type
+    fn bit(record_type: RecordType) -> u16 {
+        1 << (record_type as u8)
+    }
+
+    /// Whether the ACL was set by the current owner since the current
+    /// registration, as for `OperatorGrant`
+    fn is_current(&self, domain: &DomainRecord) -> bool {
+        self.granted_by == domain.authority && self.granted_at >= domain.created_at
+    }
+
+    /// Whether `signer` may write records of `record_type` to `domain`
+    pub fn allows(&self, domain: &DomainRecord, signer: &Pubkey, record_type: RecordType) -> bool {
+        self.is_current(domain)
+            && self
+                .entries
+                .iter()
+                .any(|entry| entry.key == *signer && entry.record_types & Self::bit(record_type) != 0)
+    }
+}
+
+/// Threshold multisig guarding a domain's high-risk actions
+/// 
+/// # Fields
+/// * `domain` - Domain account governed
+/// * `authority` - Owner that set the governance
+/// * `members` - Keys that can approve
+/// * `threshold` - Member signatures required
+/// * `created_at` - Unix timestamp it was set
+/// * `bump` - PDA bump seed
+#[account]
+#[derive(InitSpace)]
+pub struct DomainGovernance {
+    pub domain: Pubkey,
+    pub authority: Pubkey,
+    #[max_len(10)]
+    pub members: Vec<Pubkey>,
+    pub threshold: u8,
+    pub created_at: i64,
+    pub bump: u8,
+}
+
+impl DomainGovernance {
+    /// Whether this governance binds `domain`'s current owner
+    pub fn applies_to(&self, domain: &DomainRecord) -> bool {
+        self.authority == domain.authority && self.created_at >= domain.created_at
+    }
+
+    /// Number of members among `signers` that signed
+    pub fn approvals(&self, signers: &[AccountInfo]) -> u8 {
+        self.members
+            .iter()
+            .filter(|member| {
+                signers
+                    .iter()
+                    .any(|signer| signer.is_signer && signer.key == *member)
+            })
+            .count() as u8
+    }
+}
+
+/// Keys that can jointly recover a domain whose owner lost its key
+/// 
+/// # Fields
+/// * `domain` - Domain account guarded
+/// * `set_by` - Owner that named the guardians
+/// * `guardians` - Up to 10 guardian keys
+/// * `threshold` - Guardians needed to start a recovery
+/// * `set_at` - Unix timestamp the set was last changed
+/// * `bump` - PDA bump seed
+#[account]
+#[derive(InitSpace)]
+pub struct GuardianSet {
+    pub domain: Pubkey,
+    pub set_by: Pubkey,
+    #[max_len(10)]
+    pub guardians: Vec<Pubkey>,
+    pub threshold: u8,
+    pub set_at: i64,
+    pub bump: u8,
+}
+
+impl GuardianSet {
+    /// Whether these guardians were named by `domain`'s current owner
+    pub fn applies_to(&self, domain: &DomainRecord) -> bool {
+        self.set_by == domain.authority && self.set_at >= domain.created_at
+    }
+
+    /// Number of guardians among `signers` that signed
+    pub fn approvals(&self, signers: &[AccountInfo]) -> u8 {
+        self.guardians
+            .iter()
+            .filter(|guardian| {
+                signers
+                    .iter()
+                    .any(|signer| signer.is_signer && signer.key == *guardian)
+            })
+            .count() as u8
+    }
+}
+
+/// Recovery of a domain to a new key, waiting out its challenge period
+/// 
+/// # Fields
+/// * `domain` - Domain account being recovered
+/// * `owner` - Owner at the start, who can veto
+/// * `new_authority` - Key the domain moves to
+/// * `initiated_by` - Payer of the request's rent
+/// * `started_at` - Unix timestamp the recovery began
+/// * `bump` - PDA bump seed
+#[account]
+#[derive(InitSpace)]
+pub struct RecoveryRequest {
+    pub domain: Pubkey,
+    pub owner: Pubkey,
+    pub new_authority: Pubkey,
+    pub initiated_by: Pubkey,
+    pub started_at: i64,
+    pub bump: u8,
+}
+
+impl RecoveryRequest {
+    /// Whether this request targets `domain`'s current owner and registration
+    pub fn applies_to(&self, domain: &DomainRecord) -> bool {
+        self.owner == domain.authority && self.started_at >= domain.created_at
+    }
+}
+
+/// Dead-man switch handing a domain to a beneficiary
+/// 
+/// # Fields
+/// * `domain` - Domain account covered
+/// * `owner` - Owner that named the beneficiary
+/// * `beneficiary` - Key that may claim the domain
+/// * `period` - Seconds without a heartbeat before it may
+/// * `set_at` - Unix timestamp the beneficiary was named
+/// * `last_heartbeat` - Unix timestamp of the owner's last heartbeat
+/// * `bump` - PDA bump seed
+#[account]
+#[derive(InitSpace)]
+pub struct Inheritance {
+    pub domain: Pubkey,
+    pub owner: Pubkey,
+    pub beneficiary: Pubkey,
+    pub period: i64,
+    pub set_at: i64,
+    pub last_heartbeat: i64,
+    pub bump: u8,
+}
+
+impl Inheritance {
+    /// Whether `domain`'s current owner named this beneficiary during
+    /// the current registration
+    pub fn applies_to(&self, domain: &DomainRecord) -> bool {
+        self.owner == domain.authority && self.set_at >= domain.created_at
+    }
+}
+
+/// Emitted when a beneficiary is set, a heartbeat pushes the claim date
+/// back, or the beneficiary is removed (both None)
+#[event]
+pub struct InheritanceChanged {
+    pub domain: Pubkey,
+    pub beneficiary: Option<Pubkey>,
+    pub claimable_at: Option<i64>,
+    pub slot: u64,
+}
+
+/// Lifecycle step reported by `RecoveryChanged`
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq)]
+pub enum RecoveryStatus {
+    Started,
+    Vetoed,
+    Completed,
+}
+
+/// Emitted when a recovery is started, vetoed or completed
+#[event]
+pub struct RecoveryChanged {
+    pub domain: Pubkey,
+    pub new_authority: Pubkey,
+    pub completes_at: i64,
+    pub status: RecoveryStatus,
+    pub slot: u64,
+}
+
+/// Freeze on a domain's record and ownership
+/// 
+/// # Fields
+/// * `domain` - Domain account locked
+/// * `locked_by` - Owner that set the lock
+/// * `locked_at` - Unix timestamp it was set
+/// * `unlock_delay` - Seconds from `request_unlock` until it lifts
+/// * `unlock_requested_at` - Unix timestamp of `request_unlock` (0 = none)
+/// * `bump` - PDA bump seed
+#[account]
+#[derive(InitSpace)]
+pub struct DomainLock {
+    pub domain: Pubkey,
+    pub locked_by: Pubkey,
+    pub locked_at: i64,
+    pub unlock_delay: i64,
+    pub unlock_requested_at: i64,
+    pub bump: u8,
+}
+
+impl DomainLock {
+    /// Whether this lock binds `domain`'s current registration
+    /// 
+    /// Reclaiming an expired domain hands it to a new registrant, whom an
+    /// old lock must not bind.
+    pub fn applies_to(&self, domain: &DomainRecord) -> bool {
+        self.locked_by == domain.authority && self.locked_at >= domain.created_at
+    }
+
+    /// Whether the lock is in force at `now`
+    pub fn is_locked(&self, now: i64) -> bool {
+        self.unlock_requested_at == 0 || now < self.unlock_requested_at + self.unlock_delay
+    }
+}
+
+/// Opt-in delay on a domain's record updates
+/// 
+/// # Fields
+/// * `domain` - Domain account timelocked
+/// * `set_by` - Owner that set the timelock
+/// * `set_at` - Unix timestamp it was last set
+/// * `delay` - Seconds a scheduled update waits before it can apply
+/// * `removal_requested_at` - Unix timestamp of `request_timelock_removal`
+///   (0 = none)
+/// * `bump` - PDA bump seed
+#[account]
+#[derive(InitSpace)]
+pub struct UpdateTimelock {
+    pub domain: Pubkey,
+    pub set_by: Pubkey,
+    pub set_at: i64,
+    pub delay: i64,
+    pub removal_requested_at: i64,
+    pub bump: u8,
+}
+
+impl UpdateTimelock {
+    /// Whether this timelock binds `domain`'s current registration
+    pub fn applies_to(&self, domain: &DomainRecord) -> bool {
+        self.set_by == domain.authority && self.set_at >= domain.created_at
+    }
+
+    /// Whether the timelock is in force at `now`
+    pub fn is_in_force(&self, now: i64) -> bool {
+        self.removal_requested_at == 0 || now < self.removal_requested_at + self.delay
+    }
+}
+
+/// Record change waiting out a domain's update timelock
+/// 
+/// # Fields
+/// * `domain` - Domain account the change is for
+/// * `record`, `record_type`, `ttl` - The new record
+/// * `scheduled_by` - Owner that scheduled it
+/// * `scheduled_at` - Unix timestamp it was scheduled
+/// * `executes_at` - Unix timestamp from which `execute_update` applies it
+/// * `bump` - PDA bump seed
+#[account]
+#[derive(InitSpace)]
+pub struct PendingUpdate {
+    pub domain: Pubkey,
+    #[max_len(255)]
+    pub record: String,
+    pub record_type: RecordType,
+    pub ttl: u32,
+    pub scheduled_by: Pubkey,
+    pub scheduled_at: i64,
+    pub executes_at: i64,
+    pub bump: u8,
+}
+
+/// Past primary records of a domain, as a ring buffer
+/// 
+/// # Fields
+/// * `domain` - Domain account the history belongs to
+/// * `entries` - Last 8 values, overwritten oldest first once full
+/// * `next` - Slot the next entry is written to
+/// * `total` - Entries ever written (the newest has sequence `total - 1`)
+/// * `bump` - PDA bump seed
+#[account]
+#[derive(InitSpace)]
+pub struct RecordHistory {
+    pub domain: Pubkey,
+    #[max_len(8)]
+    pub entries: Vec<HistoryEntry>,
+    pub next: u8,
+    pub total: u64,
+    pub bump: u8,
+}
+
+impl RecordHistory {
+    /// Append an entry, overwriting the oldest once the buffer is full
+    pub fn push(&mut self, entry: HistoryEntry) {
+        if self.entries.len() < RECORD_HISTORY_LEN {
+            self.entries.push(entry);
+        } else {
+            self.entries[self.next as usize] = entry;
+        }
+        self.next = ((self.next as usize + 1) % RECORD_HISTORY_LEN) as u8;
+        self.total = self.total.saturating_add(1);
+    }
+
+    /// Entries from newest to oldest
+    pub fn newest_first(&self) -> impl Iterator<Item = &HistoryEntry> {
+        let len = self.entries.len();
+        let newest = self.next as usize + len;
+        (1..=len).map(move |i| &self.entries[(newest - i) % len])
+    }
+}
+
+/// One value a domain's primary record held
+/// 
+/// # Fields
+/// * `record_type`, `record`, `ttl` - The record as written
+/// * `updated_at` - Unix timestamp it took effect
+/// * `updated_by` - Signer that wrote it (the owner at registration time
+///   for back-filled entries)
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, InitSpace)]
+pub struct HistoryEntry {
+    pub record_type: RecordType,
+    #[max_len(255)]
+    pub record: String,
+    pub ttl: u32,
+    pub updated_at: i64,
+    pub updated_by: Pubkey,
+}
+
+impl HistoryEntry {
+    /// Borsh-encoded size, for budgeting return data
+    fn serialized_len(&self) -> usize {
+        1 + 4 + self.record.len() + 4 + 8 + 32
+    }
+}
+
+/// Registration phase of a domain, derived from `expires_at`
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum DomainPhase {
+    /// Resolves normally
+    Active,
+    /// Lapsed; only the owner can renew, at no extra cost
+    Grace,
+    /// Lapsed; only the owner can renew, paying the redemption fee
+    Redemption,
+    /// Anyone may `reclaim_expired` the domain
+    Expired,
+}
+
+/// Emitted when a domain is renewed
+#[event]
+pub struct DomainRenewed {
+    pub domain: Pubkey,
+    pub expires_at: i64,
+    pub fee: u64,
+    pub penalty: u64,
+}
+
+/// Emitted when a lapsed domain is reclaimed and its name freed
+#[event]
+pub struct DomainExpired {
+    pub domain: Pubkey,
+    pub domain_name: String,
+    pub authority: Pubkey,
+    pub expires_at: i64,
+    pub slot: u64,
+}
+
+/// Subdomain record stored under its parent domain
+/// 
+/// # Fields
+/// * `parent` - Parent domain account
+/// * `label` - Leftmost label; the full name is `label.parent_name`
+/// * `record` - Record value, validated for `record_type`
+/// * `record_type` - Type of `record`
+/// * `ttl` - Seconds resolvers may cache `record`
+/// * `authority` - Subdomain owner
+/// * `created_at` - Unix timestamp of registration
+/// * `updated_at` - Unix timestamp of the last change
+/// * `bump` - PDA bump seed
+#[account]
+#[derive(InitSpace)]
+pub struct SubdomainRecord {
+    pub parent: Pubkey,
+    #[max_len(63)]
+    pub label: String,
+    #[max_len(255)]
+    pub record: String,
+    pub record_type: RecordType,
+    pub ttl: u32,
+    pub authority: Pubkey,
+    pub created_at: i64,
+    pub updated_at: i64,
+    pub bump: u8,
+}
+
+/// Emitted when a subdomain is registered
+#[event]
+pub struct SubdomainRegistered {
+    pub parent: Pubkey,
+    pub subdomain: Pubkey,
+    pub label: String,
+    pub authority: Pubkey,
+}
+
+/// One subdomain in a `register_batch` call
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct BatchEntry {
+    pub label: String,
+    pub record: String,
+    pub record_type: RecordType,
+    pub ttl: u32,
+    pub owner: Pubkey,
+}
+
+/// One chunk of the admin-managed reserved/blocked name list
+/// 
+/// # Fields
+/// * `chunk` - Chunk number; holds names with `sha256(name)[0] % 16 == chunk`
+/// * `name_hashes` - sha256 of each reserved name (max 256)
+/// * `bump` - PDA bump seed
+#[account]
+#[derive(InitSpace)]
+pub struct ReservedNames {
+    pub chunk: u8,
+    #[max_len(256)]
+    pub name_hashes: Vec<[u8; 32]>,
+    pub bump: u8,
+}
+
+impl ReservedNames {
+    pub fn contains(&self, name_hash: &[u8; 32]) -> bool {
+        self.name_hashes.contains(name_hash)
+    }
+}
+
+/// Hidden intent to register a name, revealed by `reveal_and_register`
+/// 
+/// # Fields
+/// * `committer` - Wallet that must reveal
+/// * `commitment` - sha256(domain_name ‖ salt ‖ committer)
+/// * `slot` - Slot the commitment was made
+/// * `bump` - PDA bump seed
+#[account]
+#[derive(InitSpace)]
+pub struct RegistrationCommitment {
+    pub committer: Pubkey,
+    pub commitment: [u8; 32],
+    pub slot: u64,
+    pub bump: u8,
+}
+
+/// English auction for a short name
+/// 
+/// # Fields
+/// * `domain_name` - Name being auctioned
+/// * `started_by` - Opening bidder
+/// * `highest_bidder` - Current leader, whose bid is held in escrow
+/// * `highest_bid` - Leading bid in lamports
+/// * `ends_at` - Unix timestamp bidding closes (extended by late bids)
+/// * `bump` - PDA bump seed
+/// 
+/// # Notes
+/// - The escrowed bid sits in this account on top of its rent
+#[account]
+#[derive(InitSpace)]
+pub struct Auction {
+    #[max_len(253)]
+    pub domain_name: String,
+    pub started_by: Pubkey,
+    pub highest_bidder: Pubkey,
+    pub highest_bid: u64,
+    pub ends_at: i64,
+    pub bump: u8,
+}
+
+/// Emitted when an auction opens or receives a new leading bid
+#[event]
+pub struct AuctionBid {
+    pub auction: Pubkey,
+    pub domain_name: String,
+    pub bidder: Pubkey,
+    pub bid: u64,
+    pub ends_at: i64,
+    pub slot: u64,
+}
+
+/// When a reclaimed name was released, for its Dutch-auction premium
+/// 
+/// # Fields
+/// * `released_at` - Unix timestamp of the last `reclaim_expired`
+/// * `start_premium` - Premium in lamports at release
+/// * `bump` - PDA bump seed
+#[account]
+#[derive(InitSpace)]
+pub struct ReleaseRecord {
+    pub released_at: i64,
+    pub start_premium: u64,
+    pub bump: u8,
+}
+
+impl ReleaseRecord {
+    /// Premium at `now`: halves each day, falling linearly within the
+    /// day, and reaches zero 21 days after release
+    pub fn premium_at(&self, now: i64) -> u64 {
+        let elapsed = now.saturating_sub(self.released_at).max(0);
+        if elapsed >= RELEASE_PERIOD {
+            return 0;
+        }
+        let level = self.start_premium >> (elapsed / RELEASE_HALF_LIFE);
+        let into_day = (elapsed % RELEASE_HALF_LIFE) as u128;
+        let decay = (level / 2) as u128 * into_day / RELEASE_HALF_LIFE as u128;
+        level - decay as u64
+    }
+}
+
+/// Fixed-price sale offer for a domain
+/// 
+/// # Fields
+/// * `domain` - Domain account for sale
+/// * `seller` - Owner that listed it, paid on `buy_domain`
+/// * `price` - Asking price in lamports
+/// * `listed_at` - Unix timestamp of the listing
+/// * `bump` - PDA bump seed
+#[account]
+#[derive(InitSpace)]
+pub struct Listing {
+    pub domain: Pubkey,
+    pub seller: Pubkey,
+    pub price: u64,
+    pub listed_at: i64,
+    pub bump: u8,
+}
+
+/// Emitted when a domain is listed for sale
+#[event]
+pub struct DomainListed {
+    pub domain: Pubkey,
+    pub seller: Pubkey,
+    pub price: u64,
+    pub slot: u64,
+}
+
+/// Emitted when a listed domain is bought
+#[event]
+pub struct DomainSold {
+    pub domain: Pubkey,
+    pub seller: Pubkey,
+    pub buyer: Pubkey,
+    pub price: u64,
+    pub slot: u64,
+}
+
+/// Emitted when a listing is withdrawn
+#[event]
+pub struct ListingCancelled {
+    pub domain: Pubkey,
+    pub seller: Pubkey,
+    pub slot: u64,
+}
+
+/// Private sale of a domain to a named buyer
+/// 
+/// While open, this PDA is the domain authority, so neither side can
+/// change the domain until the sale completes or is cancelled.
+/// 
+/// # Fields
+/// * `domain` - Escrowed domain account
+/// * `seller` - Owner that opened the escrow, paid on completion
+/// * `buyer` - Only account that can complete the sale
+/// * `price` - Price in lamports
+/// * `opened_at` - Unix timestamp the escrow was opened
+/// * `bump` - PDA bump seed
+#[account]
+#[derive(InitSpace)]
+pub struct SaleEscrow {
+    pub domain: Pubkey,
+    pub seller: Pubkey,
+    pub buyer: Pubkey,
+    pub price: u64,
+    pub opened_at: i64,
+    pub bump: u8,
+}
+
+/// Lifecycle step reported by `EscrowChanged`
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq)]
+pub enum EscrowStatus {
+    Opened,
+    Completed,
+    Cancelled,
+}
+
+/// Emitted when a sale escrow is opened, completed or cancelled
+#[event]
+pub struct EscrowChanged {
+    pub domain: Pubkey,
+    pub seller: Pubkey,
+    pub buyer: Pubkey,
+    pub price: u64,
+    pub status: EscrowStatus,
+    pub slot: u64,
+}
+
+/// Fixed-term lease of a domain's record to a tenant
+/// 
+/// # Fields
+/// * `domain` - Leased domain account
+/// * `lessor` - Owner that granted the lease (refunded the PDA rent)
+/// * `tenant` - Only wallet that may update the record during the lease
+/// * `rent` - Lamports the tenant paid
+/// * `starts_at` - Unix timestamp the lease began
+/// * `expires_at` - Unix timestamp control reverts to the owner
+/// * `bump` - PDA bump seed
+#[account]
+#[derive(InitSpace)]
+pub struct DomainLease {
+    pub domain: Pubkey,
+    pub lessor: Pubkey,
+    pub tenant: Pubkey,
+    pub rent: u64,
+    pub starts_at: i64,
+    pub expires_at: i64,
+    pub bump: u8,
+}
+
+impl DomainLease {
+    /// Whether the lease binds `domain` at `now`
+    /// 
+    /// Unlike locks and operators, a lease follows the domain through
+    /// transfers; only a re-registration of the name voids it.
+    pub fn is_active(&self, domain: &DomainRecord, now: i64) -> bool {
+        self.starts_at >= domain.created_at && now < self.expires_at
+    }
 }
 
-/// Validator function - checks domain and IP format
-/// 
-/// # Arguments
-/// * `domain` - Domain name string to validate
-/// * `ip` - IP address string to validate
-/// 
-/// # Domain Validation Rules
-/// - Not empty
-/// - Max 256 characters
-/// - Must contain at least one dot (.)
-/// - Cannot start or end with dot
+/// Emitted when a lease starts (`expires_at` set) or is closed (`None`)
+#[event]
+pub struct LeaseChanged {
+    pub domain: Pubkey,
+    pub tenant: Pubkey,
+    pub expires_at: Option<i64>,
+    pub slot: u64,
+}
+
+/// Ownership and registration policy of a top-level domain
 /// 
-/// # IP Validation Rules (IPv4)
-/// - Not empty
-/// - Max 15 characters
-/// - Must have exactly 4 octets (separated by dots)
-/// - Each octet must be 0-255
+/// Second-level names keep their `[b"domain", sha256(name)]` address; the
+/// full name already includes its TLD, so they stay unique per TLD.
 /// 
-/// # Errors
-/// - DnsError::InvalidDomain: Domain doesn't meet format requirements
-/// - DnsError::InvalidIp: IP doesn't meet format requirements
-fn validate_domain_and_ip(domain: &str, ip: &str) -> Result<()> {
-    // Domain validation
-    require!(
-        !domain.is_empty() && domain.len() <= 256,
-        DnsError::InvalidDomain
-    );
-    require!(
-        domain.contains('.') && !domain.starts_with('.') && !domain.ends_with('.'),
-        DnsError::InvalidDomain
-    );
+/// # Fields
+/// * `tld` - The label, e.g. "neura"
+/// * `owner` - Wallet controlling the TLD
+/// * `treasury` - Wallet receiving the TLD's share of fees
+/// * `revenue_share_bps` - Share of registration and renewal fees, in
+///   basis points, paid to `treasury`
+/// * `registrar` - Extra key that may approve registrations
+/// * `open` - Whether anyone may register without approval
+/// * `created_at` - Unix timestamp the TLD was created
+/// * `bump` - PDA bump seed
+#[account]
+#[derive(InitSpace)]
+pub struct TldRecord {
+    #[max_len(63)]
+    pub tld: String,
+    pub owner: Pubkey,
+    pub treasury: Pubkey,
+    pub revenue_share_bps: u16,
+    pub registrar: Option<Pubkey>,
+    pub open: bool,
+    pub created_at: i64,
+    pub bump: u8,
+}
 
-    // IP validation (simple IPv4 check)
-    require!(
-        !ip.is_empty() && ip.len() <= 15,
-        DnsError::InvalidIp
-    );
-    let parts: Vec<&str> = ip.split('.').collect();
-    require!(parts.len() == 4, DnsError::InvalidIp);
-    
-    for part in parts {
-        let num: Result<u8, _> = part.parse();
-        require!(num.is_ok(), DnsError::InvalidIp);
+impl TldRecord {
+    /// Whether a registration approved by `approver` (if any) is allowed
+    pub fn allows(&self, approver: Option<Pubkey>) -> bool {
+        self.open
+            || approver.map_or(false, |key| {
+                key == self.owner || Some(key) == self.registrar
+            })
     }
 
-    Ok(())
+    /// The TLD owner's cut of `fee`, rounded down
+    pub fn share_of(&self, fee: u64) -> u64 {
+        (fee as u128 * self.revenue_share_bps as u128 / BPS_DENOMINATOR as u128) as u64
+    }
+
+    /// Emit `TldChanged` with the current state
+    fn emit_changed(&self) -> Result<()> {
+        emit!(TldChanged {
+            tld: self.tld.clone(),
+            owner: self.owner,
+            treasury: self.treasury,
+            revenue_share_bps: self.revenue_share_bps,
+            registrar: self.registrar,
+            open: self.open,
+            slot: Clock::get()?.slot,
+        });
+        Ok(())
+    }
 }
 
-/// Account context for domain registration
-/// 
-/// # Accounts
-/// * `domain_account` - PDA account to store domain data (init, mutable)
-/// * `authority` - Wallet signing the transaction (signer, mutable for rent)
-/// * `system_program` - Solana system program for account creation
-/// 
-/// # PDA Derivation
-/// - Seeds: [b"domain", domain_name.as_bytes()]
-/// - Deterministic address based on domain name
-/// - Collision-free, one domain per PDA
+/// Emitted when a TLD is created or its owner or policy changes
+#[event]
+pub struct TldChanged {
+    pub tld: String,
+    pub owner: Pubkey,
+    pub treasury: Pubkey,
+    pub revenue_share_bps: u16,
+    pub registrar: Option<Pubkey>,
+    pub open: bool,
+    pub slot: u64,
+}
+
+/// A wallet's chosen name for reverse resolution (wallet -> domain)
 /// 
-/// # Storage
-/// - Space: 8 bytes (discriminator) + DomainRecord::INIT_SPACE
-/// - Payer: authority (transaction signer)
-/// - Rent: Exempt (automatically calculated)
-#[derive(Accounts)]
-#[instruction(domain_name: String)]
-pub struct RegisterDomain<'info> {
-    #[account(
-        init,
-        payer = authority,
-        space = 8 + DomainRecord::INIT_SPACE,
-        seeds = [b"domain", domain_name.as_bytes()],
-        bump
-    )]
-    pub domain_account: Account<'info, DomainRecord>,
-    
-    #[account(mut)]
-    pub authority: Signer<'info>,
-    
-    pub system_program: Program<'info, System>,
+/// # Fields
+/// * `owner` - Wallet the entry belongs to
+/// * `domain` - Domain account shown for the wallet
+/// * `domain_name` - Its name, so wallets need only this account to display it
+/// * `set_at` - Unix timestamp the entry was written
+/// * `bump` - PDA bump seed
+#[account]
+#[derive(InitSpace)]
+pub struct PrimaryDomain {
+    pub owner: Pubkey,
+    pub domain: Pubkey,
+    #[max_len(253)]
+    pub domain_name: String,
+    pub set_at: i64,
+    pub bump: u8,
 }
 
-/// Account context for domain resolution
+/// Emitted when a wallet sets (`domain` set) or clears (`None`) its primary name
+#[event]
+pub struct PrimaryDomainChanged {
+    pub owner: Pubkey,
+    pub domain: Option<Pubkey>,
+    pub slot: u64,
+}
+
+/// Reverse (PTR) index of the domains pointing at one IPv4 address
 /// 
-/// # Accounts
-/// * `domain_account` - PDA account containing domain data (read-only)
-/// * `authority` - Wallet querying the domain (signer)
+/// # Fields
+/// * `ip` - The address's octets
+/// * `domains` - Domain accounts that claimed it, up to 16, oldest first
+/// * `bump` - PDA bump seed
+#[account]
+#[derive(InitSpace)]
+pub struct IpPointers {
+    pub ip: [u8; 4],
+    #[max_len(16)]
+    pub domains: Vec<Pubkey>,
+    pub bump: u8,
+}
+
+/// A domain's address on one chain, keyed by SLIP-0044 coin type
 /// 
-/// # PDA Derivation
-/// - Same seeds as registration: [b"domain", domain_name.as_bytes()]
-/// - Account must exist (created during registration)
+/// # Fields
+/// * `domain` - Domain account the address belongs to
+/// * `coin_type` - SLIP-0044 coin type (0 BTC, 60 ETH, 501 SOL, ...)
+/// * `address` - Address text, validated per coin type
+/// * `updated_at` - Unix timestamp of the last change
+/// * `bump` - PDA bump seed
+#[account]
+#[derive(InitSpace)]
+pub struct AddressRecord {
+    pub domain: Pubkey,
+    pub coin_type: u32,
+    #[max_len(128)]
+    pub address: String,
+    pub updated_at: i64,
+    pub bump: u8,
+}
+
+/// Profile keys a domain can publish text records for
 /// 
 /// # Notes
-/// - Read-only operation
-/// - No state modification
-/// - No rent fees
-#[derive(Accounts)]
-#[instruction(domain_name: String)]
-pub struct ResolveDomain<'info> {
-    #[account(
-        seeds = [b"domain", domain_name.as_bytes()],
-        bump
-    )]
-    pub domain_account: Account<'info, DomainRecord>,
-    
-    pub authority: Signer<'info>,
+/// - Serialized as a single byte, which is also the PDA seed; append
+///   new keys at the end
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, InitSpace)]
+pub enum TextKey {
+    Avatar,
+    Url,
+    Email,
+    Description,
+    Twitter,
+    Github,
+    Discord,
+    Telegram,
 }
 
-/// Domain record data structure
+impl TextKey {
+    /// Key name as wallets display it (ENS-style, e.g. "com.twitter")
+    pub fn name(&self) -> &'static str {
+        match self {
+            TextKey::Avatar => "avatar",
+            TextKey::Url => "url",
+            TextKey::Email => "email",
+            TextKey::Description => "description",
+            TextKey::Twitter => "com.twitter",
+            TextKey::Github => "com.github",
+            TextKey::Discord => "com.discord",
+            TextKey::Telegram => "org.telegram",
+        }
+    }
+}
+
+/// One profile text record of a domain
 /// 
 /// # Fields
-/// * `domain_name` - The registered domain name (max 256 chars)
-/// * `record` - IP address or record value (max 15 chars for IPv4)
-/// * `authority` - Public key of the registrar (wallet address)
-/// * `created_at` - Unix timestamp of registration
+/// * `domain` - Domain account the value belongs to
+/// * `key` - Profile key
+/// * `value` - Value, validated for `key` (at most 256 bytes)
+/// * `updated_at` - Unix timestamp of the last change
+/// * `bump` - PDA bump seed
+#[account]
+#[derive(InitSpace)]
+pub struct TextRecord {
+    pub domain: Pubkey,
+    pub key: TextKey,
+    #[max_len(256)]
+    pub value: String,
+    pub updated_at: i64,
+    pub bump: u8,
+}
+
+/// One SRV answer: where and how to reach a service (RFC 2782)
 /// 
-/// # Storage Size
-/// - domain_name: 4 bytes (length) + 256 bytes (max string)
-/// - record: 4 bytes (length) + 15 bytes (max string)
-/// - authority: 32 bytes (Pubkey)
-/// - created_at: 8 bytes (i64)
-/// - Total: ~319 bytes + 8 byte discriminator
+/// # Fields
+/// * `priority` - Lower values are tried first
+/// * `weight` - Relative share among targets of equal priority
+/// * `port` - Port the service listens on
+/// * `target` - Host name of the server, or "." for "not available"
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, InitSpace)]
+pub struct SrvTarget {
+    pub priority: u16,
+    pub weight: u16,
+    pub port: u16,
+    #[max_len(253)]
+    pub target: String,
+}
+
+/// SRV records of one service at a domain
 /// 
-/// # Notes
-/// - Immutable once created (no update function)
-/// - Deterministically addressable via PDA
-/// - Rent-exempt storage
+/// # Fields
+/// * `domain` - Domain account offering the service
+/// * `service` - Service label, e.g. "_rpc"
+/// * `protocol` - Protocol label, e.g. "_tcp"
+/// * `targets` - Up to 8 targets, sorted by priority
+/// * `ttl` - Seconds resolvers may cache the answer
+/// * `updated_at` - Unix timestamp of the last change
+/// * `bump` - PDA bump seed
 #[account]
 #[derive(InitSpace)]
-pub struct DomainRecord {
-    #[max_len(256)]
-    pub domain_name: String,
-    #[max_len(15)]
-    pub record: String,
-    pub authority: Pubkey,
-    pub created_at: i64,
+pub struct SrvRecordSet {
+    pub domain: Pubkey,
+    #[max_len(16)]
+    pub service: String,
+    #[max_len(4)]
+    pub protocol: String,
+    #[max_len(8)]
+    pub targets: Vec<SrvTarget>,
+    pub ttl: u32,
+    pub updated_at: i64,
+    pub bump: u8,
 }
 
 /// Custom error codes for DNS operations
@@ -237,21 +12477,349 @@ pub struct DomainRecord {
 /// # Errors
 /// * `InvalidDomain` - Domain format validation failed
 ///   - Empty domain
-///   - Too long (>256 chars)
-///   - Missing dots
-///   - Starts/ends with dot
+///   - Single label (no dot)
 /// 
 /// * `InvalidIp` - IP address validation failed
 ///   - Empty IP
 ///   - Too long (>15 chars)
 ///   - Not 4 octets
 ///   - Octets not 0-255
+/// 
+/// * `Unauthorized` - Signer is not the domain authority
+/// * `AlreadyWrapped` - Domain is already wrapped as an NFT
+/// * `NotWrapped` - Domain is not wrapped, or mint does not match
+/// * `NotTokenHolder` - Token account does not hold the domain NFT
+/// * `InvalidMetadataUri` - NFT metadata URI is empty or too long
+/// * `TooManyDidKeys` - More than 4 DID verification keys
+/// * `InvalidDidDocument` - DID document URI is too long
+/// * `NotAdmin` - Signer is not the program admin
+/// * `NotVerifier` - Signer is not the verification oracle
+/// * `StaleVerification` - Domain changed hands after the challenge was issued
+/// * `InvalidLocalPart` - Email local part is empty, too long or contains `@`
+/// * `InvalidOpenPgpKey` - Key data is not an OpenPGP public key
+/// * `InvalidSshfp` - Unknown SSHFP algorithm/type or wrong fingerprint length
+/// * `TooManySshfpRecords` - Domain already has 8 SSHFP fingerprints
+/// * `SshfpNotFound` - No SSHFP fingerprint with that algorithm and type
+/// * `InvalidAttestation` - Attestation fields are empty, too long or already expired
+/// * `InvalidStaleness` - Dynamic record freshness bound out of range
+/// * `NotUpdater` - Signer is not the dynamic record's updater
+/// * `IndexPageFull` - Tail index page is full; append a page first
+/// * `IndexPageNotFull` - Tail index page still has room
+/// * `FilterNotRebuilding` - No availability filter rebuild in progress
+/// * `LayoutUpToDate` - Domain account already uses the current layout
+/// * `ChildRecordsExist` - Domain still has child records to close
+/// * `OrphanedRecord` - Record belongs to a previous registration of the name
+/// * `NotPendingAuthority` - Signer is not the proposed new owner
+/// * `InvalidHostname` - CNAME/NS target is not a valid host name
+/// * `InvalidMxRecord` - MX value is not "<preference> <exchange>"
+/// * `InvalidTxtRecord` - TXT value too long, non-printable, or a malformed SPF/DMARC policy
+/// * `InvalidIpv6` - AAAA value is not a valid IPv6 address
+/// * `CnameConflict` - CNAME would share the name with other records
+/// * `DuplicateRecord` - Value is already in the record set
+/// * `TooManyRecords` - Record set would be empty or exceed 8 values
+/// * `RecordNotFound` - Value is not in the record set
+/// * `InvalidTtl` - TTL outside 30-86400 seconds
+/// * `DomainExpired` - Registration lapsed (or redemption period is over)
+/// * `DomainNotExpired` - Domain is active or still redeemable
+/// * `RenewalTooEarly` - Renewal would pay more than 10 years ahead
+/// * `SubdomainsClosed` - Only the parent authority may register subdomains
+/// * `InvalidLabel` - Subdomain label is not a valid DNS label
+/// * `UppercaseDomain` - Domain names must be lowercase
+/// * `DomainTooLong` - Domain exceeds 253 characters
+/// * `EmptyLabel` - Domain has an empty label (leading, trailing or double dot)
+/// * `LabelTooLong` - A label exceeds 63 characters
+/// * `InvalidLabelCharacter` - A label has characters other than letters, digits, hyphens
+/// * `LabelHyphen` - A label starts or ends with a hyphen
+/// * `NameReserved` - Name is reserved or blocked by the admin
+/// * `WrongReservedChunk` - Name hash or chunk number doesn't match the chunk
+/// * `ReservedListFull` - Reserved-name chunk already holds 256 names
+/// * `InsufficientFee` - Payer can't cover the registration or renewal fee
+/// * `WrongFeeMint` - Fee paid in a mint other than the configured one, or
+///   not to the treasury's token account
+/// * `InvalidBatch` - Batch is empty, over 16 entries, or its account count
+///   doesn't match
+/// * `BatchAccountMismatch` - Remaining account isn't the entry's writable
+///   subdomain PDA
+/// * `SubdomainTaken` - Subdomain is already registered
+/// * `InvalidGovernance` - Threshold outside 1..=members, duplicate members,
+///   or more than 10
+/// * `GovernanceThreshold` - Too few governance members signed
+/// * `DomainLocked` - Domain is locked and its unlock delay hasn't passed
+/// * `InvalidUnlockDelay` - Unlock delay outside 1-365 days
+/// * `CommitmentRequired` - Registration must go through commit-reveal
+/// * `CommitmentMismatch` - Commitment missing, or not for this name, salt and signer
+/// * `CommitmentTooNew` - Commitment is less than 10 slots old
+/// * `CommitmentExpired` - Commitment is over 216,000 slots old
+/// * `NameRequiresAuction` - Name can only be registered through an auction
+/// * `NotAuctionable` - Auctions are off or the name is too long for one
+/// * `DomainAlreadyRegistered` - Name already has a domain account
+/// * `BidTooLow` - Bid below the minimum or the required 5% raise
+/// * `AuctionEnded` - Bidding has closed
+/// * `AuctionNotEnded` - Bidding is still open
+/// * `AuctionMismatch` - Auction missing or for another name
+/// * `InvalidAuctionPolicy` - Auction length or duration out of range
+/// * `InvalidPrice` - Price must be above zero
+/// * `ListingStale` - Seller no longer owns the listed domain
+/// * `PriceAboveMax` - Price exceeds what the buyer agreed to pay
+/// * `DomainLeased` - Domain is leased to someone else
+/// * `InvalidLeaseDuration` - Lease length outside 1-365 days
+/// * `TldApprovalRequired` - TLD is restricted and its owner did not approve
+/// * `InvalidRevenueShare` - Revenue share above 10000 basis points
+/// * `WrongTldTreasury` - TLD fee recipient missing or not the TLD's treasury
+/// * `NotARecord` - Domain's record is not an A record
+/// * `IpPointersFull` - Reverse index for the IP is full
+/// * `InvalidWalletAddress` - SOL record is not a base58 public key
+/// * `NoWalletRecord` - Domain has no SOL record
+/// * `InvalidChainAddress` - Address fails its coin type's format check
+/// * `InvalidContentHash` - Content hash is not a valid IPFS/IPNS CID or Arweave id
+/// * `InvalidTextValue` - Profile value fails its key's format or size check
+/// * `InvalidSrvRecord` - SRV owner labels or targets are malformed
+/// * `NotDelegated` - Domain's primary record is not an NS record
+/// * `InvalidDnssecRecord` - DS or DNSKEY fields fail validation
+/// * `TooManyDnssecRecords` - Domain already has 4 DS or 4 DNSKEY records
+/// * `DnssecRecordNotFound` - No DS or DNSKEY record matches
+/// * `InvalidAclGrant` - Grant names no record types
+/// * `TooManyAclEntries` - Domain's ACL already holds 8 keys
+/// * `AclEntryNotFound` - Key holds no record type rights
+/// * `UpdateTimelocked` - Update timelock is in force
+/// * `NotTimelocked` - Domain has no update timelock in force
+/// * `InvalidUpdateDelay` - Delay outside 1 hour-30 days or shortened
+/// * `UpdateNotReady` - Scheduled update's delay hasn't passed
+/// * `InvalidGuardians` - Bad threshold, duplicate or too many guardians,
+///   or recovery to the current owner
+/// * `NoGuardians` - Domain has no guardians for its current owner
+/// * `GuardianThreshold` - Too few guardians signed
+/// * `RecoveryPending` - A recovery is already open
+/// * `RecoveryStale` - Domain changed owner since the recovery began
+/// * `RecoveryNotReady` - Challenge period hasn't passed
+/// * `InvalidInheritance` - Heartbeat period out of range or beneficiary is the owner
+/// * `HeartbeatNotMissed` - Owner's last heartbeat is within the period
+/// * `FeatureDisabled` - The admin turned this feature off
+/// * `ProgramPaused` - The admin paused registrations, updates and transfers
+/// * `NotPendingAdmin` - Signer is not the nominated config admin
+/// * `AuctionSettleable` - The auction winner can still settle
 #[error_code]
 pub enum DnsError {
     #[msg("Invalid domain format")]
     InvalidDomain,
     #[msg("Invalid IP address format")]
     InvalidIp,
+    #[msg("Signer is not the domain authority")]
+    Unauthorized,
+    #[msg("Domain is already wrapped")]
+    AlreadyWrapped,
+    #[msg("Domain is not wrapped with this mint")]
+    NotWrapped,
+    #[msg("Token account does not hold the domain NFT")]
+    NotTokenHolder,
+    #[msg("Invalid metadata URI")]
+    InvalidMetadataUri,
+    #[msg("Too many DID verification keys")]
+    TooManyDidKeys,
+    #[msg("Invalid DID document")]
+    InvalidDidDocument,
+    #[msg("Signer is not the program admin")]
+    NotAdmin,
+    #[msg("Signer is not the verification oracle")]
+    NotVerifier,
+    #[msg("Verification request does not match the current domain authority")]
+    StaleVerification,
+    #[msg("Invalid email local part")]
+    InvalidLocalPart,
+    #[msg("Invalid OpenPGP public key")]
+    InvalidOpenPgpKey,
+    #[msg("Invalid SSHFP record")]
+    InvalidSshfp,
+    #[msg("Too many SSHFP records")]
+    TooManySshfpRecords,
+    #[msg("SSHFP record not found")]
+    SshfpNotFound,
+    #[msg("Invalid attestation")]
+    InvalidAttestation,
+    #[msg("Dynamic record staleness bound out of range")]
+    InvalidStaleness,
+    #[msg("Signer is not the dynamic record updater")]
+    NotUpdater,
+    #[msg("Index page is full")]
+    IndexPageFull,
+    #[msg("Index page is not full yet")]
+    IndexPageNotFull,
+    #[msg("Availability filter is not being rebuilt")]
+    FilterNotRebuilding,
+    #[msg("Domain account already uses the current layout")]
+    LayoutUpToDate,
+    #[msg("Close the domain's subdomains, record sets and DID, SSHFP, dynamic and verification records first")]
+    ChildRecordsExist,
+    #[msg("Record belongs to a previous registration of this name")]
+    OrphanedRecord,
+    #[msg("Signer is not the pending owner of this domain")]
+    NotPendingAuthority,
+    #[msg("Invalid host name in record target")]
+    InvalidHostname,
+    #[msg("Invalid MX record: expected \"<preference> <exchange>\"")]
+    InvalidMxRecord,
+    #[msg("Invalid TXT record")]
+    InvalidTxtRecord,
+    #[msg("Invalid IPv6 address format")]
+    InvalidIpv6,
+    #[msg("CNAME must be the only record of a domain")]
+    CnameConflict,
+    #[msg("Record value is already in the set")]
+    DuplicateRecord,
+    #[msg("Record sets hold 1 to 8 values")]
+    TooManyRecords,
+    #[msg("Record value not found in the set")]
+    RecordNotFound,
+    #[msg("TTL must be between 30 and 86400 seconds")]
+    InvalidTtl,
+    #[msg("Domain registration has expired")]
+    DomainExpired,
+    #[msg("Domain can't be reclaimed until its redemption period ends")]
+    DomainNotExpired,
+    #[msg("Domains can be paid up at most 10 years ahead")]
+    RenewalTooEarly,
+    #[msg("Subdomains of this domain can only be registered by its owner")]
+    SubdomainsClosed,
+    #[msg("Invalid subdomain label")]
+    InvalidLabel,
+    #[msg("Domain names must be lowercase")]
+    UppercaseDomain,
+    #[msg("Domain name exceeds 253 characters")]
+    DomainTooLong,
+    #[msg("Domain name has an empty label")]
+    EmptyLabel,
+    #[msg("Domain label exceeds 63 characters")]
+    LabelTooLong,
+    #[msg("Domain labels may only contain letters, digits and hyphens")]
+    InvalidLabelCharacter,
+    #[msg("Domain labels cannot start or end with a hyphen")]
+    LabelHyphen,
+    #[msg("This name is reserved and cannot be registered")]
+    NameReserved,
+    #[msg("Name hash does not belong to this reserved-name chunk")]
+    WrongReservedChunk,
+    #[msg("Reserved-name chunk is full")]
+    ReservedListFull,
+    #[msg("Insufficient lamports to pay the fee")]
+    InsufficientFee,
+    #[msg("Fees can't be paid in this mint")]
+    WrongFeeMint,
+    #[msg("Batch must hold 1-16 entries, one remaining account each")]
+    InvalidBatch,
+    #[msg("Account does not match the batch entry's subdomain PDA")]
+    BatchAccountMismatch,
+    #[msg("Subdomain is already registered")]
+    SubdomainTaken,
+    #[msg("Governance needs 1-10 unique members and a threshold of 1 to their count")]
+    InvalidGovernance,
+    #[msg("Not enough governance members signed")]
+    GovernanceThreshold,
+    #[msg("Domain is locked")]
+    DomainLocked,
+    #[msg("Unlock delay must be between 1 and 365 days")]
+    InvalidUnlockDelay,
+    #[msg("Names must be registered with commit_registration and reveal_and_register")]
+    CommitmentRequired,
+    #[msg("Commitment does not match this name, salt and signer")]
+    CommitmentMismatch,
+    #[msg("Commitment must age at least 10 slots before it is revealed")]
+    CommitmentTooNew,
+    #[msg("Commitment has expired")]
+    CommitmentExpired,
+    #[msg("This name can only be registered through an auction")]
+    NameRequiresAuction,
+    #[msg("This name can't be auctioned")]
+    NotAuctionable,
+    #[msg("Domain is already registered")]
+    DomainAlreadyRegistered,
+    #[msg("Bid is too low")]
+    BidTooLow,
+    #[msg("Auction has ended")]
+    AuctionEnded,
+    #[msg("Auction is still open")]
+    AuctionNotEnded,
+    #[msg("Auction does not match this name")]
+    AuctionMismatch,
+    #[msg("Auction policy out of range")]
+    InvalidAuctionPolicy,
+    #[msg("Price must be above zero")]
+    InvalidPrice,
+    #[msg("Listing is no longer valid")]
+    ListingStale,
+    #[msg("Price is above the buyer's maximum")]
+    PriceAboveMax,
+    #[msg("Domain is leased")]
+    DomainLeased,
+    #[msg("Lease duration must be 1-365 days")]
+    InvalidLeaseDuration,
+    #[msg("TLD owner approval required")]
+    TldApprovalRequired,
+    #[msg("Revenue share must be at most 10000 basis points")]
+    InvalidRevenueShare,
+    #[msg("TLD treasury missing or incorrect")]
+    WrongTldTreasury,
+    #[msg("Domain's record is not an A record")]
+    NotARecord,
+    #[msg("Too many domains already point at this IP")]
+    IpPointersFull,
+    #[msg("Wallet address must be a base58 public key")]
+    InvalidWalletAddress,
+    #[msg("Domain has no wallet record")]
+    NoWalletRecord,
+    #[msg("Invalid address for this coin type")]
+    InvalidChainAddress,
+    #[msg("Content hash must be ipfs://<CID>, ipns://<CID> or ar://<id>")]
+    InvalidContentHash,
+    #[msg("Invalid value for this profile key")]
+    InvalidTextValue,
+    #[msg("Invalid SRV record")]
+    InvalidSrvRecord,
+    #[msg("Domain is not delegated to nameservers")]
+    NotDelegated,
+    #[msg("Invalid DNSSEC record: unsupported algorithm or malformed hex")]
+    InvalidDnssecRecord,
+    #[msg("Domain already has the maximum number of DS or DNSKEY records")]
+    TooManyDnssecRecords,
+    #[msg("No matching DS or DNSKEY record")]
+    DnssecRecordNotFound,
+    #[msg("Grant at least one record type")]
+    InvalidAclGrant,
+    #[msg("Domain's record type ACL is full")]
+    TooManyAclEntries,
+    #[msg("Key holds no record type rights on this domain")]
+    AclEntryNotFound,
+    #[msg("Domain is timelocked: schedule the update instead")]
+    UpdateTimelocked,
+    #[msg("Domain has no update timelock")]
+    NotTimelocked,
+    #[msg("Update delay must be 1 hour to 30 days and can't be shortened")]
+    InvalidUpdateDelay,
+    #[msg("Scheduled update can't be applied yet")]
+    UpdateNotReady,
+    #[msg("Invalid guardians: check threshold and duplicates")]
+    InvalidGuardians,
+    #[msg("Domain has no guardians")]
+    NoGuardians,
+    #[msg("Not enough guardians signed")]
+    GuardianThreshold,
+    #[msg("A recovery is already pending")]
+    RecoveryPending,
+    #[msg("Recovery no longer applies to this domain")]
+    RecoveryStale,
+    #[msg("Recovery challenge period has not passed")]
+    RecoveryNotReady,
+    #[msg("Heartbeat period must be 30 days to 5 years, beneficiary not the owner")]
+    InvalidInheritance,
+    #[msg("Owner's heartbeat period has not lapsed")]
+    HeartbeatNotMissed,
+    #[msg("This feature is currently disabled")]
+    FeatureDisabled,
+    #[msg("The program is paused")]
+    ProgramPaused,
+    #[msg("Signer is not the nominated config admin")]
+    NotPendingAdmin,
+    #[msg("The auction can still be settled by its winner")]
+    AuctionSettleable,
 }
 
 // ============================================================================
@@ -264,12 +12832,21 @@ pub enum DnsError {
 // Args: 
 //   - domain_name: "example.com"
 //   - record: "8.8.8.8"
+//   - record_type: A (or Aaaa, Cname, Txt, Mx "10 mail.example.com", Ns)
+//   - ttl: 3600 (seconds, 30-86400)
 // Accounts:
-//   - domain_account: PDA ["domain", "example.com"]
+//   - domain_account: PDA ["domain", sha256("example.com")]
 //   - authority: Your wallet (signer)
 //   - system_program: System Program
+//   - namespace_index: PDA ["index"]
+//   - index_page: PDA ["index_page", page_count - 1]
+//   - availability_filter: PDA ["bloom"]
+//   - protocol_stats: PDA ["stats"]
+//   - reserved_names: PDA ["reserved", sha256("example.com")[0] % 16]
+//   - config: PDA ["config"], treasury: config.treasury (receives the fee)
+//   - release: PDA ["release", sha256("example.com")] (usually empty)
 //
-// Result: Creates PDA account with domain data
+// Result: Creates PDA account with domain data and lists it in the index
 //
 // 2. RESOLVE DOMAIN
 // -----------------
@@ -277,10 +12854,614 @@ pub enum DnsError {
 // Args:
 //   - domain_name: "example.com"
 // Accounts:
-//   - domain_account: PDA ["domain", "example.com"]
+//   - domain_account: PDA ["domain", sha256("example.com")]
 //   - authority: Any wallet (signer)
 //
-// Result: Returns domain record from on-chain storage
+// Result: Returns ResolvedRecord { record_type, value, ttl } as return data
+//
+// 3. WRAP DOMAIN
+// --------------
+// Instruction: wrap_domain
+// Args:
+//   - uri: "https://.../example.com.json"
+// Accounts:
+//   - domain_account: PDA ["domain", sha256("example.com")]
+//   - authority: Domain owner (signer)
+//   - wrap_escrow: PDA ["wrap_escrow", domain_account]
+//   - mint: New keypair (signer)
+//   - holder_token_account, Token-2022 program, ATA program, System Program
+//
+// Result: Owner holds a Token-2022 NFT whose metadata names the domain and
+//         carries its PDA under "domain"; domain authority moves to escrow,
+//         and the holder updates the record by passing holder_token_account
+//         to update_record
+//
+// 4. UNWRAP DOMAIN
+// ----------------
+// Instruction: unwrap_domain
+// Accounts:
+//   - domain_account, holder (signer), mint, holder_token_account
+//
+// Result: NFT is burned; holder becomes the domain authority
+//
+// 5. DID DOCUMENT
+// ---------------
+// Instruction: set_did_document / resolve_did / remove_did_document
+// Args (set):
+//   - verification_keys: [Pubkey, ...]
+//   - document_uri: "" or "https://.../did.json"
+// Accounts:
+//   - domain_account: PDA ["domain", sha256("example.com")]
+//   - did_record: PDA ["did", domain_account]
+//   - governance, lock, timelock, authority (signer), config
+//
+// Result: "did:neura:example.com" resolves to the stored keys/URI;
+//         resolve_did returns ResolvedDid { did, controller, verification_keys, document_uri }
+//
+// 6. EXTERNAL VERIFICATION BADGE
+// ------------------------------
+// Instruction: request_external_verification (domain owner)
+// Args:
+//   - method: DnsTxt | DkimEmail
+// Accounts:
+//   - domain_account, verification_request: PDA ["verification", domain_account]
+//
+// Then publish the logged challenge at _neura-challenge.example.com (TXT).
+//
+// Instruction: confirm_external_verification (verifier oracle)
+// Accounts:
+//   - config: PDA ["config"], verifier (signer), domain_account,
+//     verification_request, requester
+//
+// Result: domain_account.verified_external = true, shown on resolution
+//
+// 7. OPENPGPKEY
+// -------------
+// Instruction: set_openpgp_key / resolve_openpgp_key / remove_openpgp_key
+// Args:
+//   - localpart: "alice"          (for alice@example.com)
+//   - key: [binary public key]    (set only)
+// Accounts:
+//   - domain_account: PDA ["domain", sha256("example.com")]
+//   - key_record: PDA ["openpgpkey", domain_account, sha256("alice")[..28]]
+//   - governance, lock, timelock, authority (signer), config (set/remove only)
+//
+// Result: Key discoverable from the email address alone; resolve_openpgp_key
+//         returns the key bytes as return data
+//
+// 8. SSHFP
+// --------
+// Instruction: set_sshfp / remove_sshfp / resolve_sshfp
+// Args (set):
+//   - algorithm: 4 (Ed25519)
+//   - fingerprint_type: 2 (SHA-256)
+//   - fingerprint: [32 bytes]
+// Accounts:
+//   - domain_account: PDA ["domain", sha256("example.com")]
+//   - sshfp_records: PDA ["sshfp", domain_account]
+//   - governance, lock, timelock, authority (signer), config (set/remove only)
+//
+// Result: resolve_sshfp logs "example.com. IN SSHFP 4 2 <hex>" lines and
+//         returns the entries as return data
+//
+// 9. ATTESTATIONS
+// ---------------
+// Instruction: add_attestation (attester signs)
+// Args:
+//   - claim_type: "kyc-business"
+//   - claim_uri: "https://.../credential.json"
+//   - claim_hash: sha256(credential)
+//   - expires_at: Some(1767225600) or None
+// Accounts:
+//   - domain_account, attestation: PDA ["attestation", domain_account, attester]
+//
+// Then: accept_attestation / remove_attestation (owner),
+//       revoke_attestation (attester)
+//
+// 10. DYNAMIC RECORDS
+// -------------------
+// Instruction: configure_dynamic_record (owner)
+// Args:
+//   - updater: Health checker's key
+//   - max_staleness: 300
+// Accounts:
+//   - domain_account, dynamic_record: PDA ["dynamic", domain_account], authority
+//   - governance, lock, timelock, config (checked like update_record)
+//
+// Instruction: push_dynamic_record (updater)
+// Args:
+//   - value: "203.0.113.7"
+// Accounts:
+//   - dynamic_record, updater, domain_account, lock, config
+//   - Pushes fail while the domain is locked or the program is paused
+//
+// Result: resolve_domain (with dynamic_record passed) answers the pushed
+//         value while it is fresh, otherwise the static record
+//
+// 11. MIGRATE LEGACY DOMAIN
+// -------------------------
+// Instruction: migrate_domain_account
+// Args:
+//   - domain_name: "example.com"
+// Accounts:
+//   - legacy_account: PDA ["domain", "example.com"]
+//   - domain_account: PDA ["domain", sha256("example.com")]
+//   - authority: Domain owner (signer)
+//   - system_program: System Program
+//
+// Result: Domain lives at the hashed PDA; legacy rent is refunded
+//
+// 12. NAMESPACE INDEX
+// -------------------
+// Instruction: initialize_index (once) / append_index_page (when tail is full)
+// Accounts:
+//   - namespace_index: PDA ["index"]
+//   - first_page / new_page: PDA ["index_page", n (u32 LE)]
+//   - payer (signer), system_program
+//
+// Enumerate: read ["index"], then pages 0..page_count (or follow `next`)
+//
+// 13. AVAILABILITY FILTER
+// -----------------------
+// Read PDA ["bloom"] once, then for each candidate name:
+//   - h = sha256(name); probes = 4 little-endian u16s from h[0..8]
+//   - any probe bit clear            -> definitely available
+//   - all set (or rebuilding == 1)   -> read the domain PDA to confirm
+//
+// Admin: begin_filter_rebuild -> load_filter_entries (batches) ->
+//        finish_filter_rebuild
+//
+// 14. LAYOUT UPGRADE
+// ------------------
+// Instruction: upgrade_domain_account
+// Accounts: domain_account (mut), payer (signer), system_program
+//
+// Result: Account reallocated and stamped with the current layout;
+//         DomainAccountUpgraded { domain, from_version, to_version, space }
+//
+// 15. PROTOCOL STATS
+// ------------------
+// Read PDA ["stats"]:
+//   - lifetime.{registrations, renewals, deletions, fee_volume}
+//   - history[epoch % 8] for the last 8 active epochs (check .epoch)
+//   - registrant_sketch: HyperLogLog estimate of unique registrants
+//
+// 16. UPDATE RECORD
+// -----------------
+// Instruction: update_record
+// Args: record: "1.1.1.1", record_type: A, ttl: 300
+// Accounts: domain_account (mut), record_history PDA ["history", domain],
+//           operator_grant (only when the operator signs), record_acl
+//           (only when a per-type key signs), signer (owner, operator or
+//           per-type key), system_program
+//
+// Result: Record replaced, updated_at set, account resized to fit,
+//         new value appended to the history
+//
+// get_history(skip: 0)
+// Accounts: domain, record_history PDA ["history", domain]
+// Result: Last values, newest first, each with updated_at and updated_by
+//
+// set_operator(devops_key) / revoke_operator()
+// Accounts: domain_account, operator_grant PDA ["operator", domain],
+//           authority (signer), system_program (set only)
+// Result: devops_key can call update_record, nothing else
+//
+// set_governance([cfo, cto, ciso], 2) / remove_governance()
+// Accounts: domain_account, governance PDA ["governance", domain],
+//           authority (signer), system_program (set only)
+// Then update_record, record set changes, propose_transfer, wrap_domain and
+// unregister_domain take the governance PDA and need 2 of the 3 to sign as
+// remaining_accounts
+//
+// lock_domain(unlock_delay: 604800) - record and ownership frozen
+// request_unlock() - lock lifts 7 days later; cancel_unlock() re-arms it
+// unlock_domain() - closes the lock once the delay has passed
+// Accounts: domain_account, lock PDA ["lock", domain], authority (signer)
+// update_record, record set changes, propose_transfer, wrap_domain and
+// unregister_domain take the lock PDA too
+//
+// 17. UNREGISTER DOMAIN
+// ---------------------
+// Instruction: unregister_domain
+// Accounts: domain_account (mut), authority (signer, mut), namespace_index,
+//           index_page ["index_page", domain.index_page], protocol_stats,
+//           did_record, sshfp_records, dynamic_record,
+//           verification_request (all must be closed)
+//
+// Result: Account closed, rent refunded, name available again
+//
+// 18. TRANSFER OWNERSHIP (two-step)
+// ---------------------------------
+// 1. propose_transfer(new_authority)
+//    Accounts: domain_account (mut), authority (signer), system_program
+// 2. accept_transfer()
+//    Accounts: domain_account (mut), new_authority (signer)
+// cancel_transfer() withdraws a proposal before it is accepted
+//
+// Result: Authority replaced; DomainTransferred { domain, from, to, timestamp, slot }
+//
+// 19. RECORD SETS
+// ---------------
+// add_record(record_type, value) / remove_record(record_type, value) /
+// replace_records(record_type, values) / resolve_records(record_type)
+// Accounts: domain_account, record_set PDA ["record", domain, type as u8],
+//           governance, lock, authority (signer), system_program
+//
+// Example: add_record(Mx, "10 mx1.example.com"), add_record(Mx, "20 mx2.example.com")
+// Result: Up to 8 values per type next to the primary record; the set is
+//         resized on every change, so rent tracks what is stored
+//
+// 20. RENEWAL AND EXPIRY
+// ---------------------
+// renew_domain()
+// Accounts: domain_account (mut), authority (signer), config PDA ["config"],
+//           treasury, protocol_stats PDA ["stats"], system_program
+// reclaim_expired()
+// Accounts: domain_account, authority (rent recipient unless wrapped),
+//           reclaimer (signer; gets a wrapped domain's rent),
+//           namespace_index, index_page, protocol_stats, child PDAs (empty),
+//           config, release PDA ["release", sha256(name)], system_program
+//
+// Timeline: expires_at -> +30d grace (free renew) -> +30d redemption
+//           (0.1 SOL renew) -> anyone may reclaim -> re-registration premium
+//           halves daily for 21 days
+//
+// 21. SUBDOMAINS
+// -------------
+// set_subdomain_policy(open) - parent owner lets anyone mint subdomains
+// register_subdomain(label, record, record_type, ttl, owner)
+// Accounts: parent_account (mut), subdomain PDA
+//           ["subdomain", parent, sha256(label)], payer (signer), system_program,
+//           config
+// resolve_subdomain(label) / remove_subdomain(label)
+//
+// Example: register_subdomain("api", "10.0.0.1", A, 300, <owner>) under example.com
+// Result: api.example.com, owned by <owner>, resolvable via its parent
+//
+// register_batch([{ label, record, record_type, ttl, owner }, ...], all_or_nothing)
+// Accounts: parent_account (mut), payer (signer), system_program, config,
+//           remaining_accounts: one subdomain PDA per entry, in order
+// Result: up to 16 subdomains in one transaction; with all_or_nothing = false
+//         invalid or taken entries are logged and skipped
+//
+// 22. RESERVED NAMES (admin)
+// -------------------------
+// initialize_reserved_names(chunk) for chunk 0..15, then
+// reserve_names(chunk, [sha256("solana.com"), ...]) / release_names(chunk, hashes)
+// Accounts: config PDA ["config"], reserved_names PDA ["reserved", chunk], admin (signer)
+//
+// Result: register_request fails with NameReserved for listed names
+//
+// 23. EVENTS
+// ----------
+// Indexers subscribe to Anchor events instead of parsing msg! logs:
+//   DomainRegistered  - register_request (domain, name, record, type, ttl, authority, slot)
+//   DomainUpdated     - update_record (domain, authority, record, type, ttl, slot)
+//   DomainTransferred - accept_transfer (domain, from, to, timestamp, slot)
+//   OperatorChanged   - set_operator / revoke_operator (domain, operator, slot)
+//   RecordAclChanged  - grant_record_types / revoke_record_types (domain, key, record_types, slot)
+//   UpdateTimelockChanged - set_update_delay / request_timelock_removal /
+//                       remove_update_timelock (domain, delay, removed_at, slot)
+//   ScheduledUpdateChanged - schedule_update / cancel_scheduled_update
+//                       (domain, record, record_type, ttl, executes_at, slot)
+//   RecoveryChanged   - start_recovery / veto_recovery / complete_recovery
+//                       (domain, new_authority, completes_at, status, slot)
+//   InheritanceChanged - set_beneficiary / heartbeat / remove_beneficiary
+//                       (domain, beneficiary, claimable_at, slot)
+//   DomainLockChanged - lock_domain / request_unlock / cancel_unlock /
+//                       unlock_domain (domain, locked, unlocks_at, slot)
+//   DomainListed / DomainSold / ListingCancelled - marketplace
+//   EscrowChanged (domain, seller, buyer, price, status, slot) - private sales
+//   LeaseChanged (domain, tenant, expires_at, slot) - lease_domain / end_lease
+//   TldChanged (tld, owner, treasury, revenue_share_bps, registrar, open, slot)
+//     - TLD created or updated
+//   PrimaryDomainChanged (owner, domain, slot) - reverse resolution
+//   DomainRenewed     - renew_domain (domain, expires_at, fee, penalty)
+//   DomainExpired     - reclaim_expired (domain, name, lapsed authority, expires_at, slot)
+//
+// 24. FEES (admin)
+// ---------------
+// set_fees(treasury, registration_fee, renewal_fee)
+// Accounts: config PDA ["config"], admin (signer)
+// Configs from before fees existed: resize_config() first
+//
+// Result: register_request / renew_domain transfer the fee to the treasury
+//
+// set_premium_pricing([1000, 200, 40, 8]) - 1-4 character names pay that multiple
+// set_commitment_required(true) - register_request off, commit-reveal only
+// set_auction_policy(3, 1_000_000_000, 259_200) - names of 1-3 characters are
+//   auctioned for 3 days, opening at 1 SOL
+// set_release_premium(100_000_000_000) - reclaimed names start 100 SOL dearer
+//
+// set_token_fees(Some(usdc_mint), 5_000_000, 5_000_000)
+// Then pass token_program, fee_mint, payer_token_account and
+// treasury_token_account to register_request / renew_domain to pay in USDC
+//
+// 25. COMMIT-REVEAL REGISTRATION
+// ------------------------------
+// 1. commit_registration(sha256("example.com" ‖ salt ‖ wallet))
+//    Accounts: commitment PDA ["commit", commitment], committer (signer)
+// 2. Wait at least 10 slots (and no more than 216,000)
+// 3. reveal_and_register("example.com", record, record_type, ttl, salt)
+//    Accounts: as register_request, plus commitment (the PDA above)
+//
+// Result: The name is only public once the commitment has aged;
+//         close_commitment() refunds an unused commitment
+//
+// 26. AUCTIONS
+// ------------
+// start_auction("abc.com", 1_000_000_000)
+//   Accounts: auction PDA ["auction", sha256("abc.com")], domain_account
+//             PDA (must be empty), config, bidder (signer), system_program
+// place_bid(1_050_000_000)
+//   Accounts: auction, previous_bidder (= auction.highest_bidder), bidder
+// settle_auction("abc.com", record, record_type, ttl) after ends_at
+//   Accounts: as register_request, plus auction; winner signs
+// refund_bid()                     // anyone, if the winner can't or didn't
+//   Accounts: auction, highest_bidder, domain_account, config, signer
+//
+// Result: Winning bid goes to the treasury, outbid bidders were refunded
+//         along the way, and the winner owns abc.com
+//
+// 27. MARKETPLACE
+// ---------------
+// list_for_sale(5_000_000_000)
+//   Accounts: domain_account, listing PDA ["listing", domain], authority
+//             (signer), system_program, governance, lock
+// buy_domain(max_price: 5_000_000_000)
+//   Accounts: domain_account (mut), listing, seller (mut), buyer (signer),
+//             lock, system_program
+// cancel_listing()
+//   Accounts: listing, seller (signer)
+//
+// Result: Buyer pays the seller and becomes the authority in one instruction
+//
+// 28. ESCROWED PRIVATE SALE
+// -------------------------
+// open_escrow(buyer: <buyer_pubkey>, price: 2_000_000_000)
+//   Accounts: domain_account, escrow PDA ["escrow", domain], authority
+//             (signer), system_program, governance, lock
+// complete_escrow()
+//   Accounts: domain_account, escrow, seller (mut), buyer (signer),
+//             system_program
+// cancel_escrow()
+//   Accounts: domain_account, escrow, seller (mut), signer (seller or buyer)
+//
+// Result: The escrow PDA holds the domain until the named buyer pays
+//
+// 29. LEASING
+// -----------
+// lease_domain(tenant: <tenant_pubkey>, duration: 30 * 86400, rent: 1_000_000_000)
+//   Accounts: domain_account, lease PDA ["lease", domain], authority
+//             (signer), tenant (signer), system_program, governance, lock
+// update_record (as tenant): signer = tenant, lease PDA passed as usual
+// end_lease()
+//   Accounts: domain_account, lease, lessor (mut), signer
+//
+// Result: Tenant controls the record for 30 days, then the owner again
+//
+// 30. TOP-LEVEL DOMAINS
+// ---------------------
+// register_tld(tld: "dao", owner: <owner_pubkey>)   (admin)
+//   Accounts: config, tld PDA ["tld", sha256("dao")], admin (signer),
+//             system_program
+// set_tld_policy(open: false, registrar: Some(<registrar_pubkey>))
+// transfer_tld(new_owner: <pubkey>) / set_tld_treasury(<pubkey>)
+//   Accounts: tld, owner (signer)
+// set_tld_revenue_share(share_bps: 2_000)   (admin; 20% of fees)
+//   Accounts: config, tld, admin (signer)
+// register_request("example.dao", ...) with tld = ["tld", sha256("dao")]
+//   and tld_approver = owner or registrar (signer) unless the TLD is open,
+//   plus tld_treasury while the TLD takes a share
+//
+// Result: Only the .dao owner decides who registers under .dao
+//
+// 31. REVERSE RESOLUTION
+// ----------------------
+// set_primary_domain()
+//   Accounts: domain_account, reverse PDA ["reverse", wallet], authority
+//             (signer), system_program
+// resolve_primary() -> Option<String>
+//   Accounts: reverse PDA, domain_account
+// clear_primary()
+//   Accounts: reverse PDA, owner (signer)
+//
+// Result: Wallets show "alice.neura" instead of a base58 address
+//
+// 32. REVERSE IP (PTR) INDEX
+// --------------------------
+// claim_ip_pointer()   (after registering "example.neura" -> 8.8.8.8)
+//   Accounts: domain_account, ip_pointers PDA ["ptr", [8, 8, 8, 8]],
+//             authority (signer), system_program
+// resolve_ip("8.8.8.8") -> Vec<Pubkey>
+//   Accounts: ip_pointers PDA
+// remove_ip_pointer("8.8.8.8")
+//   Accounts: ip_pointers PDA, domain_account, signer
+//
+// Result: Up to 16 domains listed per IP; stale ones prunable by anyone
+//
+// 33. WALLET RECORDS
+// ------------------
+// register_request("alice.neura", "<base58 pubkey>", RecordType::Sol, 3600)
+//   or add_record(RecordType::Sol, "<base58 pubkey>") next to an A record
+// resolve_wallet() -> Pubkey
+//   Accounts: domain_account, record_set PDA ["record", domain, 6] (optional)
+//
+// Result: "pay alice.neura" resolves to a wallet address
+//
+// 34. CROSS-CHAIN ADDRESSES
+// -------------------------
+// set_address(coin_type: 60, "0x5aAeb6053F3E94C9b9A09f33669435E7Ef1BeAed")
+// set_address(coin_type: 0, "bc1qw508d6qejxtdg4y5r3zarvary0c5xw7kv8f3t4")
+//   Accounts: domain_account, address_record PDA
+//             ["address", domain, coin_type LE], authority (signer),
+//             system_program
+// address_of(coin_type: 60) -> String
+//   Accounts: domain_account, address_record PDA
+//
+// Result: One name resolves to an address on every chain it lists
+//
+// 35. CONTENT HASH (DECENTRALIZED SITES)
+// --------------------------------------
+// register_request(
+//     "mysite.neura",
+//     "ipfs://bafybeigdyrzt5sfp7udm7hu76uh7y26nf3efuylqabf3oclgtqy55fbzdi",
+//     RecordType::ContentHash,
+//     3600
+// )
+// resolve_domain("mysite.neura") -> ResolvedRecord { CONTENTHASH, "ipfs://...", 3600 }
+//
+// Result: IPFS gateways can serve the site behind mysite.neura
+//
+// 36. PROFILE TEXT RECORDS
+// ------------------------
+// set_text(TextKey::Avatar, "ipfs://bafybeigdyrzt5sfp7udm7hu76uh7y26nf3efuylqabf3oclgtqy55fbzdi")
+// set_text(TextKey::Twitter, "alice")
+//   Accounts: domain_account, text_record PDA ["text", domain, key],
+//             authority (signer), system_program
+// text_of(TextKey::Twitter) -> String
+//   Accounts: domain_account, text_record PDA
+// remove_text(TextKey::Twitter)
+//   Accounts: domain_account, text_record PDA, governance, lock, timelock,
+//             authority (signer), config
+//
+// Result: Wallets and dApps render an identity card for alice.neura
+//
+// 37. SRV (SERVICE DISCOVERY)
+// ---------------------------
+// set_srv("_rpc", "_tcp", [
+//     SrvTarget { priority: 10, weight: 60, port: 8899, target: "rpc1.mynode.neura" },
+//     SrvTarget { priority: 10, weight: 40, port: 8899, target: "rpc2.mynode.neura" },
+// ], 300)
+//   Accounts: domain_account, srv_record PDA ["srv", domain, "_rpc", "_tcp"],
+//             authority (signer), system_program
+// resolve_srv("_rpc", "_tcp") -> ResolvedSrv { targets, ttl }
+//   Accounts: domain_account, srv_record PDA
+//
+// Result: _rpc._tcp.mynode.neura. 300 IN SRV 10 60 8899 rpc1.mynode.neura
+//
+// 38. MAIL EXCHANGERS
+// -------------------
+// add_record(Mx, "10 mx1.example.neura"), add_record(Mx, "20 mx2.example.neura")
+// resolve_mx() -> Vec<MxExchange { priority, exchange }>
+//   Accounts: domain_account, record_set PDA ["record", domain, Mx as u8] (optional)
+//
+// Result: [(10, "mx1.example.neura"), (20, "mx2.example.neura")], lowest first
+//
+// 39. NS DELEGATION
+// -----------------
+// update_record("ns1.example.com", Ns, 86400)
+// add_record(Ns, "ns2.example.com")
+// resolve_delegation() -> ["ns1.example.com", "ns2.example.com"]
+//   Accounts: domain_account, record_set PDA ["record", domain, Ns as u8] (optional)
+//
+// Result: The gateway's GET /resolve answers with a referral to these
+//         nameservers instead of an authoritative record
+//
+// 40. DNSSEC (DS / DNSKEY)
+// ------------------------
+// set_dnskey(257, 3, 13, "<128 hex chars: P-256 key>")
+// set_ds(2371, 13, 2, "<64 hex chars: SHA-256 digest>")
+//   Accounts: domain_account, dnssec_records PDA ["dnssec", domain],
+//             governance, lock, timelock, authority (signer),
+//             system_program, config
+// resolve_dnssec() -> ResolvedDnssec { ds, dnskeys }
+//
+// Result: The DNSKEY's computed key tag and the DS digest are what the
+//         operator publishes at their registrar to anchor the zone
+//
+// 41. PER-TYPE ACCESS CONTROL
+// ---------------------------
+// grant_record_types(ops_key, [A, Aaaa]) / revoke_record_types(ops_key)
+// Accounts: domain_account, record_acl PDA ["acl", domain],
+//           authority (signer), system_program (grant only)
+// update_record("203.0.113.7", A, 300) signed by ops_key, passing record_acl
+//
+// Result: ops_key may repoint A/AAAA but is refused (Unauthorized) while
+//         the primary record is, or would become, a SOL wallet
+//
+// 42. TIME-LOCKED UPDATES
+// -----------------------
+// set_update_delay(86400)
+//   Accounts: domain_account, timelock PDA ["timelock", domain],
+//             authority (signer), system_program
+// schedule_update("203.0.113.9", A, 300)
+//   Accounts: domain_account, pending_update PDA ["pending_update", domain],
+//             timelock, governance, lock, authority (signer), system_program
+// cancel_scheduled_update()        // owner, any time before it applies
+// execute_update()                 // anyone, 24h later
+//   Accounts: domain_account (mut), pending_update, record_history, lock,
+//             lease, authority, payer (signer), system_program
+// request_timelock_removal() -> remove_update_timelock() a delay later
+// add_record, remove_record, replace_records, set_address, set_text,
+// set_srv and unregister_domain take the timelock PDA and fail with
+// UpdateTimelocked while it is in force
+//
+// Result: ScheduledUpdateChanged fires a day before traffic moves
+//
+// 43. SOCIAL RECOVERY
+// -------------------
+// set_guardians([alice, bob, carol], 2)
+//   Accounts: domain_account, guardian_set PDA ["guardians", domain],
+//             governance, lock, authority (signer), system_program
+// start_recovery(new_key)          // signed by 2 of the 3 guardians
+//   Accounts: domain_account, guardian_set, recovery PDA ["recovery", domain],
+//             initiator (signer), system_program; guardians as remaining_accounts
+// veto_recovery()                  // owner, within 7 days
+// complete_recovery()              // anyone, after 7 days
+//   Accounts: domain_account (mut), recovery, initiator
+//
+// Result: Authority moves to new_key; DomainTransferred is emitted
+//
+// 44. INHERITANCE (DEAD-MAN SWITCH)
+// ---------------------------------
+// set_beneficiary(heir, 31536000)  // one year
+//   Accounts: domain_account, inheritance PDA ["inheritance", domain],
+//             governance, lock, authority (signer), system_program
+// heartbeat()                      // owner, any time; resets the clock
+// claim_inheritance()              // heir, after a year without a heartbeat
+//   Accounts: domain_account (mut), inheritance, beneficiary (signer)
+//
+// Result: Authority moves to the heir; DomainTransferred is emitted
+//
+// 45. FEATURE TOGGLES
+// -------------------
+// set_feature(Marketplace, false)  // admin; new listings and sales stop
+//   Accounts: config (mut), admin (signer)
+// list_for_sale(...)               // fails with FeatureDisabled
+// cancel_listing()                 // still works
+// set_feature(Marketplace, true)   // back on
+//
+// Result: Marketplace, Escrow, Leasing, Wrapping and Subdomains can be turned
+//         off without a program upgrade
+//
+// 46. EMERGENCY PAUSE
+// -------------------
+// set_paused(true)                 // admin, e.g. after finding a bug
+//   Accounts: config (mut), admin (signer)
+// register_request(...)            // fails with ProgramPaused
+// update_record(...)               // fails with ProgramPaused
+// resolve_domain("example.com")    // still works
+// set_paused(false)                // after the fix
+//
+// Registration (including subdomains), record writes (record sets,
+// addresses, text, SRV, DNSSEC, SSHFP, OpenPGP, DID, dynamic records) and
+// moves of a domain or its authority (transfers, marketplace, escrow,
+// wrapping, leases, recovery, inheritance, unregistering) take the config
+// PDA and check the flag; owner settings such as governance and locks don't
+//
+// Result: Writes halt without a program upgrade; reads are unaffected
+//
+// 47. ADMIN ROTATION
+// ------------------
+// nominate_admin(new_admin)        // current admin
+//   Accounts: config (mut), admin (signer)
+// accept_admin()                   // nominee
+//   Accounts: config (mut), new_admin (signer)
+//
+// Result: Admin changes only once the new key has signed; a typo can be
+//         corrected by nominating again
 //
 // ============================================================================
 // DEPLOYMENT INFORMATION